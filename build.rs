@@ -0,0 +1,345 @@
+// Generates the product kernels of R(3,0,1) from its Cayley table.
+//
+// Every element keeps its components in up to four partitions of four lanes:
+// planes in p0 (e0, e1, e2, e3), the scalar and Euclidean bivectors in p1
+// (1, e23, e31, e12), the pseudoscalar and ideal bivectors in p2 (e0123, e01,
+// e02, e03) and points in p3 (e123, e032, e013, e021). For every product and
+// every pair of partition sets used by the elements a kernel is written to
+// `$OUT_DIR/kernels.rs`, once as lane vector shuffles and once lane by lane in
+// `scalar`, generic over the `diff::Scalar` of the lanes. `gp_12_0` is the
+// geometric product of a motor and a plane, its arguments are the partitions
+// `a1, a2, b0` and it returns the non zero partitions of the result in order,
+// here `(p0, p3)`.
+//
+// The sandwiches of the motors (`12`), rotors (`1`), translators (`2`) and
+// planes (`0`) are written the same way: `sw_12_0_terms` computes the
+// coefficients of $MX\widetilde{M}$ that only depend on the versor, once per
+// batch, and `sw_12_0` applies them to a plane with a multiply-add per lane.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+// The basis blades in storage order as bitmasks of e0, e1, e2 and e3, with the
+// sign of the stored blade relative to the one with ascending indices.
+const BLADES:[(u8, i8); 16] = [
+  (0b0001, 1), (0b0010, 1), (0b0100, 1), (0b1000, 1),
+  (0b0000, 1), (0b1100, 1), (0b1010, -1), (0b0110, 1),
+  (0b1111, 1), (0b0011, 1), (0b0101, 1), (0b1001, 1),
+  (0b1110, 1), (0b1101, -1), (0b1011, 1), (0b0111, -1),
+];
+
+// Partitions of the planes, branches, horizons, points, lines and motors,
+// flectors and general multivectors.
+const OPERANDS:[&[usize]; 7] = [&[0], &[1], &[2], &[3], &[1, 2], &[0, 3], &[0, 1, 2, 3]];
+
+#[derive(Clone, Copy)]
+enum Product { Geometric, Outer, Regressive, Inner }
+
+impl Product {
+  fn name(self)->&'static str {
+    match self { Product::Geometric => "gp", Product::Outer => "ext", Product::Regressive => "reg", Product::Inner => "dot" }
+  }
+
+  // Index and sign of the product of the basis blades `i` and `j`.
+  fn blades(self, i:usize, j:usize)->Option<(usize, i8)> {
+    match self {
+      Product::Geometric => geometric(i, j),
+      Product::Outer => geometric(i, j).filter(|&(k, _)| grade(k) == grade(i) + grade(j)),
+      Product::Inner => geometric(i, j).filter(|&(k, _)| grade(k) == grade(i).abs_diff(grade(j))),
+      // The dual swaps p0 with p3 and p1 with p2 lane by lane, a & b = !(!a ^ !b).
+      Product::Regressive => Product::Outer.blades(i ^ 12, j ^ 12).map(|(k, s)| (k ^ 12, s)),
+    }
+  }
+}
+
+fn grade(i:usize)->u32 { BLADES[i].0.count_ones() }
+
+fn geometric(i:usize, j:usize)->Option<(usize, i8)> {
+  let ((a, sa), (b, sb)) = (BLADES[i], BLADES[j]);
+  if a & b & 1 != 0 { return None }
+  let k = BLADES.iter().position(|&(m, _)| m == a ^ b).unwrap();
+  Some((k, sa * sb * BLADES[k].1 * reordering(a, b)))
+}
+
+// Sign of moving the vectors of `b` past the vectors of `a` with higher indices.
+fn reordering(a:u8, b:u8)->i8 {
+  let (mut a, mut swaps) = (a >> 1, 0);
+  while a != 0 {
+    swaps += (a & b).count_ones();
+    a >>= 1;
+  }
+  if swaps % 2 == 0 { 1 } else { -1 }
+}
+
+// The lane `l` of partition `p` of the left and right operand and the sign of
+// their product.
+#[derive(Clone, Copy)]
+struct Term { pa:usize, la:usize, pb:usize, lb:usize, sign:i8 }
+
+struct Kernel { name:String, a:Vec<usize>, b:Vec<usize>, out:Vec<(usize, [Vec<Term>; 4])> }
+
+fn kernel(product:Product, a:&[usize], b:&[usize])->Option<Kernel> {
+  let mut out:Vec<(usize, [Vec<Term>; 4])> = (0..4).map(|p| (p, Default::default())).collect();
+  for &pa in a { for la in 0..4 { for &pb in b { for lb in 0..4 {
+    if let Some((k, sign)) = product.blades(4*pa + la, 4*pb + lb) {
+      out[k / 4].1[k % 4].push(Term{pa, la, pb, lb, sign});
+    }
+  }}}}
+  out.retain(|(_, lanes)| lanes.iter().any(|l| !l.is_empty()));
+  if out.is_empty() { return None }
+  Some(Kernel{name: format!("{}_{}_{}", product.name(), digits(a), digits(b)), a: a.to_vec(), b: b.to_vec(), out})
+}
+
+fn signature(k:&Kernel, generics:&str, vector:&str)->String {
+  let args = k.a.iter().map(|p| format!("a{}:&{}", p, vector)).chain(k.b.iter().map(|p| format!("b{}:&{}", p, vector)));
+  let ret = if k.out.len() == 1 { vector.to_string() } else { format!("({})", vec![vector; k.out.len()].join(", ")) };
+  format!("pub fn {}{}({})->{}", k.name, generics, args.collect::<Vec<_>>().join(", "), ret)
+}
+
+fn comment(k:&Kernel)->String {
+  let parts = |s:&[usize]| s.iter().map(|p| format!("p{}", p)).collect::<Vec<_>>().join(" + ");
+  format!("// {} of ({}) and ({}) into ({})\n", k.name, parts(&k.a), parts(&k.b), parts(&k.out.iter().map(|o| o.0).collect::<Vec<_>>()))
+}
+
+fn tuple(parts:Vec<String>)->String { if parts.len() == 1 { parts[0].clone() } else { format!("({})", parts.join(", ")) } }
+
+fn swizzle(name:String, lanes:[usize; 4])->String {
+  if lanes == [0, 1, 2, 3] { format!("*{}", name) } else { format!("swizzle4!(*{}, {:?})", name, lanes) }
+}
+
+// Each lane vector product takes one term of every lane of the output, lanes
+// that ran out of terms are multiplied by zero.
+fn simd(k:&Kernel)->String {
+  let parts = k.out.iter().map(|(p, lanes)| {
+    let mut expr = String::new();
+    for &pa in &k.a { for &pb in &k.b {
+      let mut lanes:Vec<Vec<Term>> = lanes.iter().map(|l| l.iter().filter(|t| t.pa == pa && t.pb == pb).copied().collect()).collect();
+      while lanes.iter().any(|l| !l.is_empty()) {
+        let terms:Vec<Option<Term>> = lanes.iter_mut().map(|l| if l.is_empty() { None } else { Some(l.remove(0)) }).collect();
+        let la = [0, 1, 2, 3].map(|i| terms[i].map_or(i, |t| t.la));
+        let lb = [0, 1, 2, 3].map(|i| terms[i].map_or(i, |t| t.lb));
+        let signs = [0, 1, 2, 3].map(|i| terms[i].map_or(0, |t| t.sign));
+        let product = format!("{} * {}", swizzle(format!("a{}", pa), la), swizzle(format!("b{}", pb), lb));
+        let (op, term) = if signs == [1; 4] { ("+", product) }
+          else if signs == [-1; 4] { ("-", product) }
+          else { ("+", format!("{} * Lanes::from_array({:?})", product, signs.map(|s| s as f64))) };
+        match (expr.is_empty(), op) {
+          (true, "+") => expr = term,
+          (true, _) => expr = format!("-({})", term),
+          _ => write!(expr, "\n    {} {}", op, term).unwrap(),
+        }
+      }
+    }}
+    format!("  let p{} = {};\n", p, expr)
+  });
+  let names = k.out.iter().map(|(p, _)| format!("p{}", p)).collect();
+  format!("{}{} {{\n{}  {}\n}}\n", comment(k), signature(k, "", "Lanes"), parts.collect::<String>(), tuple(names))
+}
+
+fn scalar(k:&Kernel)->String {
+  let parts = k.out.iter().map(|(p, lanes)| {
+    let lanes = lanes.iter().map(|terms| {
+      let mut expr = String::new();
+      for (i, t) in terms.iter().enumerate() {
+        let product = format!("a{}[{}]*b{}[{}]", t.pa, t.la, t.pb, t.lb);
+        match (i, t.sign) {
+          (0, 1) => expr = product,
+          (0, _) => expr = format!("-{}", product),
+          (_, 1) => write!(expr, " + {}", product).unwrap(),
+          _ => write!(expr, " - {}", product).unwrap(),
+        }
+      }
+      if expr.is_empty() { "S::zero()".to_string() } else { expr }
+    });
+    format!("    let p{} = [\n      {}];\n", p, lanes.collect::<Vec<_>>().join(",\n      "))
+  });
+  let names = k.out.iter().map(|(p, _)| format!("p{}", p)).collect();
+  format!("  {}  {} {{\n{}    {}\n  }}\n", comment(k), signature(k, "<S:Scalar>", "[S;4]"), parts.collect::<String>(), tuple(names))
+}
+
+// The versors of the sandwiches $VX\widetilde{V}$ by the partitions and lanes
+// they take: motors, rotors, translators with their implicit scalar of one and
+// planes, the reflections.
+struct Versor { parts:&'static [(usize, &'static [usize])], one:bool }
+
+const VERSORS:[Versor; 4] = [
+  Versor{parts: &[(1, &[0, 1, 2, 3]), (2, &[0, 1, 2, 3])], one: false},
+  Versor{parts: &[(1, &[0, 1, 2, 3])], one: false},
+  Versor{parts: &[(2, &[1, 2, 3])], one: true},
+  Versor{parts: &[(0, &[0, 1, 2, 3])], one: false},
+];
+
+// The elements `X` of the sandwiches by their partitions and lanes, planes,
+// lines and points. Lines leave out the scalar and the pseudoscalar.
+const SANDWICHES:[&[(usize, &[usize])]; 3] = [&[(0, &[0, 1, 2, 3])], &[(1, &[1, 2, 3]), (2, &[1, 2, 3])], &[(3, &[0, 1, 2, 3])]];
+
+// The implicit scalar of a translator, a blade that is always one.
+const ONE:usize = 16;
+
+// The output blade of a sandwich is linear in the input blade with a
+// coefficient quadratic in the versor.
+struct Sandwich { name:String, versor:Vec<usize>, one:bool, input:Vec<(usize, Vec<usize>)>, columns:Vec<(usize, usize, [Quadratic; 4])> }
+
+// Signed products of two versor blades.
+type Quadratic = Vec<(usize, usize, i32)>;
+
+// The sign of the reverse of blade `i`.
+fn reverse(i:usize)->i32 { if grade(i) % 4 < 2 { 1 } else { -1 } }
+
+fn digits(s:&[usize])->String { s.iter().map(|p| p.to_string()).collect() }
+
+fn sandwich(v:&Versor, input:&[(usize, &[usize])])->Sandwich {
+  let mut blades:Vec<usize> = v.parts.iter().flat_map(|&(p, lanes)| lanes.iter().map(move |l| 4*p + l)).collect();
+  if v.one { blades.push(ONE) }
+  let product = |i:usize, j:usize| if i == ONE { Some((j, 1)) } else if j == ONE { Some((i, 1)) } else { geometric(i, j) };
+  let mut columns = vec![];
+  for &(pb, lanes) in input { for &lb in lanes {
+    let j = 4*pb + lb;
+    // The coefficient of every output blade as pairs of versor blades.
+    let mut out:Vec<Quadratic> = vec![vec![]; 16];
+    for &i in &blades { for &k in &blades {
+      let Some((o, s1)) = product(i, j) else { continue };
+      let Some((o, s2)) = product(o, k) else { continue };
+      let sign = (s1 * s2) as i32 * if k == ONE { 1 } else { reverse(k) };
+      let (i, k) = (i.min(k), i.max(k));
+      match out[o].iter_mut().find(|t| t.0 == i && t.1 == k) { Some(t) => t.2 += sign, None => out[o].push((i, k, sign)) }
+    }}
+    for terms in out.iter_mut() { terms.retain(|t| t.2 != 0) }
+    for po in 0..4 {
+      let lanes:[Quadratic; 4] = std::array::from_fn(|l| out[4*po + l].clone());
+      if lanes.iter().any(|l| !l.is_empty()) { columns.push((4*pb + lb, po, lanes)) }
+    }
+  }}
+  let versor:Vec<usize> = v.parts.iter().map(|(p, _)| *p).collect();
+  let name = format!("sw_{}_{}", digits(&versor), digits(&input.iter().map(|(p, _)| *p).collect::<Vec<_>>()));
+  Sandwich{name, versor, one: v.one, input: input.iter().map(|(p, l)| (*p, l.to_vec())).collect(), columns}
+}
+
+// The polynomial of a lane in the versor, `zero` when it vanishes.
+fn polynomial(terms:&[(usize, usize, i32)], zero:&str, scale:&dyn Fn(i32)->String)->String {
+  let mut expr = String::new();
+  for (n, &(i, k, c)) in terms.iter().enumerate() {
+    let mut factors:Vec<String> = [i, k].iter().filter(|&&b| b != ONE).map(|b| format!("a{}[{}]", b / 4, b % 4)).collect();
+    if c.abs() != 1 || factors.is_empty() { factors.insert(0, scale(c.abs())) }
+    let product = factors.join("*");
+    match (n, c > 0) {
+      (0, true) => expr = product,
+      (0, false) => expr = format!("-{}", product),
+      (_, true) => write!(expr, " + {}", product).unwrap(),
+      _ => write!(expr, " - {}", product).unwrap(),
+    }
+  }
+  if expr.is_empty() { zero.to_string() } else { expr }
+}
+
+fn sandwich_comment(w:&Sandwich)->String {
+  let parts = |s:&[usize]| s.iter().map(|p| format!("p{}", p)).collect::<Vec<_>>().join(" + ");
+  let versor = if w.one { format!("1 + {}", parts(&w.versor)) } else { parts(&w.versor) };
+  format!("// {} of the versor ({}) around ({}), with one term for every input lane and output partition\n",
+    w.name, versor, parts(&w.input.iter().map(|(p, _)| *p).collect::<Vec<_>>()))
+}
+
+fn outputs(w:&Sandwich)->Vec<usize> { (0..4).filter(|p| w.columns.iter().any(|c| c.1 == *p)).collect() }
+
+fn versor_args(w:&Sandwich, vector:&str)->String { w.versor.iter().map(|p| format!("a{}:&{}", p, vector)).collect::<Vec<_>>().join(", ") }
+
+// The terms only depend on the versor and are computed once for a batch, the
+// sandwich of every element then multiplies and adds them lane by lane.
+fn simd_sandwich(w:&Sandwich)->String {
+  let n = w.columns.len();
+  let terms = w.columns.iter().map(|(_, _, lanes)| {
+    format!("Lanes::from_array([{}])", lanes.iter().map(|l| polynomial(l, "0.0", &|c| format!("{}.0", c))).collect::<Vec<_>>().join(", "))
+  }).collect::<Vec<_>>().join(",\n    ");
+  let mut src = format!("{}pub fn {}_terms({})->[Lanes; {}] {{\n  [{}]\n}}\n\n", sandwich_comment(w), w.name, versor_args(w, "Lanes"), n, terms);
+  let args = w.input.iter().map(|(p, _)| format!("b{}:&Lanes", p)).collect::<Vec<_>>().join(", ");
+  let outs = outputs(w);
+  let parts = outs.iter().map(|po| {
+    let sum = w.columns.iter().enumerate().filter(|(_, c)| c.1 == *po)
+      .map(|(t, c)| format!("t[{}] * swizzle4!(*b{}, [{l},{l},{l},{l}])", t, c.0 / 4, l = c.0 % 4)).collect::<Vec<_>>().join("\n    + ");
+    format!("  let p{} = {};\n", po, sum)
+  }).collect::<String>();
+  let ret = if outs.len() == 1 { "Lanes".to_string() } else { format!("({})", vec!["Lanes"; outs.len()].join(", ")) };
+  writeln!(src, "pub fn {}(t:&[Lanes; {}], {})->{} {{\n{}  {}\n}}", w.name, n, args, ret, parts, tuple(outs.iter().map(|p| format!("p{}", p)).collect())).unwrap();
+  src
+}
+
+// The sandwich lane by lane, the reference for the terms.
+fn scalar_sandwich(w:&Sandwich)->String {
+  let args = w.input.iter().map(|(p, _)| format!("b{}:&[S;4]", p)).collect::<Vec<_>>().join(", ");
+  let outs = outputs(w);
+  let parts = outs.iter().map(|po| {
+    let lanes = (0..4).map(|l| {
+      let sum = w.columns.iter().filter(|c| c.1 == *po && !c.2[l].is_empty())
+        .map(|c| format!("({})*b{}[{}]", polynomial(&c.2[l], "", &|c| format!("S::from_f64({}.0)", c)), c.0 / 4, c.0 % 4)).collect::<Vec<_>>();
+      if sum.is_empty() { "S::zero()".to_string() } else { sum.join(" + ") }
+    }).collect::<Vec<_>>().join(",\n      ");
+    format!("    let p{} = [\n      {}];\n", po, lanes)
+  }).collect::<String>();
+  let ret = if outs.len() == 1 { "[S;4]".to_string() } else { format!("({})", vec!["[S;4]"; outs.len()].join(", ")) };
+  format!("  {}  pub fn {}<S:Scalar>({}, {})->{} {{\n{}    {}\n  }}\n",
+    sandwich_comment(w), w.name, versor_args(w, "[S;4]"), args, ret, parts, tuple(outs.iter().map(|p| format!("p{}", p)).collect()))
+}
+
+// The terms agree with the lane by lane sandwich and with the two geometric
+// products $VX$ and then $(VX)\widetilde{V}$, a translator taking part as the
+// motor with a scalar of one.
+fn sandwich_test(w:&Sandwich)->String {
+  let outs = outputs(w);
+  let parts = (0..outs.len()).map(|i| if outs.len() == 1 { "x".to_string() } else { format!("x.{}", i) }).collect::<Vec<_>>().join(", ");
+  let array = |p:usize, lanes:&[usize], sign:&dyn Fn(usize)->i32| format!("[{}]", (0..4).map(|l| if lanes.contains(&l) {
+    format!("{}INPUTS[{}][{}]", if sign(4*p + l) < 0 { "-" } else { "" }, p, l) } else { "0.0".to_string() }).collect::<Vec<_>>().join(", "));
+  let input = w.input.iter().map(|(p, lanes)| array(*p, lanes, &|_| 1)).collect::<Vec<_>>();
+  let lanes = input.iter().map(|x| format!("&Lanes::from_array({})", x)).collect::<Vec<_>>().join(", ");
+  let scalars = input.iter().map(|x| format!("&{}", x)).collect::<Vec<_>>().join(", ");
+  let versor = VERSORS.iter().find(|v| v.one == w.one && v.parts.iter().map(|(p, _)| *p).eq(w.versor.iter().copied())).unwrap();
+  let versor_array = |sign:&dyn Fn(usize)->i32| versor.parts.iter().map(|&(p, lanes)| (p, array(p, lanes, sign))).collect::<Vec<_>>();
+  let v = versor_array(&|_| 1);
+  let (mut m, mut r) = (v.clone(), versor_array(&reverse));
+  if w.one { m.insert(0, (1, "[1.0, 0.0, 0.0, 0.0]".to_string())); r.insert(0, (1, "[1.0, 0.0, 0.0, 0.0]".to_string())) }
+  let refs = |x:&[(usize, String)]| x.iter().map(|(_, a)| format!("&{}", a)).collect::<Vec<_>>().join(", ");
+  let m_parts = m.iter().map(|(p, _)| *p).collect::<Vec<_>>();
+  let mx = kernel(Product::Geometric, &m_parts, &w.input.iter().map(|(p, _)| *p).collect::<Vec<_>>()).unwrap();
+  let mx_parts = mx.out.iter().map(|o| o.0).collect::<Vec<_>>();
+  let mxm = kernel(Product::Geometric, &mx_parts, &m_parts).unwrap();
+  let mut src = format!("    let x = super::{n}(&super::{n}_terms({}), {}); let x = [{}];\n",
+    v.iter().map(|(_, a)| format!("&Lanes::from_array({})", a)).collect::<Vec<_>>().join(", "), lanes,
+    (0..outs.len()).map(|i| if outs.len() == 1 { "x.to_array()".to_string() } else { format!("x.{}.to_array()", i) }).collect::<Vec<_>>().join(", "), n = w.name);
+  writeln!(src, "    assert_eq!(x, {{ let x = scalar::{}({}, {}); [{}] }}, \"{}\");", w.name, refs(&v), scalars, parts, w.name).unwrap();
+  let mx_names = mx_parts.iter().map(|p| format!("y{}", p)).collect::<Vec<_>>();
+  writeln!(src, "    let {} = scalar::{}({}, {});", tuple(mx_names.clone()), mx.name, refs(&m), scalars).unwrap();
+  let mxm_out = mxm.out.iter().map(|o| format!("z{}", o.0)).collect::<Vec<_>>();
+  writeln!(src, "    let {} = scalar::{}({}, {});", tuple(mxm_out), mxm.name, mx_names.iter().map(|y| format!("&{}", y)).collect::<Vec<_>>().join(", "), refs(&r)).unwrap();
+  writeln!(src, "    assert_eq!(x, [{}], \"{} against {} and {}\");", outs.iter().map(|p| format!("z{}", p)).collect::<Vec<_>>().join(", "), w.name, mx.name, mxm.name).unwrap();
+  src
+}
+
+// Both versions of every kernel agree on inputs that multiply and add without
+// rounding, the right operand takes the inputs in reverse order.
+fn test(k:&Kernel)->String {
+  let inputs = |wrap:fn(usize)->String| k.a.iter().map(|&p| wrap(p)).chain(k.b.iter().map(|&p| wrap(3 - p))).collect::<Vec<_>>().join(", ");
+  let (lanes, scalar) = (inputs(|i| format!("&Lanes::from_array(INPUTS[{}])", i)), inputs(|i| format!("&INPUTS[{}]", i)));
+  let parts = (0..k.out.len()).map(|i| if k.out.len() == 1 { "x".to_string() } else { format!("x.{}", i) }).collect::<Vec<_>>();
+  format!("    let x = super::{}({}); let x = [{}];\n    assert_eq!(x, {{ let x = scalar::{}({}); [{}] }}, \"{}\");\n",
+    k.name, lanes, parts.iter().map(|x| format!("{}.to_array()", x)).collect::<Vec<_>>().join(", "), k.name, scalar, parts.join(", "), k.name)
+}
+
+fn main() {
+  let kernels:Vec<Kernel> = [Product::Geometric, Product::Outer, Product::Regressive, Product::Inner].iter()
+    .flat_map(|&p| OPERANDS.iter().flat_map(move |a| OPERANDS.iter().filter_map(move |b| kernel(p, a, b))))
+    .collect();
+  let mut src = String::from("// Generated by build.rs from the Cayley table of R(3,0,1), do not edit.\n\nuse super::*;\n\n");
+  let sandwiches:Vec<Sandwich> = VERSORS.iter().flat_map(|v| SANDWICHES.iter().map(move |x| sandwich(v, x))).collect();
+  for k in &kernels { src += &simd(k); src += "\n"; }
+  for w in &sandwiches { src += &simd_sandwich(w); src += "\n"; }
+  src += "pub mod scalar {\n  use crate::diff::Scalar;\n\n";
+  for k in &kernels { src += &scalar(k); src += "\n"; }
+  for w in &sandwiches { src += &scalar_sandwich(w); src += "\n"; }
+  src += "}\n\n#[cfg(test)]\nmod tests {\n  use super::*;\n\n";
+  src += "  const INPUTS:[[Float;4];4] = [[1.0, -2.0, 0.5, 3.0], [-1.5, 2.0, 4.0, -0.25], [0.75, 1.0, -3.0, 2.0], [2.0, 0.5, -1.0, 1.5]];\n\n";
+  src += "  #[test] fn lanes_match_scalar() {\n";
+  for k in &kernels { src += &test(k); }
+  src += "  }\n\n  #[test] fn sandwiches_match_products() {\n";
+  for w in &sandwiches { src += &sandwich_test(w); }
+  src += "  }\n}\n";
+  fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("kernels.rs"), src).unwrap();
+  println!("cargo:rerun-if-changed=build.rs");
+}
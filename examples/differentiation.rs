@@ -1,41 +1,28 @@
-// use g3::prelude::*;
-//
-// fn align(p:Point, q:Point)->Translator {
-//   (q.normalized()/p.normalized()).sqrt()
-// }
-// fn steps(n:u32)->impl Iterator<Item = f32> {
-//   (0..n).map(move|i| i as f32/(n as f32-1.0))
-// }
-// fn lerp(m:Translator, f:f32)->Translator {
-//   (m*f) //.normalized(); TODO normalized is broken
-// }
-// fn path(m:Translator, n:u32, x:Point)->impl Iterator<Item = Point> {
-//   steps(n).map(move|f|lerp(m, f)(x))
-// }
-//
-// const N:u32 = 8;
-//
-// fn main() {
-//   App::new()
-//     .add_plugin(PlotPlugin)
-//     .add_startup_system(|mut cmd: Cmd| {
-//       let (a,b,d) = (point(-1.0,1.0,0.0), point(1.0,1.0,0.0), point(-1.0,-1.0,0.0));
-//       let ad = align(a,d);
-//       for i in 0..N {
-//         let down = lerp(ad, i as f32/(7.0));
-//         let p = down(a);
-//         let q = down(b);
-//         let pq = align(down(a), q);
-//         path(pq, N, p).for_each(|p|{cmd.spawn_bundle((p, Color::CYAN));});
-//       }})
-//     .add_system(|time: Res<Time>, mut q:Q<(&mut Point, &Color)>|{
-//       let t = time.seconds_since_startup() as f32;
-//       for (mut p,_) in q.iter_mut() {
-//         let x = p.x(); let y = p.y();
-//         let z = 0.5*(t*5.0).sin()*x*x*x-0.5*t.cos()*y*y;
-//         *p = point(x, y, z);
-//       }
-//     })
-//     .run();
-// }
-fn main() {}
\ No newline at end of file
+// Fit a motor to point correspondences by gradient descent, the gradient of
+// the squared distances comes from forward mode differentiation through the
+// motor. Every step moves the current motor by exp(δ), with the six
+// components of the line δ as the variables at zero. The step stays below two
+// over the curvature of the error, which grows with the distances of the points.
+use g3::{*, diff::Jet};
+
+fn error(m:Motor, pairs:&[(Point, Point)])->Jet<6> {
+  let [a, b, c, d, e, f] = Jet::variables([0.0; 6]);
+  let m = diff::line(a, b, c, d, e, f).exp() * diff::Motor::from(m);
+  pairs.iter().fold(Jet::constant(0.0), |sum, &(p, q)| {
+    sum + m.apply(diff::Point::from(p)).squared_distance(diff::Point::from(q))
+  })
+}
+
+fn main() {
+  let truth = rotor(pi / 3.0, 1.0, 2.0, 0.5) * translator(1.5, 0.0, 1.0, -1.0);
+  let points = [point(1.0, 0.0, 0.0), point(0.0, 2.0, 0.0), point(0.0, 0.0, -1.0), point(1.0, 1.0, 1.0), point(-2.0, 0.5, 1.0)];
+  let pairs = points.map(|p| (p, truth.apply(p)));
+  let mut m = Motor::from(rotor(0.0, 0.0, 0.0, 1.0));
+  for step in 0..300 {
+    let e = error(m, &pairs);
+    if step % 30 == 0 { println!("{:3} {:.6}", step, e.value) }
+    let [a, b, c, d, f, g] = e.grad.map(|x| -0.005 * x as f32);
+    m = (line(a, b, c, d, f, g).exp() * m).normalized();
+  }
+  println!("fitted {:.4}\ntruth  {:.4}", m, truth);
+}
@@ -0,0 +1,194 @@
+use super::{Motor, Rotor, Line, maths::*};
+
+/// How a [`Track`] fills in the time between two keyframes.
+#[derive(Default,Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Interpolation {
+  /// Hold the value of the previous keyframe.
+  Step,
+  /// Constant speed along the screw (or arc) between two keyframes.
+  #[default]
+  Linear,
+  /// Smooth curve through every keyframe. Rotors use a squad spline and
+  /// motors a Catmull-Rom curve in the Lie algebra of lines. The tangents of
+  /// the motor curve follow the key times, the squad control rotors assume
+  /// evenly spaced keys.
+  Cubic,
+}
+
+/// A sequence of keyframes sorted by time.
+///
+/// ```
+/// use g3::{Track, Interpolation, Motor, translator};
+/// let mut track = Track::new(Interpolation::Cubic);
+/// track.insert(0.0, Motor::one());
+/// track.insert(1.0, Motor::from(translator(2.0, 1.0, 0.0, 0.0)));
+/// let m = track.sample(0.5);
+/// ```
+#[derive(Default,Debug,Clone,PartialEq)]
+pub struct Track<T> {
+  keys: Vec<(Float, T)>,
+  interpolation: Interpolation,
+}
+
+impl<T> Track<T> {
+  pub fn new(interpolation:Interpolation)->Track<T> { Track{keys: Vec::new(), interpolation} }
+
+  /// Insert a keyframe, an existing keyframe at the same time is replaced.
+  pub fn insert(&mut self, time:Float, value:T) {
+    let i = self.keys.partition_point(|(t, _)| *t < time);
+    if self.keys.get(i).is_some_and(|(t, _)| *t == time) { self.keys[i].1 = value } else { self.keys.insert(i, (time, value)) }
+  }
+
+  pub fn keys(&self)->&[(Float, T)] { &self.keys }
+  pub fn len(&self)->usize { self.keys.len() }
+  pub fn is_empty(&self)->bool { self.keys.is_empty() }
+  pub fn interpolation(&self)->Interpolation { self.interpolation }
+  pub fn set_interpolation(&mut self, interpolation:Interpolation) { self.interpolation = interpolation }
+
+  /// Time of the first and last keyframe.
+  pub fn range(&self)->Option<(Float, Float)> { Some((self.keys.first()?.0, self.keys.last()?.0)) }
+
+  // The segment `k..k+1` that contains `t` and how far into it `t` is, `t` is
+  // clamped to the keyed range. Needs at least two keys.
+  fn segment(&self, t:Float)->(usize, Float) {
+    let k = self.keys.partition_point(|(s, _)| *s <= t).clamp(1, self.keys.len() - 1) - 1;
+    let (t0, t1) = (self.keys[k].0, self.keys[k+1].0);
+    (k, ((t - t0) / (t1 - t0)).clamp(0.0, 1.0))
+  }
+
+  // Tangent scale of the cubic between keys `a` and `b` for a segment starting at `k`.
+  fn span(&self, k:usize, a:usize, b:usize)->Float {
+    (self.keys[k+1].0 - self.keys[k].0) / (self.keys[b].0 - self.keys[a].0)
+  }
+}
+
+impl<T> FromIterator<(Float, T)> for Track<T> {
+  fn from_iter<I:IntoIterator<Item=(Float, T)>>(iter:I)->Self {
+    let mut track = Track::new(Interpolation::default());
+    for (t, v) in iter { track.insert(t, v) }
+    track
+  }
+}
+
+impl Track<Motor> {
+  /// Sample the track at time `t`, outside of the keyed range the first or
+  /// last keyframe is held. An empty track samples to the identity. The
+  /// keyframes must be normalized, so is the result.
+  pub fn sample(&self, t:Float)->Motor {
+    match self.keys.len() {
+      0 => return Motor::one(),
+      1 => return self.keys[0].1,
+      _ => {}
+    }
+    let (k, u) = self.segment(t);
+    let (a, b) = (self.keys[k].1, self.keys[k+1].1);
+    match self.interpolation {
+      Interpolation::Step => if u < 1.0 { a } else { b },
+      Interpolation::Linear => a.slerp(b, u),
+      Interpolation::Cubic => self.cubic(k, u),
+    }
+  }
+
+  // Hermite curve through the logarithms of the keys relative to key `k`, the
+  // tangents are finite differences of the neighbouring keys.
+  fn cubic(&self, k:usize, u:Float)->Motor {
+    let m = self.keys[k].1;
+    let last = self.keys.len() - 1;
+    let log = |i:usize| if i == k { Line::default() } else { (m.shortest(self.keys[i].1) * m.reverse()).log() };
+    let tangent = |i:usize| {
+      let (a, b) = (i.saturating_sub(1), (i + 1).min(last));
+      (log(b) - log(a)) * self.span(k, a, b)
+    };
+    let (u2, u3) = (u*u, u*u*u);
+    let l = log(k+1) * (3.0*u2 - 2.0*u3) + tangent(k) * (u3 - 2.0*u2 + u) + tangent(k+1) * (u3 - u2);
+    (l.exp() * m).normalized()
+  }
+}
+
+impl Track<Rotor> {
+  /// Sample the track at time `t`, outside of the keyed range the first or
+  /// last keyframe is held. An empty track samples to the identity. The
+  /// keyframes must be normalized, so is the result. A cubic track passes
+  /// through every key but only eases evenly when the key times are uniform.
+  pub fn sample(&self, t:Float)->Rotor {
+    match self.keys.len() {
+      0 => return Rotor::one(),
+      1 => return self.keys[0].1,
+      _ => {}
+    }
+    let (k, u) = self.segment(t);
+    let (a, b) = (self.keys[k].1, self.keys[k+1].1);
+    match self.interpolation {
+      Interpolation::Step => if u < 1.0 { a } else { b },
+      Interpolation::Linear => a.slerp(b, u),
+      Interpolation::Cubic => a.slerp(b, u).slerp(self.control(k).slerp(self.control(k+1), u), 2.0*u*(1.0 - u)),
+    }
+  }
+
+  // Inner control rotor of squad at key `i`, the end keys are their own control.
+  // The logarithm goes through `Motor` which is well defined at the identity.
+  fn control(&self, i:usize)->Rotor {
+    let q = self.keys[i].1;
+    if i == 0 || i == self.keys.len() - 1 { return q }
+    let log = |r:Rotor| (Motor::from(q.shortest(r)) * Motor::from(q.reverse())).log();
+    Rotor(((log(self.keys[i-1].1) + log(self.keys[i+1].1)) * -0.25).exp().p1) * q
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  #[test] fn track_keys() {
+    let mut track:Track<Motor> = [(1.0, Motor::one()), (0.0, Motor::one())].into_iter().collect();
+    track.insert(0.5, Motor::one());
+    track.insert(1.0, -Motor::one());
+    assert_eq!(track.keys().iter().map(|k| k.0).collect::<Vec<_>>(), [0.0, 0.5, 1.0]);
+    assert_eq!(track.keys()[2].1, -Motor::one());
+    assert_eq!(track.range(), Some((0.0, 1.0)));
+    assert_eq!(Track::<Motor>::default().sample(3.0), Motor::one());
+  }
+
+  #[test] fn track_step_linear() {
+    let m = Motor::from_screw_axis(pi/2.0, 2.0, screw_axis());
+    let mut track = Track::new(Interpolation::Step);
+    track.insert(1.0, Motor::one());
+    track.insert(3.0, m);
+    assert_eq!(track.sample(2.9), Motor::one());
+    assert_eq!(track.sample(3.0), m);
+    assert_eq!(track.sample(-1.0), Motor::one());
+    track.set_interpolation(Interpolation::Linear);
+    close(track.sample(1.5), Motor::one().slerp(m, 0.25));
+    close(track.sample(9.0), m);
+  }
+
+  #[test] fn track_cubic_motor() {
+    // Keys at constant speed along one screw, the spline follows that screw.
+    let l = screw_axis();
+    let mut track:Track<Motor> = (0..4).map(|i| (i as Float, Motor::from_screw_axis(0.5*i as Float, 0.3*i as Float, l))).collect();
+    track.set_interpolation(Interpolation::Cubic);
+    for t in [0.0, 0.4, 1.0, 1.5, 2.25, 3.0] {
+      let m = track.sample(t);
+      close(m, Motor::from_screw_axis(0.5*t, 0.3*t, l));
+      close(m * m.reverse(), Motor::one());
+    }
+    // Through every key when the keys are irregular.
+    let keys = [(0.0, Motor::one()), (0.3, Motor::from_screw_axis(1.0, 0.0, l)), (2.0, Motor::from(translator(1.0, 0.0, 1.0, 0.0)))];
+    let mut track:Track<Motor> = keys.into_iter().collect();
+    track.set_interpolation(Interpolation::Cubic);
+    for (t, m) in keys { close(track.sample(t), m) }
+  }
+
+  #[test] fn track_cubic_rotor() {
+    let mut track = Track::new(Interpolation::Cubic);
+    for i in 0..4 { track.insert(i as Float, rotor(0.4*i as Float, 1.0, -2.0, 0.5)) }
+    for t in [0.0, 0.5, 1.2, 2.0, 2.7] {
+      close(track.sample(t), rotor(0.4*t, 1.0, -2.0, 0.5));
+    }
+    track.insert(4.0, rotor(2.0, 0.0, 1.0, 0.0));
+    close(track.sample(4.0), rotor(2.0, 0.0, 1.0, 0.0));
+    let r = track.sample(3.5);
+    close(r * r.reverse(), Rotor::one());
+  }
+}
@@ -0,0 +1,104 @@
+// The vector types come from one of two interchangeable backends, portable
+// SIMD when the `simd` feature is enabled, plain arrays otherwise.
+#[cfg(feature = "simd")] mod simd;
+#[cfg(feature = "simd")] pub use simd::*;
+#[cfg(any(not(feature = "simd"), test))] mod scalar;
+#[cfg(not(feature = "simd"))] pub use scalar::*;
+/// The single precision scalar of the components.
+pub type Float = f32;
+/// An unsigned 32-bit integer, for the bits of a single precision lane.
+pub type Bits = u32;
+/// Four single precision lanes, the storage of the elements.
+pub type Lanes = f32x4;
+/// Two single precision lanes, the storage of a dual number.
+pub type Lanes2 = f32x2;
+/// The bits of four single precision lanes.
+pub type LaneBits = u32x4;
+/// A mask over four single precision lanes.
+pub type Mask = mask32x4;
+// The glam types of the same precision, for the conversions behind the `glam` feature.
+#[cfg(feature = "glam")] pub use glam::{Vec3, Vec4, Quat, Mat3, Mat4, Affine3A};
+
+/// The double precision scalar and lanes under the same names. The kernels and
+/// elements are compiled a second time against these for `crate::double`.
+pub mod wide {
+  pub use super::{f64x2, f64x4, u64x4, mask64x4, b2b3a2a3, b0a1a2a3};
+  pub(crate) use super::swizzle4;
+  #[cfg(feature = "simd")] pub use super::{SimdFloat, StdFloat, Select};
+  #[cfg(not(feature = "simd"))] pub use super::Blend;
+  /// The double precision scalar of the components.
+  pub type Float = f64;
+  /// An unsigned 64-bit integer, for the bits of a double precision lane.
+  pub type Bits = u64;
+  /// Four double precision lanes, the storage of the elements.
+  pub type Lanes = f64x4;
+  /// Two double precision lanes, the storage of a dual number.
+  pub type Lanes2 = f64x2;
+  /// The bits of four double precision lanes.
+  pub type LaneBits = u64x4;
+  /// A mask over four double precision lanes.
+  pub type Mask = mask64x4;
+  #[cfg(feature = "glam")] pub use glam::{DVec3 as Vec3, DVec4 as Vec4, DQuat as Quat, DMat3 as Mat3, DMat4 as Mat4, DAffine3 as Affine3A};
+}
+
+// Both backends must agree lane for lane, run with `--features simd`.
+#[cfg(all(test, feature = "simd"))]
+macro_rules! swizzles {
+  ($a:ident, $b:ident) => { swizzles!($a, $b, [0,0,0,0],[0,1,0,1],[0,0,1,1],[0,0,2,2],[0,2,0,0],[0,2,3,1],[0,3,1,2],[0,3,2,1],
+    [1,0,0,0],[1,0,3,0],[1,1,1,1],[1,1,2,3],[1,1,3,2],[1,1,3,3],[1,2,0,0],[1,2,1,2],[1,2,1,3],[1,2,3,0],[1,2,3,1],[1,3,1,0],
+    [1,3,1,2],[2,0,0,0],[2,0,2,0],[2,1,2,3],[2,2,2,2],[2,2,3,0],[2,2,3,1],[2,2,3,3],[2,3,0,0],[2,3,0,1],[2,3,1,0],[2,3,1,2],
+    [2,3,2,3],[2,3,3,1],[3,0,0,0],[3,1,2,0],[3,1,2,3],[3,1,3,0],[3,1,3,2],[3,2,1,2],[3,2,1,3],[3,2,3,1],[3,3,0,0],[3,3,1,2],
+    [3,3,3,3]) };
+  ($a:ident, $b:ident, $([$i:literal,$j:literal,$k:literal,$l:literal]),*) => {
+    $(same(simd::swizzle4!($a, [$i,$j,$k,$l]), scalar::swizzle4!($b, [$i,$j,$k,$l]));)*
+  }
+}
+
+#[cfg(all(test, feature = "simd"))]
+macro_rules! parity {
+  ($name:ident, $t:ty, $v:ident, $mask:ident) => {
+    mod $name {
+      use super::super::{simd, scalar, simd::{SimdFloat, StdFloat, Select}, scalar::Blend};
+
+      const INPUTS:[[$t;4];4] = [[1.0,2.0,3.0,4.0], [-0.0,0.5,-7.25,1e-3], [3.5,-2.0,0.0,9.0], [1e6,-1e-6,42.0,-0.125]];
+
+      fn pair(a:[$t;4])->(simd::$v, scalar::$v) { (simd::$v::from_array(a), scalar::$v::from_array(a)) }
+
+      fn same(a:simd::$v, b:scalar::$v) {
+        let (a, b) = (a.to_array(), b.to_array());
+        for i in 0..4 { assert_eq!(a[i].to_bits(), b[i].to_bits(), "lane {} of {:?} != {:?}", i, a, b) }
+      }
+
+      #[test] fn swizzles() {
+        for v in INPUTS { let (a, b) = pair(v); swizzles!(a, b); }
+      }
+
+      #[test] fn arithmetic() {
+        for u in INPUTS { for v in INPUTS {
+          let ((a, b), (c, d)) = (pair(u), pair(v));
+          same(a + c, b + d); same(a - c, b - d); same(a * c, b * d); same(a / c, b / d);
+          same(-a, -b); same(a.abs(), b.abs()); same(a.sqrt(), b.sqrt());
+          same(simd::b0a1a2a3(&a, &c), scalar::b0a1a2a3(&b, &d));
+          same(simd::b2b3a2a3(&a, &c), scalar::b2b3a2a3(&b, &d));
+          same(simd::$v::from_bits(a.to_bits() ^ c.to_bits()), scalar::$v::from_bits(b.to_bits() ^ d.to_bits()));
+          same(simd::$v::from_bits(a.to_bits() & c.to_bits()), scalar::$v::from_bits(b.to_bits() & d.to_bits()));
+        }}
+      }
+
+      #[test] fn select() {
+        let m = [true,false,false,true];
+        for u in INPUTS { for v in INPUTS {
+          let ((a, b), (c, d)) = (pair(u), pair(v));
+          same(simd::$mask::from_array(m).select(a, c), scalar::$mask::from_array(m).select(b, d));
+        }}
+      }
+    }
+  }
+}
+
+// Run with `cargo +nightly test --features simd`, see the readme.
+#[cfg(all(test, feature = "simd"))]
+mod parity {
+  parity!(single, f32, f32x4, mask32x4);
+  parity!(double, f64, f64x4, mask64x4);
+}
@@ -0,0 +1,120 @@
+// Scalar backend, every lane is stored in a plain array so that g3 builds on
+// stable Rust. The types mirror the subset of the `std::simd` API that is used
+// by the kernels in `maths.rs` so both backends share the same code.
+#![allow(non_camel_case_types)]
+
+use std::{fmt::{Debug,Formatter,Result},ops::{Add,AddAssign,Sub,SubAssign,Mul,MulAssign,Div,DivAssign,Neg,BitAnd,BitAndAssign,BitXor,BitXorAssign,Index,IndexMut}};
+
+macro_rules! swizzle4 {
+  ($a:expr, [$i0:literal,$i1:literal,$i2:literal,$i3:literal]) => {{
+    let a = $a;
+    Blend::blend(a, a, [$i0,$i1,$i2,$i3])
+  }}
+}
+pub(crate) use swizzle4;
+
+/// Pick four lanes out of two vectors, indices `4..8` refer to the second
+/// vector just like `simd_swizzle!` with two inputs.
+pub trait Blend:Copy {
+  fn blend(self, b:Self, i:[usize;4])->Self;
+}
+
+macro_rules! vector {
+  ($name:ident, $t:ty, $n:literal) => {
+    #[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+    #[derive(Default,Clone,Copy,PartialEq)]
+    #[repr(transparent)]
+    pub struct $name([$t;$n]);
+
+    impl $name {
+      #[inline] pub const fn from_array(a:[$t;$n])->Self { Self(a) }
+      #[inline] pub const fn splat(s:$t)->Self { Self([s;$n]) }
+      #[inline] pub const fn to_array(self)->[$t;$n] { self.0 }
+      #[inline] pub const fn as_array(&self)->&[$t;$n] { &self.0 }
+      #[inline] fn zip(self, b:Self, f:impl Fn($t,$t)->$t)->Self {
+        let mut out = self.0;
+        for (a, b) in out.iter_mut().zip(b.0) { *a = f(*a, b) }
+        Self(out)
+      }
+    }
+
+    impl Debug for $name { fn fmt(&self, f:&mut Formatter<'_>)->Result { Debug::fmt(&self.0, f) } }
+    impl From<[$t;$n]> for $name { #[inline] fn from(a:[$t;$n])->Self { Self(a) } }
+    impl From<$name> for [$t;$n] { #[inline] fn from(v:$name)->Self { v.0 } }
+    impl Index<usize> for $name { type Output = $t; #[inline] fn index(&self, i:usize)->&$t { &self.0[i] } }
+    impl IndexMut<usize> for $name { #[inline] fn index_mut(&mut self, i:usize)->&mut $t { &mut self.0[i] } }
+  }
+}
+
+macro_rules! binary {
+  ($name:ident, $op:ident, $f:ident, $assign:ident, $fa:ident, $e:expr) => {
+    impl $op<$name> for $name { type Output = $name; #[inline] fn $f(self, b:$name)->$name { self.zip(b, $e) } }
+    impl $op<&$name> for $name { type Output = $name; #[inline] fn $f(self, b:&$name)->$name { self.zip(*b, $e) } }
+    impl $op<$name> for &$name { type Output = $name; #[inline] fn $f(self, b:$name)->$name { self.zip(b, $e) } }
+    impl $op<&$name> for &$name { type Output = $name; #[inline] fn $f(self, b:&$name)->$name { self.zip(*b, $e) } }
+    impl $assign<$name> for $name { #[inline] fn $fa(&mut self, b:$name) { *self = self.zip(b, $e) } }
+    impl $assign<&$name> for $name { #[inline] fn $fa(&mut self, b:&$name) { *self = self.zip(*b, $e) } }
+  }
+}
+
+macro_rules! float {
+  ($name:ident, $t:ty, $n:literal) => {
+    vector!($name, $t, $n);
+    binary!($name, Add, add, AddAssign, add_assign, |a,b| a + b);
+    binary!($name, Sub, sub, SubAssign, sub_assign, |a,b| a - b);
+    binary!($name, Mul, mul, MulAssign, mul_assign, |a,b| a * b);
+    binary!($name, Div, div, DivAssign, div_assign, |a,b| a / b);
+    impl Neg for $name { type Output = $name; #[inline] fn neg(self)->$name { self.map(|a| -a) } }
+    impl Neg for &$name { type Output = $name; #[inline] fn neg(self)->$name { self.map(|a| -a) } }
+    impl $name {
+      #[inline] fn map(self, f:impl Fn($t)->$t)->Self { Self(self.0.map(f)) }
+      #[inline] pub fn abs(self)->Self { self.map(<$t>::abs) }
+      #[inline] pub fn sqrt(self)->Self { self.map(<$t>::sqrt) }
+    }
+  }
+}
+
+// Four float lanes with their bit pattern and a lane mask
+macro_rules! float4 {
+  ($name:ident, $t:ty, $bits:ident, $u:ty, $mask:ident) => {
+    float!($name, $t, 4);
+    vector!($bits, $u, 4);
+    binary!($bits, BitXor, bitxor, BitXorAssign, bitxor_assign, |a,b| a ^ b);
+    binary!($bits, BitAnd, bitand, BitAndAssign, bitand_assign, |a,b| a & b);
+
+    impl $name {
+      #[inline] pub fn to_bits(self)->$bits { $bits(self.0.map(<$t>::to_bits)) }
+      #[inline] pub fn from_bits(bits:$bits)->$name { $name(bits.0.map(<$t>::from_bits)) }
+    }
+
+    impl Blend for $name {
+      #[inline] fn blend(self, b:Self, i:[usize;4])->Self {
+        $name(i.map(|i| if i < 4 { self.0[i] } else { b.0[i - 4] }))
+      }
+    }
+
+    /// Lane selection mask
+    #[derive(Default,Debug,Clone,Copy,PartialEq)]
+    pub struct $mask([bool;4]);
+
+    impl $mask {
+      #[inline] pub const fn from_array(a:[bool;4])->Self { Self(a) }
+      /// Choose lanes from `t` where the mask is set and from `f` elsewhere.
+      #[inline] pub fn select(self, t:$name, f:$name)->$name {
+        let mut out = f.0;
+        for (i, m) in self.0.iter().enumerate() { if *m { out[i] = t.0[i] } }
+        $name(out)
+      }
+    }
+
+    impl From<[bool;4]> for $mask { #[inline] fn from(a:[bool;4])->Self { Self(a) } }
+  }
+}
+
+float!(f32x2, f32, 2);
+float4!(f32x4, f32, u32x4, u32, mask32x4);
+float!(f64x2, f64, 2);
+float4!(f64x4, f64, u64x4, u64, mask64x4);
+
+#[inline] pub fn b2b3a2a3<V:Blend>(a:&V,b:&V)->V { a.blend(*b, [6,7,2,3]) }
+#[inline] pub fn b0a1a2a3<V:Blend>(a:&V,b:&V)->V { a.blend(*b, [4,1,2,3]) }
@@ -0,0 +1,13 @@
+// Portable SIMD backend, enabled with the `simd` feature. This requires a
+// nightly compiler since `std::simd` is not stabilized yet.
+
+pub use std::simd::{f32x2,f32x4,u32x4,mask32x4,f64x2,f64x4,u64x4,mask64x4,simd_swizzle,num::SimdFloat,StdFloat,Select};
+use std::simd::{Simd,SimdElement};
+
+macro_rules! swizzle4 {
+  ($a:expr, [$i0:literal,$i1:literal,$i2:literal,$i3:literal]) => { std::simd::simd_swizzle!($a, [$i0,$i1,$i2,$i3]) }
+}
+pub(crate) use swizzle4;
+
+#[inline] pub fn b2b3a2a3<T:SimdElement>(a:&Simd<T,4>,b:&Simd<T,4>)->Simd<T,4> { simd_swizzle!(*a, *b, [6,7,2,3]) }
+#[inline] pub fn b0a1a2a3<T:SimdElement>(a:&Simd<T,4>,b:&Simd<T,4>)->Simd<T,4> { simd_swizzle!(*a, *b, [4,1,2,3]) }
@@ -1,8 +1,8 @@
-use std::{simd::{f32x4},ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, BitXor, BitAnd}};
-use crate::{Dual, Plane, Point, Rotor, Line, Horizon,maths::*};
+use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, BitXor, BitAnd}};
+use super::{Dual, Plane, Point, Rotor, Line, Horizon, Direction, Translator, Motor, Flector, maths::*};
 
 /// ae₂₃ + be₃₁ + ce₁₂
-pub const fn branch(a:f32,b:f32,c:f32)->Branch { Branch::new(a,b,c) }
+pub const fn branch(a:Float,b:Float,c:Float)->Branch { Branch::new(a,b,c) }
 
 /// The `Branch` both a line through the origin and also the principal branch of
 /// the logarithm of a rotor.
@@ -29,7 +29,9 @@ pub const fn branch(a:f32,b:f32,c:f32)->Branch { Branch::new(a,b,c) }
 //     no translational components, the branch is given its own type for
 //     efficiency.
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Branch(pub(crate) f32x4);
+pub struct Branch(pub(crate) Lanes);
+
+serde_blades!(Branch { e23, e31, e12 } => |[a, b, c]| Branch::new(a, b, c));
 
 impl Branch {
   /// Construct the branch as the following multivector:
@@ -39,16 +41,16 @@ impl Branch {
   /// To convince yourself this is a line through the origin, remember that
   /// such a line can be generated using the geometric product of two planes
   /// through the origin.
-  pub const fn new(a:f32, b:f32, c:f32)->Branch { Branch(f32x4::from_array([0.0, a, b, c])) }
+  pub const fn new(a:Float, b:Float, c:Float)->Branch { Branch(Lanes::from_array([0.0, a, b, c])) }
 
   /// If a line is constructed as the regressive product (join) of
   /// two points, the squared norm provided here is the squared
   /// distance between the two points (provided the points are
   /// normalized). Returns $d^2 + e^2 + f^2$.
-  pub fn squared_norm(self)->f32 { hi_dp(&self.0, &self.0)[0] }
+  pub fn squared_norm(self)->Float { hi_dp(&self.0, &self.0)[0] }
 
   /// Returns the square root of the quantity produced by `squared_norm`.
-  pub fn norm(self)->f32 { self.squared_norm().sqrt() }
+  pub fn norm(self)->Float { self.squared_norm().sqrt() }
 
   // TODO normalize
 
@@ -69,14 +71,14 @@ impl Branch {
   pub fn exp(self)->Rotor {
     let ang = sqrt_nr1(&hi_dp(&self.0, &self.0))[0];
     let cos_ang = ang.cos();
-    let sin_ang = ang.sin() / ang;
-    let mut p1 = f32x4::splat(sin_ang) * self.0;
-    p1 = p1 + f32x4::from_array([cos_ang, 0.0, 0.0, 0.0]);
+    let sin_ang = sinc(ang);
+    let mut p1 = Lanes::splat(sin_ang) * self.0;
+    p1 += Lanes::from_array([cos_ang, 0.0, 0.0, 0.0]);
     Rotor(p1)
   }
 
   pub fn sqrt(self)->Rotor {
-    let p1 = self.0 + f32x4::from_array([1.0, 0.0, 0.0, 0.0]);
+    let p1 = self.0 + Lanes::from_array([1.0, 0.0, 0.0, 0.0]);
     Rotor(p1).normalized()
   }
 
@@ -85,19 +87,23 @@ impl Branch {
     Branch(flip_signs(&self.0, [false,true,true,true].into()))
   }
 
-  #[inline] pub fn e12(&self)->f32 { self.0[3] }
-  #[inline] pub fn e21(&self)->f32 { -self.e12() }
-  #[inline] pub fn z(&self)->f32 { self.e12() }
-  #[inline] pub fn e31(&self)->f32 { self.0[2] }
-  #[inline] pub fn e13(&self)->f32 { -self.e31() }
-  #[inline] pub fn y(&self)->f32 { self.e31() }
-  #[inline] pub fn e23(&self)->f32 { self.0[1] }
-  #[inline] pub fn e32(&self)->f32 { -self.e23() }
-  #[inline] pub fn x(&self)->f32 { self.e23() }
+  #[inline] pub fn e12(&self)->Float { self.0[3] }
+  #[inline] pub fn e21(&self)->Float { -self.e12() }
+  #[inline] pub fn z(&self)->Float { self.e12() }
+  #[inline] pub fn e31(&self)->Float { self.0[2] }
+  #[inline] pub fn e13(&self)->Float { -self.e31() }
+  #[inline] pub fn y(&self)->Float { self.e31() }
+  #[inline] pub fn e23(&self)->Float { self.0[1] }
+  #[inline] pub fn e32(&self)->Float { -self.e23() }
+  #[inline] pub fn x(&self)->Float { self.e23() }
 }
 
 impl From<Line> for Branch { fn from(l:Line)->Self { Self(l.p1) } }
-impl From<&Line> for Branch { fn from(l:&Line)->Self { Self(l.p1.into()) } }
+impl From<&Line> for Branch { fn from(l:&Line)->Self { Self(l.p1) } }
+#[cfg(feature = "mint")] impl From<mint::Vector3<Float>> for Branch { fn from(v: mint::Vector3<Float>)->Branch { Branch::new(v.x, v.y, v.z) } }
+#[cfg(feature = "mint")] impl From<Branch> for mint::Vector3<Float> { fn from(b: Branch)->Self { [b.x(), b.y(), b.z()].into() } }
+#[cfg(feature = "glam")] impl From<Vec3> for Branch { fn from(v: Vec3)->Branch { Branch::new(v.x, v.y, v.z) } }
+#[cfg(feature = "glam")] impl From<Branch> for Vec3 { fn from(b: Branch)->Self { Vec3::new(b.x(), b.y(), b.z()) } }
 
 impl Add<Branch> for Branch {
   type Output = Branch;
@@ -121,16 +127,10 @@ impl SubAssign for Branch {
     self.0 -= b.0;
   }
 }
-impl Mul<f32> for Branch {
+impl Mul<Float> for Branch {
   type Output = Branch;
-  fn mul(self, s: f32) -> Branch {
-    Branch(self.0*f32x4::splat(s))
-  }
-}
-impl Mul<Branch> for Branch {
-  type Output = Rotor;
-  fn mul(self, b: Branch) -> Rotor {
-    Rotor(gp11(&self.0, &b.0))
+  fn mul(self, s: Float) -> Branch {
+    Branch(self.0*Lanes::splat(s))
   }
 }
 impl Div<Branch> for Branch {
@@ -139,18 +139,18 @@ impl Div<Branch> for Branch {
     self * b.inverse()
   }
 }
-impl MulAssign<f32> for Branch {
-  fn mul_assign(&mut self, s: f32) {
-    self.0 *= f32x4::splat(s)
+impl MulAssign<Float> for Branch {
+  fn mul_assign(&mut self, s: Float) {
+    self.0 *= Lanes::splat(s)
   }
 }
-impl Div<f32> for Branch {
+impl Div<Float> for Branch {
   type Output = Branch;
-  fn div(self, s: f32) -> Branch { Branch(self.0/f32x4::splat(s)) }
+  fn div(self, s: Float) -> Branch { Branch(self.0/Lanes::splat(s)) }
 }
-impl DivAssign<f32> for Branch {
-  fn div_assign(&mut self, s: f32) {
-    self.0 /= f32x4::splat(s)
+impl DivAssign<Float> for Branch {
+  fn div_assign(&mut self, s: Float) {
+    self.0 /= Lanes::splat(s)
   }
 }
 
@@ -186,3 +186,42 @@ impl BitAnd<Point> for Branch {
   type Output = Plane;
   fn bitand(self, a:Point)->Plane{ a & self }
 }
+
+products! {
+  Branch * Plane = Flector, gp_1_0(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Branch * Line = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Branch * Branch = Rotor, gp_1_1(0; 0) -> p1 => Rotor(p1);
+  Branch * Horizon = Motor, gp_1_2(0; p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Branch * Point = Flector, gp_1_3(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Branch * Direction = Flector, gp_1_3(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Branch * Dual as Motor = Line, gp_1_12(0; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Branch * Rotor = Rotor, gp_1_1(0; 0) -> p1 => Rotor(p1);
+  Branch * Translator as Motor = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Branch * Motor = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Branch ^ Dual as Motor = Branch, ext_1_12(0; p1, p2) -> (p1, _) => Branch(p1);
+  Branch ^ Rotor = Branch, ext_1_1(0; 0) -> p1 => Branch(p1);
+  Branch ^ Translator as Motor = Motor, ext_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Branch ^ Motor = Motor, ext_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Branch & Line = Float, reg_1_12(0; p1, p2) -> p1 => p1[0];
+  Branch & Horizon = Float, reg_1_2(0; p2) -> p1 => p1[0];
+  Branch & Direction = Plane, reg_1_3(0; 0) -> p0 => Plane(p0);
+  Branch & Dual as Motor = Branch, reg_1_12(0; p1, p2) -> p1 => Branch(p1);
+  Branch & Translator as Motor = Float, reg_1_12(0; p1, p2) -> p1 => p1[0];
+  Branch & Motor = Rotor, reg_1_12(0; p1, p2) -> p1 => Rotor(p1);
+  Branch | Plane = Plane, dot_1_0(0; 0) -> p0 => Plane(p0);
+  Branch | Line = Float, dot_1_12(0; p1, p2) -> (p1, _) => p1[0];
+  Branch | Branch = Float, dot_1_1(0; 0) -> p1 => p1[0];
+  Branch | Point = Plane, dot_1_3(0; 0) -> (p0, _) => Plane(p0);
+  Branch | Direction = Plane, dot_1_3(0; 0) -> (p0, _) => Plane(p0);
+  Branch | Dual as Motor = Line, dot_1_12(0; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Branch | Rotor = Rotor, dot_1_1(0; 0) -> p1 => Rotor(p1);
+  Branch | Translator as Motor = Branch, dot_1_12(0; p1, p2) -> (p1, _) => Branch(p1);
+  Branch | Motor = Motor, dot_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Branch / Plane = Flector;
+  Branch / Line = Motor;
+  Branch / Point = Flector;
+  Branch / Dual = Line;
+  Branch / Rotor = Rotor;
+  Branch / Translator = Motor;
+  Branch / Motor = Motor;
+}
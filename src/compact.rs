@@ -0,0 +1,82 @@
+//! Serde support for the elements.
+//!
+//! Human readable formats like JSON get the components by the names of their
+//! accessors, a point is `{"x":1.0,"y":2.0,"z":3.0,"w":1.0}` and a motor
+//! `{"scalar":1.0,"e23":0.0,…,"e0123":0.0}`. Binary formats get a plain array
+//! in the same order, and either form is accepted when reading.
+//!
+//! For the array in a human readable format too, use this module with
+//! `#[serde(with = "g3::compact")]`.
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Pose { #[serde(with = "g3::compact")] motor:g3::Motor }
+//! # }
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{DeserializeOwned, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+
+/// An element as an array of its components, with their names.
+pub trait Blades: Sized {
+  type Scalar: Serialize + DeserializeOwned + Copy;
+  type Array: Serialize + DeserializeOwned + Default + AsMut<[Self::Scalar]> + AsRef<[Self::Scalar]>;
+  const NAME:&'static str;
+  const FIELDS:&'static [&'static str];
+  fn to_blades(&self)->Self::Array;
+  fn from_blades(a:Self::Array)->Self;
+}
+
+/// Write the components as an array.
+pub fn serialize<T:Blades, S:Serializer>(a:&T, s:S)->Result<S::Ok, S::Error> { a.to_blades().serialize(s) }
+
+/// Read the components from an array.
+pub fn deserialize<'de, T:Blades, D:Deserializer<'de>>(d:D)->Result<T, D::Error> { T::Array::deserialize(d).map(T::from_blades) }
+
+pub(crate) fn serialize_named<T:Blades, S:Serializer>(a:&T, s:S)->Result<S::Ok, S::Error> {
+  if !s.is_human_readable() { return serialize(a, s) }
+  let mut st = s.serialize_struct(T::NAME, T::FIELDS.len())?;
+  for (name, x) in T::FIELDS.iter().zip(a.to_blades().as_ref()) { st.serialize_field(name, x)? }
+  st.end()
+}
+
+pub(crate) fn deserialize_named<'de, T:Blades, D:Deserializer<'de>>(d:D)->Result<T, D::Error> {
+  if !d.is_human_readable() { return deserialize(d) }
+  d.deserialize_struct(T::NAME, T::FIELDS, Named(PhantomData))
+}
+
+struct Named<T>(PhantomData<T>);
+
+impl<'de, T:Blades> Visitor<'de> for Named<T> {
+  type Value = T;
+
+  fn expecting(&self, f:&mut fmt::Formatter)->fmt::Result {
+    write!(f, "a {} as a map or an array of {}", T::NAME, T::FIELDS.join(", "))
+  }
+
+  fn visit_seq<A:SeqAccess<'de>>(self, mut seq:A)->Result<T, A::Error> {
+    let mut a = T::Array::default();
+    for (i, x) in a.as_mut().iter_mut().enumerate() {
+      *x = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+    }
+    if seq.next_element::<T::Scalar>()?.is_some() { return Err(A::Error::invalid_length(T::FIELDS.len() + 1, &self)) }
+    Ok(T::from_blades(a))
+  }
+
+  fn visit_map<A:MapAccess<'de>>(self, mut map:A)->Result<T, A::Error> {
+    let mut a = T::Array::default();
+    let mut seen = vec![false; T::FIELDS.len()];
+    while let Some(key) = map.next_key::<String>()? {
+      let i = T::FIELDS.iter().position(|f| *f == key).ok_or_else(|| A::Error::unknown_field(&key, T::FIELDS))?;
+      if seen[i] { return Err(A::Error::duplicate_field(T::FIELDS[i])) }
+      a.as_mut()[i] = map.next_value()?;
+      seen[i] = true;
+    }
+    if let Some(i) = seen.iter().position(|s| !s) { return Err(A::Error::missing_field(T::FIELDS[i])) }
+    Ok(T::from_blades(a))
+  }
+}
@@ -0,0 +1,319 @@
+//! Forward mode automatic differentiation through the geometric operations.
+//!
+//! Planes, points, lines, rotors, motors and flectors with components of any
+//! [`Scalar`], computed lane by lane with the same generated kernels and the
+//! same exponential, logarithm and normalization as the crate root. With the
+//! components a [`Jet`], a value with the gradient to `N` variables, every
+//! result carries its derivatives, for fitting motors to point correspondences
+//! or calibrating cameras with gradient descent.
+//!
+//! ```
+//! use g3::{Transform, diff::{Jet, Motor, Point, line}};
+//! // The motor exp(δ) m near m, with the six components of δ as the variables
+//! let [a, b, c, d, e, f] = Jet::variables([0.0; 6]);
+//! let m = line(a, b, c, d, e, f).exp() * Motor::from(g3::Motor::from(g3::translator(2.0, 1.0, 0.0, 0.0)));
+//! let p = m.apply(Point::from(g3::point(1.0, 0.0, 0.0)));
+//! let error = p.distance(Point::from(g3::point(0.0, 0.0, 0.0)));
+//! assert_eq!(error.value, 3.0);
+//! // exp(a e01) translates by -2a along x, every other motion is perpendicular
+//! // to the distance and does not change it to first order
+//! assert_eq!(error.grad, [-2.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+//! ```
+//!
+//! These are the elements of the crate root with the lanes stored in arrays of
+//! the scalar, `diff::Motor<S>` is `g3::Motor<[S;4]>`, and their operations
+//! run on the scalar kernels that `build.rs` generates next to the SIMD ones.
+//! Only the six elements above have them. There are no generic branches,
+//! horizons, translators or directions, convert them to the [`Line`],
+//! [`Motor`] or [`Point`] they are through the crate root first,
+//! `diff::Motor::from(g3::Motor::from(t))` for a translator `t`.
+//!
+//! The [`Jet`] is a dual number $a + \sum_i b_i \epsilon_i$ with $\epsilon_i
+//! \epsilon_j = 0$, not to be confused with the [`Dual`](crate::Dual) of the
+//! algebra, $a + b\,e_{0123}$, where the pseudoscalar plays the part of
+//! $\epsilon$.
+
+use std::{fmt::Debug, ops::{Add, Sub, Mul, Div, Neg}};
+use crate::{Transform, maths::{exp, logarithm, normalize, kernels::scalar as kernels}};
+
+/// The numbers the components of the elements in this module are made of.
+pub trait Scalar:Copy + Debug + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self> {
+  /// Relative rounding error of the value, below it the series of `exp` and
+  /// `log` take over from their closed forms.
+  const EPSILON:f64;
+  fn from_f64(x:f64)->Self;
+  /// The value without derivatives, for branches and conversions.
+  fn value(self)->f64;
+  /// The square root, for jets with a zero gradient at zero where the
+  /// derivative is infinite, the subgradient of a distance at its minimum.
+  fn sqrt(self)->Self;
+  fn sin(self)->Self;
+  fn cos(self)->Self;
+  fn atan2(self, x:Self)->Self;
+  fn zero()->Self { Self::from_f64(0.0) }
+  fn one()->Self { Self::from_f64(1.0) }
+}
+
+macro_rules! float {
+  ($($t:ident),*) => {$(
+    impl Scalar for $t {
+      const EPSILON:f64 = $t::EPSILON as f64;
+      fn from_f64(x:f64)->$t { x as $t }
+      fn value(self)->f64 { self.into() }
+      fn sqrt(self)->$t { $t::sqrt(self) }
+      fn sin(self)->$t { $t::sin(self) }
+      fn cos(self)->$t { $t::cos(self) }
+      fn atan2(self, x:$t)->$t { $t::atan2(self, x) }
+    }
+  )*}
+}
+
+float!(f32, f64);
+
+/// A value and its gradient to `N` variables, the scalar of forward mode
+/// automatic differentiation. Comparisons only look at the value.
+#[derive(Debug,Clone,Copy)]
+pub struct Jet<const N:usize> { pub value:f64, pub grad:[f64;N] }
+
+impl<const N:usize> Jet<N> {
+  /// A value that does not depend on any of the variables.
+  pub fn constant(value:f64)->Jet<N> { Jet{value, grad: [0.0; N]} }
+  /// The variable `i` with this value.
+  pub fn variable(value:f64, i:usize)->Jet<N> {
+    let mut grad = [0.0; N];
+    grad[i] = 1.0;
+    Jet{value, grad}
+  }
+  /// All `N` variables at once, `let [x, y] = Jet::variables([1.0, 2.0])`.
+  pub fn variables(values:[f64;N])->[Jet<N>;N] {
+    let mut i = 0;
+    values.map(|x| { i += 1; Jet::variable(x, i - 1) })
+  }
+  // f(a) from f(a.value) and f'(a.value)
+  fn chain(self, value:f64, derivative:f64)->Jet<N> { Jet{value, grad: self.grad.map(|g| g * derivative)} }
+}
+
+impl<const N:usize> From<f64> for Jet<N> { fn from(x:f64)->Jet<N> { Jet::constant(x) } }
+impl<const N:usize> PartialEq for Jet<N> { fn eq(&self, b:&Jet<N>)->bool { self.value == b.value } }
+impl<const N:usize> PartialOrd for Jet<N> {
+  fn partial_cmp(&self, b:&Jet<N>)->Option<std::cmp::Ordering> { self.value.partial_cmp(&b.value) }
+}
+
+impl<const N:usize> Add for Jet<N> {
+  type Output = Jet<N>;
+  fn add(self, b:Jet<N>)->Jet<N> { Jet{value: self.value + b.value, grad: std::array::from_fn(|i| self.grad[i] + b.grad[i])} }
+}
+impl<const N:usize> Sub for Jet<N> {
+  type Output = Jet<N>;
+  fn sub(self, b:Jet<N>)->Jet<N> { Jet{value: self.value - b.value, grad: std::array::from_fn(|i| self.grad[i] - b.grad[i])} }
+}
+impl<const N:usize> Mul for Jet<N> {
+  type Output = Jet<N>;
+  fn mul(self, b:Jet<N>)->Jet<N> {
+    Jet{value: self.value * b.value, grad: std::array::from_fn(|i| self.grad[i] * b.value + self.value * b.grad[i])}
+  }
+}
+impl<const N:usize> Div for Jet<N> {
+  type Output = Jet<N>;
+  fn div(self, b:Jet<N>)->Jet<N> {
+    let value = self.value / b.value;
+    Jet{value, grad: std::array::from_fn(|i| (self.grad[i] - value * b.grad[i]) / b.value)}
+  }
+}
+impl<const N:usize> Neg for Jet<N> { type Output = Jet<N>; fn neg(self)->Jet<N> { Jet{value: -self.value, grad: self.grad.map(|g| -g)} } }
+
+impl<const N:usize> Scalar for Jet<N> {
+  const EPSILON:f64 = f64::EPSILON;
+  fn from_f64(x:f64)->Jet<N> { Jet::constant(x) }
+  fn value(self)->f64 { self.value }
+  fn sqrt(self)->Jet<N> { let s = self.value.sqrt(); self.chain(s, if s == 0.0 { 0.0 } else { 0.5 / s }) }
+  fn sin(self)->Jet<N> { self.chain(self.value.sin(), self.value.cos()) }
+  fn cos(self)->Jet<N> { self.chain(self.value.cos(), -self.value.sin()) }
+  fn atan2(self, x:Jet<N>)->Jet<N> {
+    let r2 = self.value * self.value + x.value * x.value;
+    Jet{value: self.value.atan2(x.value), grad: std::array::from_fn(|i| (x.value * self.grad[i] - self.value * x.grad[i]) / r2)}
+  }
+}
+
+/// A plane with components of a [`Scalar`], see [`crate::Plane`].
+pub type Plane<S> = crate::Plane<[S;4]>;
+/// A point with components of a [`Scalar`], see [`crate::Point`].
+pub type Point<S> = crate::Point<[S;4]>;
+/// A line with components of a [`Scalar`], see [`crate::Line`].
+pub type Line<S> = crate::Line<[S;4]>;
+/// A rotor with components of a [`Scalar`], see [`crate::Rotor`].
+pub type Rotor<S> = crate::Rotor<[S;4]>;
+/// A motor with components of a [`Scalar`], see [`crate::Motor`].
+pub type Motor<S> = crate::Motor<[S;4]>;
+/// A flector with components of a [`Scalar`], see [`crate::Flector`].
+pub type Flector<S> = crate::Flector<[S;4]>;
+
+/// The plane a x + b y + c z + d = 0, in the order of [`crate::plane`].
+pub fn plane<S:Scalar>(a:S, b:S, c:S, d:S)->Plane<S> { crate::Plane([d, a, b, c]) }
+
+pub fn point<S:Scalar>(x:S, y:S, z:S)->Point<S> { crate::Point([S::one(), x, y, z]) }
+
+/// The line with the ideal part a e01 + b e02 + c e03 and the Euclidean
+/// part d e23 + e e31 + f e12, in the order of [`crate::line`].
+pub fn line<S:Scalar>(a:S, b:S, c:S, d:S, e:S, f:S)->Line<S> { Line{p1: [S::zero(), d, e, f], p2: [S::zero(), a, b, c]} }
+
+fn add<S:Scalar>(a:[S;4], b:[S;4])->[S;4] { std::array::from_fn(|i| a[i] + b[i]) }
+fn scale<S:Scalar>(a:[S;4], s:S)->[S;4] { a.map(|x| x * s) }
+fn hi_dp<S:Scalar>(a:&[S;4], b:&[S;4])->S { a[1] * b[1] + a[2] * b[2] + a[3] * b[3] }
+
+impl<S:Scalar> Plane<S> {
+  pub fn a(&self)->S { self.0[1] }
+  pub fn b(&self)->S { self.0[2] }
+  pub fn c(&self)->S { self.0[3] }
+  pub fn d(&self)->S { self.0[0] }
+  /// The plane with a normal of unit length.
+  pub fn normalized(&self)->Plane<S> { crate::Plane(scale(self.0, S::one() / hi_dp(&self.0, &self.0).sqrt())) }
+}
+
+impl<S:Scalar> Point<S> {
+  pub fn w(&self)->S { self.0[0] }
+  pub fn x(&self)->S { self.0[1] }
+  pub fn y(&self)->S { self.0[2] }
+  pub fn z(&self)->S { self.0[3] }
+  pub fn normalized(&self)->Point<S> { let w = S::one() / self.0[0]; crate::Point(self.0.map(|x| x * w)) }
+  /// Euclidean distance to another finite point, the norm of their join. Its
+  /// gradient is zero where the points coincide, see [`Scalar::sqrt`].
+  pub fn distance(&self, p:Point<S>)->S { self.squared_distance(p).sqrt() }
+  /// The square of the distance, smooth everywhere, for least squares.
+  pub fn squared_distance(&self, p:Point<S>)->S {
+    let l = self.normalized() & p.normalized();
+    hi_dp(&l.p1, &l.p1)
+  }
+}
+
+impl<S:Scalar> Line<S> {
+  pub fn e01(&self)->S { self.p2[1] }
+  pub fn e02(&self)->S { self.p2[2] }
+  pub fn e03(&self)->S { self.p2[3] }
+  pub fn e23(&self)->S { self.p1[1] }
+  pub fn e31(&self)->S { self.p1[2] }
+  pub fn e12(&self)->S { self.p1[3] }
+
+  /// The motor with this line as its axis, see [`crate::Line::exp`]. The
+  /// derivatives stay finite for pure translations and at the zero line.
+  pub fn exp(&self)->Motor<S> { let (p1, p2) = exp(&self.p1, &self.p2); Motor{p1, p2} }
+}
+
+impl<S:Scalar> Add for Line<S> { type Output = Line<S>; fn add(self, l:Line<S>)->Line<S> { Line{p1: add(self.p1, l.p1), p2: add(self.p2, l.p2)} } }
+impl<S:Scalar> Mul<S> for Line<S> { type Output = Line<S>; fn mul(self, s:S)->Line<S> { Line{p1: scale(self.p1, s), p2: scale(self.p2, s)} } }
+
+impl<S:Scalar> Rotor<S> {
+  pub fn scalar(&self)->S { self.0[0] }
+  pub fn e23(&self)->S { self.0[1] }
+  pub fn e31(&self)->S { self.0[2] }
+  pub fn e12(&self)->S { self.0[3] }
+  pub fn reverse(&self)->Rotor<S> { let [s, a, b, c] = self.0; crate::Rotor([s, -a, -b, -c]) }
+}
+
+impl<S:Scalar> From<Rotor<S>> for Motor<S> { fn from(r:Rotor<S>)->Motor<S> { Motor{p1: r.0, p2: [S::zero(); 4]} } }
+
+impl<S:Scalar> Motor<S> {
+  pub fn scalar(&self)->S { self.p1[0] }
+  pub fn e23(&self)->S { self.p1[1] }
+  pub fn e31(&self)->S { self.p1[2] }
+  pub fn e12(&self)->S { self.p1[3] }
+  pub fn e0123(&self)->S { self.p2[0] }
+  pub fn e01(&self)->S { self.p2[1] }
+  pub fn e02(&self)->S { self.p2[2] }
+  pub fn e03(&self)->S { self.p2[3] }
+
+  pub fn reverse(&self)->Motor<S> {
+    let [s, a, b, c] = self.p1;
+    let [d, e, f, g] = self.p2;
+    Motor{p1: [s, -a, -b, -c], p2: [d, -e, -f, -g]}
+  }
+
+  /// See [`crate::Motor::normalized`], m / sqrt(m ~m).
+  pub fn normalized(&self)->Motor<S> { let (p1, p2) = normalize(&self.p1, &self.p2); Motor{p1, p2} }
+
+  /// The principal branch of the logarithm like [`crate::Motor::log`], the
+  /// derivatives stay finite for pure translations and the identity.
+  pub fn log(&self)->Line<S> {
+    let m = self.normalized();
+    let (p1, p2) = logarithm(&m.p1, &m.p2);
+    Line{p1, p2}
+  }
+}
+
+impl<S:Scalar> Flector<S> {
+  pub fn e0(&self)->S { self.p0[0] }
+  pub fn e1(&self)->S { self.p0[1] }
+  pub fn e2(&self)->S { self.p0[2] }
+  pub fn e3(&self)->S { self.p0[3] }
+  pub fn e123(&self)->S { self.p3[0] }
+  pub fn e032(&self)->S { self.p3[1] }
+  pub fn e013(&self)->S { self.p3[2] }
+  pub fn e021(&self)->S { self.p3[3] }
+}
+
+// The products of the crate root between these elements, on the same kernels
+// lane by lane. Those into a dual number are left out.
+products! { <S:Scalar>
+  Plane * Plane = Motor<S>, gp_0_0(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Plane * Point = Motor<S>, gp_0_3(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Point * Plane = Motor<S>, gp_3_0(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Rotor * Rotor = Rotor<S>, gp_1_1(0; 0) -> p1 => crate::Rotor(p1);
+  Rotor * Motor = Motor<S>, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Motor * Rotor = Motor<S>, gp_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Motor * Motor = Motor<S>, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Motor * Plane = Flector<S>, gp_12_0(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Motor * Point = Flector<S>, gp_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Plane ^ Plane = Line<S>, ext_0_0(0; 0) -> (p1, p2) => Line{p1, p2};
+  Plane ^ Line = Point<S>, ext_0_12(0; p1, p2) -> (_, p3) => crate::Point(p3);
+  Line ^ Plane = Point<S>, ext_12_0(p1, p2; 0) -> (_, p3) => crate::Point(p3);
+  Point & Point = Line<S>, reg_3_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Point & Line = Plane<S>, reg_3_12(0; p1, p2) -> (p0, _) => crate::Plane(p0);
+  Line & Point = Plane<S>, reg_12_3(p1, p2; 0) -> (p0, _) => crate::Plane(p0);
+  Line & Line = S, reg_12_12(p1, p2; p1, p2) -> (p1, _) => p1[0];
+  Plane | Plane = S, dot_0_0(0; 0) -> p1 => p1[0];
+  Plane | Point = Line<S>, dot_0_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Point | Plane = Line<S>, dot_3_0(0; 0) -> (p1, p2) => Line{p1, p2};
+  Plane | Line = Plane<S>, dot_0_12(0; p1, p2) -> (p0, _) => crate::Plane(p0);
+  Line | Plane = Plane<S>, dot_12_0(p1, p2; 0) -> (p0, _) => crate::Plane(p0);
+  Point | Line = Plane<S>, dot_3_12(0; p1, p2) -> (p0, _) => crate::Plane(p0);
+  Line | Point = Plane<S>, dot_12_3(p1, p2; 0) -> (p0, _) => crate::Plane(p0);
+  Line | Line = S, dot_12_12(p1, p2; p1, p2) -> (p1, _) => p1[0];
+  Point | Point = S, dot_3_3(0; 0) -> p1 => p1[0];
+}
+
+// The sandwiches $MX\widetilde{M}$ of the crate root, generated by `build.rs`.
+impl<S:Scalar> Transform<Plane<S>> for Motor<S> {
+  type Output = Plane<S>;
+  fn apply(&self, p:Plane<S>)->Plane<S> { crate::Plane(kernels::sw_12_0(&self.p1, &self.p2, &p.0)) }
+}
+
+impl<S:Scalar> Transform<Point<S>> for Motor<S> {
+  type Output = Point<S>;
+  fn apply(&self, p:Point<S>)->Point<S> { crate::Point(kernels::sw_12_3(&self.p1, &self.p2, &p.0)) }
+}
+
+impl<S:Scalar> Transform<Line<S>> for Motor<S> {
+  type Output = Line<S>;
+  fn apply(&self, l:Line<S>)->Line<S> { let (p1, p2) = kernels::sw_12_12(&self.p1, &self.p2, &l.p1, &l.p2); Line{p1, p2} }
+}
+
+impl<S:Scalar, T> Transform<T> for Rotor<S> where Motor<S>:Transform<T> {
+  type Output = <Motor<S> as Transform<T>>::Output;
+  fn apply(&self, x:T)->Self::Output { Motor::from(*self).apply(x) }
+}
+
+// Constants from the single precision elements and back, rounding every
+// component to the nearest `f32`.
+macro_rules! scalar_of {
+  ($($name:ident { $($field:tt),* }),*) => {$(
+    impl<S:Scalar> From<crate::$name> for $name<S> {
+      fn from(a:crate::$name)->$name<S> { $name { $($field: a.$field.to_array().map(|x| S::from_f64(x.into()))),* } }
+    }
+    impl<S:Scalar> $name<S> {
+      /// The values as a single precision element, without the derivatives.
+      pub fn to_f32(&self)->crate::$name { crate::$name { $($field: self.$field.map(|x| x.value() as f32).into()),* } }
+    }
+  )*}
+}
+
+scalar_of!(Plane{0}, Point{0}, Line{p1,p2}, Rotor{0}, Motor{p1,p2}, Flector{p0,p3});
@@ -1,29 +1,35 @@
-use std::{simd::{f32x4,u32x4,SimdFloat},ops::{Add,AddAssign,Sub,SubAssign,Mul,MulAssign,Div,DivAssign,Neg}};
-use crate::maths::*;
+use std::{ops::{Add,AddAssign,Sub,SubAssign,Mul,MulAssign,Div,DivAssign,Neg}};
+use super::{Dual, Plane, Point, Line, Branch, Horizon, Rotor, Translator, Motor, Flector, maths::*};
 
 /// Directions in are represented using points at infinity (homogeneous coordinate 0).
 /// Having a homogeneous coordinate of zero ensures that directions are translation-invariant.
-#[derive(Default,Debug,Clone,PartialEq)]
-pub struct Direction(pub(crate) f32x4);
+#[derive(Default,Debug,Clone,Copy,PartialEq)]
+pub struct Direction(pub(crate) Lanes);
+
+serde_blades!(Direction { x, y, z } => |[x, y, z]| Direction(Lanes::from_array([0.0, x, y, z])));
 
 impl Direction {
   /// Create a normalized direction
-  pub fn new(x:f32,y:f32,z:f32)->Direction { Direction(f32x4::from_array([0.0,x,y,z])).normalized() }
+  pub fn new(x:Float,y:Float,z:Float)->Direction { Direction(Lanes::from_array([0.0,x,y,z])).normalized() }
   /// Data should point to four floats with memory layout `(0.f, x, y, z)`
   /// where the zero occupies the lowest address in memory.
-  pub fn from_bits(bits:u32x4)->Direction {
-    Direction(f32x4::from_bits(bits))
+  pub fn from_bits(bits:[Bits;4])->Direction {
+    Direction(Lanes::from_bits(LaneBits::from_array(bits)))
   }
   /// Normalize this direction by dividing all components by the
   /// magnitude (by default, `rsqrtps` is used with a single Newton-Raphson
   /// refinement iteration)
   pub fn normalized(&self)->Direction { Direction(&self.0 * rsqrt_nr1(&hi_dp_bc(&self.0, &self.0))) }
-  pub fn x(&self)->f32 { self.0[1] }
-  pub fn y(&self)->f32 { self.0[2] }
-  pub fn z(&self)->f32 { self.0[3] }
+  pub fn x(&self)->Float { self.0[1] }
+  pub fn y(&self)->Float { self.0[2] }
+  pub fn z(&self)->Float { self.0[3] }
 }
 
-impl Into<[f32;3]> for Direction { fn into(self) -> [f32; 3] { [self.x(), self.y(), self.z()] } }
+impl From<Direction> for [Float;3] { fn from(val: Direction) -> Self { [val.x(), val.y(), val.z()] } }
+#[cfg(feature = "mint")] impl From<mint::Vector3<Float>> for Direction { fn from(v: mint::Vector3<Float>)->Direction { Direction::new(v.x, v.y, v.z) } }
+#[cfg(feature = "mint")] impl From<Direction> for mint::Vector3<Float> { fn from(d: Direction)->Self { <[Float;3]>::from(d).into() } }
+#[cfg(feature = "glam")] impl From<Vec3> for Direction { fn from(v: Vec3)->Direction { Direction::new(v.x, v.y, v.z) } }
+#[cfg(feature = "glam")] impl From<Direction> for Vec3 { fn from(d: Direction)->Self { Vec3::from_array(d.into()) } }
 
 impl Add<Direction> for Direction {
   type Output = Direction;
@@ -43,22 +49,22 @@ impl SubAssign for Direction {
   fn sub_assign(&mut self, d: Self) { self.0 -= d.0 }
 }
 
-impl Mul<f32> for Direction {
+impl Mul<Float> for Direction {
   type Output = Direction;
-  fn mul(self, s: f32) -> Direction { Direction(self.0*f32x4::splat(s))}
+  fn mul(self, s: Float) -> Direction { Direction(self.0*Lanes::splat(s))}
 }
 
-impl MulAssign<f32> for Direction {
-  fn mul_assign(&mut self, s: f32) { self.0 *= f32x4::splat(s) }
+impl MulAssign<Float> for Direction {
+  fn mul_assign(&mut self, s: Float) { self.0 *= Lanes::splat(s) }
 }
 
-impl Div<f32> for Direction {
+impl Div<Float> for Direction {
   type Output = Direction;
-  fn div(self, s: f32) -> Direction { Direction(self.0*refined_reciprocal(s)) }
+  fn div(self, s: Float) -> Direction { Direction(self.0*refined_reciprocal(s)) }
 }
 
-impl DivAssign<f32> for Direction {
-  fn div_assign(&mut self, s: f32) { self.0 *= refined_reciprocal(s) }
+impl DivAssign<Float> for Direction {
+  fn div_assign(&mut self, s: Float) { self.0 *= refined_reciprocal(s) }
 }
 
 // Unary minus
@@ -66,3 +72,44 @@ impl Neg for Direction {
   type Output = Direction;
   fn neg(self)->Direction { Direction(-self.0) }
 }
+
+products! {
+  Direction * Plane = Motor, gp_3_0(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Direction * Line = Flector, gp_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction * Branch = Flector, gp_3_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Direction * Point = Horizon, gp_3_3(0; 0) -> (_, p2) => Horizon{p2};
+  Direction * Dual as Motor = Flector, gp_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction * Rotor = Flector, gp_3_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Direction * Translator as Motor = Flector, gp_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction * Motor = Flector, gp_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction ^ Plane = Dual, ext_3_0(0; 0) -> p2 => Dual::new(0.0, p2[0]);
+  Direction ^ Dual as Motor = Point, ext_3_12(0; p1, p2) -> p3 => Point(p3);
+  Direction ^ Rotor = Point, ext_3_1(0; 0) -> p3 => Point(p3);
+  Direction ^ Translator as Motor = Point, ext_3_12(0; p1, p2) -> p3 => Point(p3);
+  Direction ^ Motor = Point, ext_3_12(0; p1, p2) -> p3 => Point(p3);
+  Direction & Plane = Dual, reg_3_0(0; 0) -> p1 => Dual::new(p1[0], 0.0);
+  Direction & Line = Plane, reg_3_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Direction & Branch = Plane, reg_3_1(0; 0) -> p0 => Plane(p0);
+  Direction & Horizon = Plane, reg_3_2(0; p2) -> (p0, _) => Plane(p0);
+  Direction & Point = Line, reg_3_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Direction & Direction = Line, reg_3_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Direction & Dual as Motor = Point, reg_3_12(0; p1, p2) -> (_, p3) => Point(p3);
+  Direction & Rotor = Plane, reg_3_1(0; 0) -> p0 => Plane(p0);
+  Direction & Translator as Motor = Plane, reg_3_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Direction & Motor = Flector, reg_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction | Plane = Line, dot_3_0(0; 0) -> (p1, p2) => Line{p1, p2};
+  Direction | Line = Plane, dot_3_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Direction | Branch = Plane, dot_3_1(0; 0) -> (p0, _) => Plane(p0);
+  Direction | Dual as Motor = Flector, dot_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction | Rotor = Flector, dot_3_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Direction | Translator as Motor = Point, dot_3_12(0; p1, p2) -> (_, p3) => Point(p3);
+  Direction | Motor = Flector, dot_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Direction / Plane = Motor;
+  Direction / Line = Flector;
+  Direction / Branch = Flector;
+  Direction / Point = Horizon;
+  Direction / Dual = Flector;
+  Direction / Rotor = Flector;
+  Direction / Translator = Flector;
+  Direction / Motor = Flector;
+}
@@ -0,0 +1,121 @@
+//! Double precision elements.
+//!
+//! The same elements, operators and sandwich kernels as the crate root but
+//! stored as `f64`, for scenes with coordinates far from the origin.
+//!
+//! ```
+//! use g3::double::{point, translator, Transform};
+//! let t = translator(1e9, 1.0, 0.0, 0.0);
+//! let p = t.apply(point(1e9, 0.5, 0.0));
+//! ```
+//!
+//! Widening from the single precision types with `From` is lossless, narrowing
+//! back with `to_f32` rounds every component to the nearest `f32`.
+
+#![allow(clippy::duplicate_mod)]
+
+// The element sources are shared with the crate root. In here the vector types
+// and `Float` resolve to their 64-bit counterparts from `backend::wide`.
+mod backend { pub use crate::backend::wide::*; }
+#[path = "maths.rs"] pub(crate) mod maths;
+#[path = "dual.rs"] mod dual;
+#[path = "point.rs"] mod point;
+#[path = "line.rs"] mod line;
+#[path = "branch.rs"] mod branch;
+#[path = "horizon.rs"] mod horizon;
+#[path = "plane.rs"] mod plane;
+#[path = "motor.rs"] mod motor;
+#[path = "rotor.rs"] mod rotor;
+#[path = "direction.rs"] mod direction;
+#[path = "translator.rs"] mod translator;
+#[path = "flector.rs"] mod flector;
+#[path = "animation.rs"] mod animation;
+#[path = "kinematics.rs"] mod kinematics;
+#[path = "dynamics.rs"] mod dynamics;
+#[path = "metric.rs"] mod metric;
+#[path = "multivector.rs"] mod multivector;
+#[path = "notation.rs"] mod notation;
+#[path = "query.rs"] pub mod query;
+
+/// π
+pub const pi:Float = std::f64::consts::PI;
+/// τ = 2π
+pub const tau:Float = pi*2.0;
+
+pub use crate::{Transform,PoincareDual,GeometricProduct,JoinProduct,MeetProduct,SandwichProduct,InnerProduct};
+pub use dual::{Dual, dual, e0123};
+pub use point::*;
+pub use line::{Line,line};
+pub use branch::{Branch,branch};
+pub use horizon::{Horizon,horizon};
+pub use plane::*;
+pub use motor::{Motor,Screw,motor};
+pub use rotor::{Euler,EulerAngles,EulerFrame,EulerOrder,Rotor,rotor};
+pub use direction::{Direction};
+pub use translator::{Translator,translator};
+pub use flector::Flector;
+pub use animation::{Track,Interpolation};
+pub use kinematics::{Chain,Joint,JointKind,Limit};
+pub use dynamics::{RigidBody,Inertia,Integrator};
+pub use metric::{Distance,Angle,distance,angle};
+pub use multivector::Multivector;
+pub use notation::ParseError;
+pub use backend::Float;
+
+// Element modules glob import this module, so the single precision vector
+// types are only named by their full path here.
+trait Widen { type Wide; fn widen(self)->Self::Wide; fn narrow(w:Self::Wide)->Self; }
+impl Widen for crate::maths::f32x4 {
+  type Wide = crate::maths::f64x4;
+  fn widen(self)->Self::Wide { Self::Wide::from_array(self.to_array().map(f64::from)) }
+  fn narrow(w:Self::Wide)->Self { Self::from_array(w.to_array().map(|x| x as f32)) }
+}
+impl Widen for crate::maths::f32x2 {
+  type Wide = crate::maths::f64x2;
+  fn widen(self)->Self::Wide { Self::Wide::from_array(self.to_array().map(f64::from)) }
+  fn narrow(w:Self::Wide)->Self { Self::from_array(w.to_array().map(|x| x as f32)) }
+}
+
+macro_rules! precision {
+  ($($name:ident { $($field:tt),* }),*) => {$(
+    impl From<crate::$name> for $name {
+      fn from(a:crate::$name)->$name { $name { $($field: Widen::widen(a.$field)),* } }
+    }
+    impl $name {
+      /// Narrow to single precision, every component is rounded to the nearest `f32`.
+      pub fn to_f32(&self)->crate::$name { crate::$name { $($field: Widen::narrow(self.$field)),* } }
+    }
+  )*}
+}
+
+precision!(Plane{0}, Point{0}, Direction{0}, Line{p1,p2}, Branch{0}, Horizon{p2}, Motor{p1,p2}, Rotor{0}, Translator{p2}, Flector{p0,p3}, Dual{p});
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test] fn widen_is_lossless() {
+    let m = crate::maths::screw();
+    assert_eq!(Motor::from(m).to_f32(), m);
+    let p = crate::point(0.1, -7.3, 1e7);
+    assert_eq!(Point::from(p).to_f32(), p);
+    let d = crate::dual(0.3, -0.7);
+    assert_eq!(Dual::from(d).to_f32(), d);
+  }
+
+  #[test] fn precision_far_from_origin() {
+    let t = translator(1e9, 1.0, 0.0, 0.0);
+    let p = t.apply(point(1e9, 0.5, 0.0));
+    assert_eq!([p.x(), p.y(), p.z()], [2e9, 0.5, 0.0]);
+    let q = Translator::from(crate::translator(1.0, 1.0, 0.0, 0.0)).apply(point(1e9, 0.0, 0.0));
+    assert_eq!(q.x(), 1e9 + 1.0);
+  }
+
+  #[test] fn motor_point() {
+    let m = Motor::from_screw_axis(pi/2.0, 3.0, maths::screw_axis());
+    let a = m.to_f32().apply(crate::point(-1.0, 2.0, 0.5));
+    let b = m.apply(point(-1.0, 2.0, 0.5));
+    let a = Point::from(a);
+    for (x, y) in [(a.w(), b.w()), (a.x(), b.x()), (a.y(), b.y()), (a.z(), b.z())] { assert!((x - y).abs() < 1e-4, "{:?} ≉ {:?}", a, b) }
+  }
+}
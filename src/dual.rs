@@ -1,31 +1,89 @@
-use std::{simd::{f32x2},ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not}};
+use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not}};
+use super::{Plane, Point, Line, Branch, Horizon, Direction, Rotor, Translator, Motor, Flector, maths::*};
 
 pub const e0123:Dual = dual(0.0,1.0);
 
 /// scalar + pseudoscalar
-pub const fn dual(p:f32,q:f32)->Dual { Dual::new(p,q) }
+pub const fn dual(p:Float,q:Float)->Dual { Dual::new(p,q) }
 /// Dual Number
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Dual { pub(crate) p:f32x2 }
+pub struct Dual { pub(crate) p:Lanes2 }
+
+serde_blades!(Dual { scalar, e0123 } => |[p, q]| Dual::new(p, q));
 
 impl Dual {
-  pub const fn new(p:f32,q:f32)->Dual { Dual{p:f32x2::from_array([p,q])} }
-  #[inline] pub fn scalar(&self)->f32 { self.p[0] }
-  #[inline] pub fn e0123(&self)->f32 { self.p[1] }
-  #[inline] pub fn p(&self)->f32 { self.p[0] }
-  #[inline] pub fn q(&self)->f32 { self.p[1] }
+  pub const fn new(p:Float,q:Float)->Dual { Dual{p:Lanes2::from_array([p,q])} }
+  #[inline] pub fn scalar(&self)->Float { self.p[0] }
+  #[inline] pub fn e0123(&self)->Float { self.p[1] }
+  #[inline] pub fn p(&self)->Float { self.p[0] }
+  #[inline] pub fn q(&self)->Float { self.p[1] }
+  /// $(p + q\mathbf{e}_{0123})^{-1} = 1/p - q/p^2\mathbf{e}_{0123}$, requires a non zero scalar.
+  pub fn inverse(&self)->Dual { let p = self.p(); Dual::new(1.0 / p, -self.q() / (p * p)) }
+  /// The scalar and pseudoscalar are their own reverse.
+  #[inline] pub fn reverse(&self)->Dual { *self }
 }
 
 impl Add<Dual> for Dual { type Output = Dual;fn add(self, other: Dual) -> Dual { Dual { p:self.p+other.p } } }
 impl AddAssign for Dual { fn add_assign(&mut self, other: Self) { self.p += other.p } }
 impl Sub<Dual> for Dual { type Output = Dual;fn sub(self, other: Dual) -> Dual { Dual { p:self.p-other.p } } }
 impl SubAssign for Dual { fn sub_assign(&mut self, other: Self) { self.p -= other.p } }
-impl Mul<f32> for Dual { type Output = Dual;fn mul(self, s: f32) -> Dual { Dual { p:self.p*f32x2::splat(s) } } }
-impl MulAssign<f32> for Dual { fn mul_assign(&mut self, s: f32) { self.p *= f32x2::splat(s) } }
-impl Div<f32> for Dual { type Output = Dual;fn div(self, s: f32) -> Dual { Dual { p:self.p/f32x2::splat(s) } } }
-impl DivAssign<f32> for Dual { fn div_assign(&mut self, s: f32) { self.p /= f32x2::splat(s) } }
+impl Mul<Float> for Dual { type Output = Dual;fn mul(self, s: Float) -> Dual { Dual { p:self.p*Lanes2::splat(s) } } }
+impl MulAssign<Float> for Dual { fn mul_assign(&mut self, s: Float) { self.p *= Lanes2::splat(s) } }
+impl Div<Float> for Dual { type Output = Dual;fn div(self, s: Float) -> Dual { Dual { p:self.p/Lanes2::splat(s) } } }
+impl DivAssign<Float> for Dual { fn div_assign(&mut self, s: Float) { self.p /= Lanes2::splat(s) } }
 impl Not for Dual { type Output = Dual;fn not(self)->Dual { Dual::new(self.e0123(), self.scalar()) } }
 
+products! {
+  Dual as Motor * Plane = Flector, gp_12_0(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Dual as Motor * Line = Line, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Dual as Motor * Branch = Line, gp_12_1(p1, p2; 0) -> (p1, p2) => Line{p1, p2};
+  Dual as Motor * Horizon = Horizon, gp_12_2(p1, p2; p2) -> p2 => Horizon{p2};
+  Dual as Motor * Point = Flector, gp_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Dual as Motor * Direction = Flector, gp_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Dual as Motor * Dual as Motor = Dual, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Dual::new(p1[0], p2[0]);
+  Dual as Motor * Rotor = Motor, gp_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor * Translator as Motor = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor * Motor = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor ^ Plane = Plane, ext_12_0(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Dual as Motor ^ Line = Line, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Dual as Motor ^ Branch = Branch, ext_12_1(p1, p2; 0) -> (p1, _) => Branch(p1);
+  Dual as Motor ^ Horizon = Horizon, ext_12_2(p1, p2; p2) -> p2 => Horizon{p2};
+  Dual as Motor ^ Point = Point, ext_12_3(p1, p2; 0) -> p3 => Point(p3);
+  Dual as Motor ^ Direction = Point, ext_12_3(p1, p2; 0) -> p3 => Point(p3);
+  Dual as Motor ^ Dual as Motor = Dual, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Dual::new(p1[0], p2[0]);
+  Dual as Motor ^ Rotor = Motor, ext_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor ^ Translator as Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor ^ Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor & Plane = Plane, reg_12_0(p1, p2; 0) -> p0 => Plane(p0);
+  Dual as Motor & Line = Line, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Dual as Motor & Branch = Branch, reg_12_1(p1, p2; 0) -> p1 => Branch(p1);
+  Dual as Motor & Horizon = Horizon, reg_12_2(p1, p2; p2) -> (_, p2) => Horizon{p2};
+  Dual as Motor & Point = Point, reg_12_3(p1, p2; 0) -> (_, p3) => Point(p3);
+  Dual as Motor & Direction = Point, reg_12_3(p1, p2; 0) -> (_, p3) => Point(p3);
+  Dual as Motor & Dual as Motor = Dual, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Dual::new(p1[0], p2[0]);
+  Dual as Motor & Rotor = Rotor, reg_12_1(p1, p2; 0) -> p1 => Rotor(p1);
+  Dual as Motor & Translator as Motor = Motor, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor & Motor = Motor, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor | Plane = Flector, dot_12_0(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Dual as Motor | Line = Line, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Dual as Motor | Branch = Line, dot_12_1(p1, p2; 0) -> (p1, p2) => Line{p1, p2};
+  Dual as Motor | Horizon = Horizon, dot_12_2(p1, p2; p2) -> p2 => Horizon{p2};
+  Dual as Motor | Point = Flector, dot_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Dual as Motor | Direction = Flector, dot_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Dual as Motor | Dual as Motor = Dual, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Dual::new(p1[0], p2[0]);
+  Dual as Motor | Rotor = Motor, dot_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor | Translator as Motor = Motor, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual as Motor | Motor = Motor, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Dual / Plane = Flector;
+  Dual / Line = Line;
+  Dual / Branch = Line;
+  Dual / Point = Flector;
+  Dual / Dual = Dual;
+  Dual / Rotor = Motor;
+  Dual / Translator = Motor;
+  Dual / Motor = Motor;
+}
+
 #[cfg(test)]
 mod tests {
   use super::{Dual,dual};
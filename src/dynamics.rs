@@ -0,0 +1,213 @@
+use super::{Motor, Line, Point, Transform, maths::*};
+
+/// Inertia of a rigid body about its center of mass along its principal axes,
+/// a linear map from the rate of a body to its momentum.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Inertia { mass:Float, moments:[Float;3] }
+
+impl Inertia {
+  /// The mass and every moment must be positive, `rate` divides by them. A
+  /// point mass or a rod fails a `debug_assert` and gives infinite or NaN
+  /// rates in release builds.
+  pub fn new(mass:Float, moments:[Float;3])->Inertia {
+    debug_assert!(mass > 0.0 && moments.iter().all(|&m| m > 0.0), "Inertia without positive mass and moments {:?} {:?}", mass, moments);
+    Inertia{mass, moments}
+  }
+  pub fn mass(&self)->Float { self.mass }
+  /// Principal moments of inertia about the x, y and z axes.
+  pub fn moments(&self)->[Float;3] { self.moments }
+  /// Solid sphere of uniform density.
+  pub fn sphere(mass:Float, radius:Float)->Inertia { Inertia::new(mass, [0.4 * mass * radius * radius; 3]) }
+  /// Solid box of uniform density with edge lengths `x`, `y` and `z`.
+  pub fn cuboid(mass:Float, x:Float, y:Float, z:Float)->Inertia {
+    let m = mass / 12.0;
+    Inertia::new(mass, [m * (y*y + z*z), m * (x*x + z*z), m * (x*x + y*y)])
+  }
+
+  /// The momentum of a body moving with `rate`. The angular velocity of the
+  /// Euclidean part becomes angular momentum in the ideal part and the linear
+  /// velocity of the ideal part becomes linear momentum in the Euclidean part.
+  pub fn momentum(&self, rate:Line)->Line {
+    let [x, y, z] = self.moments;
+    Line{p1: rate.p2 * Lanes::from_array([0.0, self.mass, self.mass, self.mass]), p2: rate.p1 * Lanes::from_array([0.0, x, y, z])}
+  }
+
+  /// The rate of a body with `momentum`, the inverse of `momentum`.
+  pub fn rate(&self, momentum:Line)->Line {
+    let [x, y, z] = self.moments;
+    let m = 1.0 / self.mass;
+    Line{p1: momentum.p2 * Lanes::from_array([0.0, 1.0/x, 1.0/y, 1.0/z]), p2: momentum.p1 * Lanes::from_array([0.0, m, m, m])}
+  }
+}
+
+/// Integration scheme for `RigidBody::step`.
+#[derive(Default,Debug,Clone,Copy,PartialEq,Eq)]
+pub enum Integrator {
+  /// Update the pose with the rate at the start of the step.
+  Euler,
+  /// Update the rate first and the pose with the new rate.
+  #[default]
+  SemiImplicit,
+  /// Classic fourth order Runge-Kutta, the pose is advanced with the exponential
+  /// of the weighted rates.
+  RungeKutta4,
+}
+
+/// A rigid body with its pose as a motor from the body to the world and its
+/// rate as a line in the body frame.
+///
+/// The pose changes as $\dot M = -\frac{1}{2}MB$, the momentum $P = I[B]$ as
+/// $\dot P = W + \frac{1}{2}(BP - PB)$ where $W$ is the wrench in the body
+/// frame. Forces and torques are lines, a force is its line of action and a
+/// torque is an ideal line.
+///
+/// ```
+/// use g3::{RigidBody, Inertia, Integrator, Motor, point};
+/// let mut ball = RigidBody::new(Motor::one(), Inertia::sphere(1.0, 0.2));
+/// for _ in 0..60 {
+///   ball.apply_force(ball.center(), [0.0, -9.81, 0.0]);
+///   ball.step(1.0 / 60.0, Integrator::SemiImplicit);
+/// }
+/// ```
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct RigidBody {
+  pub pose:Motor,
+  pub rate:Line,
+  pub inertia:Inertia,
+  wrench:Line,
+}
+
+impl RigidBody {
+  pub fn new(pose:Motor, inertia:Inertia)->RigidBody {
+    RigidBody{pose, rate: Line::default(), inertia, wrench: Line::default()}
+  }
+
+  /// Center of mass in the world.
+  pub fn center(&self)->Point { self.pose.apply(Point::new(0.0, 0.0, 0.0)).normalized() }
+
+  /// Momentum in the body frame.
+  pub fn momentum(&self)->Line { self.inertia.momentum(self.rate) }
+
+  /// Momentum in the world.
+  pub fn world_momentum(&self)->Line { self.pose.apply(self.momentum()) }
+
+  /// Kinetic energy, $\frac{1}{2}(B \cdot I[B])$.
+  pub fn energy(&self)->Float {
+    let (b, p) = (self.rate, self.momentum());
+    0.5 * (hi_dp(&b.p1, &p.p2)[0] + hi_dp(&b.p2, &p.p1)[0])
+  }
+
+  /// Add a wrench in the world frame, it acts during the next `step`.
+  pub fn apply_wrench(&mut self, w:Line) { self.wrench += w }
+
+  /// Add a force in the world frame acting at point `at` during the next `step`.
+  pub fn apply_force(&mut self, at:Point, force:[Float;3]) {
+    let (c, [x, y, z]) = (at.normalized(), force);
+    self.apply_wrench(Line::new(c.y()*z - c.z()*y, c.z()*x - c.x()*z, c.x()*y - c.y()*x, x, y, z))
+  }
+
+  /// Advance the body by `dt` and clear the applied wrenches.
+  pub fn step(&mut self, dt:Float, integrator:Integrator) {
+    let (m, b) = (self.pose, self.rate);
+    let (pose, rate) = match integrator {
+      Integrator::Euler => (advance(m, b, dt), b + self.acceleration(m, b) * dt),
+      Integrator::SemiImplicit => {
+        let b = b + self.acceleration(m, b) * dt;
+        (advance(m, b, dt), b)
+      }
+      Integrator::RungeKutta4 => {
+        let a1 = self.acceleration(m, b);
+        let b2 = b + a1 * (0.5 * dt);
+        let a2 = self.acceleration(advance(m, b, 0.5 * dt), b2);
+        let b3 = b + a2 * (0.5 * dt);
+        let a3 = self.acceleration(advance(m, b2, 0.5 * dt), b3);
+        let b4 = b + a3 * dt;
+        let a4 = self.acceleration(advance(m, b3, dt), b4);
+        (advance(m, (b + b2 * 2.0 + b3 * 2.0 + b4) / 6.0, dt), b + (a1 + a2 * 2.0 + a3 * 2.0 + a4) * (dt / 6.0))
+      }
+    };
+    self.pose = pose.normalized();
+    self.rate = rate;
+    self.wrench = Line::default();
+  }
+
+  // Change of the rate at pose `m` and rate `b`.
+  fn acceleration(&self, m:Motor, b:Line)->Line {
+    let p = self.inertia.momentum(b);
+    let w = m.reverse().apply(self.wrench);
+    let (bm, pm) = (Motor{p1: b.p1, p2: b.p2}, Motor{p1: p.p1, p2: p.p2});
+    let c = (bm * pm - pm * bm) * 0.5;
+    self.inertia.rate(w + Line{p1: zero_first(c.p1), p2: zero_first(c.p2)})
+  }
+}
+
+// Pose `m` moved with rate `b` for time `dt`.
+fn advance(m:Motor, b:Line, dt:Float)->Motor { m * (b * (-0.5 * dt)).exp() }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  const INTEGRATORS: [Integrator; 3] = [Integrator::Euler, Integrator::SemiImplicit, Integrator::RungeKutta4];
+
+  #[test] fn inertia_map() {
+    let i = Inertia::cuboid(2.0, 1.0, 2.0, 3.0);
+    let b = line(1.0, -2.0, 0.5, 0.3, 0.2, -0.1);
+    let p = i.momentum(b);
+    assert_eq!([p.e23(), p.e31(), p.e12()], [2.0, -4.0, 1.0]);
+    let q = i.rate(p);
+    approx_eq3([q.e01(), q.e02(), q.e03()], [b.e01(), b.e02(), b.e03()], 1e-6);
+    approx_eq3([q.e23(), q.e31(), q.e12()], [b.e23(), b.e31(), b.e12()], 1e-6);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test] #[should_panic(expected = "positive mass")] fn inertia_of_point_mass() {
+    Inertia::sphere(1.0, 0.0);
+  }
+
+  #[test] fn free_motion() {
+    for integrator in INTEGRATORS {
+      let mut body = RigidBody::new(Motor::one(), Inertia::sphere(1.0, 1.0));
+      body.rate = line(1.0, 2.0, 0.0, 0.0, 0.0, 0.0);
+      for _ in 0..10 { body.step(0.1, integrator) }
+      approx_eq3(xyz(&body.center()), [1.0, 2.0, 0.0], 1e-4);
+    }
+  }
+
+  #[test] fn falling() {
+    let (dt, n) = (0.01, 100);
+    for (integrator, epsilon) in [(Integrator::Euler, 0.06), (Integrator::SemiImplicit, 0.06), (Integrator::RungeKutta4, 1e-3)] {
+      let mut body = RigidBody::new(Motor::one(), Inertia::sphere(2.0, 0.5));
+      for _ in 0..n {
+        body.apply_force(body.center(), [0.0, -2.0 * 9.81, 0.0]);
+        body.step(dt, integrator);
+      }
+      approx_eq3(xyz(&body.center()), [0.0, -0.5 * 9.81, 0.0], epsilon);
+      assert_eq!(body.wrench, Line::default());
+    }
+  }
+
+  #[test] fn lever() {
+    // Pushing up at x = 1 turns the body counter-clockwise about z.
+    let mut body = RigidBody::new(Motor::one(), Inertia::sphere(1.0, 1.0));
+    body.apply_force(point(1.0, 0.0, 0.0), [0.0, 1.0, 0.0]);
+    body.step(0.1, Integrator::SemiImplicit);
+    assert!(body.rate.e12() > 0.0);
+    assert!(body.rate.e02() > 0.0);
+    let p = body.pose.apply(point(1.0, 0.0, 0.0));
+    assert!(p.y() > 0.0);
+  }
+
+  #[test] fn torque_free_rotation() {
+    // Spinning about a tilted axis, energy and world momentum are conserved.
+    let mut body = RigidBody::new(Motor::one(), Inertia::cuboid(1.0, 1.0, 2.0, 3.0));
+    body.rate = line(0.5, 0.0, 0.0, 1.0, 0.1, 2.0);
+    let (e, l) = (body.energy(), body.world_momentum());
+    for _ in 0..100 { body.step(0.01, Integrator::RungeKutta4) }
+    assert!((body.energy() - e).abs() < 1e-3);
+    let m = body.world_momentum();
+    approx_eq3([m.e23(), m.e31(), m.e12()], [l.e23(), l.e31(), l.e12()], 1e-3);
+    approx_eq3([m.e01(), m.e02(), m.e03()], [l.e01(), l.e02(), l.e03()], 1e-2);
+  }
+}
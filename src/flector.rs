@@ -0,0 +1,239 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg};
+use super::{Plane, Point, Origin, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Transform, maths::*};
+
+/// Odd versor, the sum of a plane and a point.
+///
+/// A single plane is a reflection, a point is a point reflection and the
+/// product of a plane with a motor is a rotoreflection or a transflection.
+/// Composing two flectors gives back a motor.
+///
+/// ```
+/// use g3::{Flector, Motor, Transform, plane, point, rotor};
+/// let mirror = Flector::from(plane(1.0, 0.0, 0.0, 0.0));
+/// let f = mirror * Motor::from(rotor(1.0, 0.0, 0.0, 1.0));
+/// let p = f.apply(point(1.0, 2.0, 3.0));
+/// let m:Motor = f * mirror;
+/// ```
+#[derive(Default,Debug,Clone,Copy,PartialEq)]
+pub struct Flector<L = Lanes> { pub(crate) p0:L, pub(crate) p3:L }
+
+serde_blades!(Flector { e0, e1, e2, e3, e123, e032, e013, e021 } =>
+  |[d, a, b, c, w, x, y, z]| Flector{p0: Lanes::from_array([d, a, b, c]), p3: Lanes::from_array([w, x, y, z])});
+
+impl Flector {
+  pub fn new(p:Plane, a:Point)->Flector { Flector{p0: p.0, p3: a.0} }
+
+  /// The plane part, grade 1.
+  pub fn plane(&self)->Plane { Plane(self.p0) }
+  /// The point part, grade 3.
+  pub fn point(&self)->Point { Point(self.p3) }
+
+  /// Reverse the order of the factors, negates the point part.
+  pub fn reverse(&self)->Flector { Flector{p0: self.p0, p3: -self.p3} }
+
+  /// Scale so that $F\widetilde{F} = 1$, which requires the product of the
+  /// flector with its reverse to be a dual number with a non zero scalar.
+  pub fn normalized(&self)->Flector {
+    let (s, q) = self.square_norm();
+    let inv_norm = 1.0 / s.sqrt();
+    *self * dual_number(inv_norm, -0.5 * q * inv_norm / s)
+  }
+
+  pub fn inverse(&self)->Flector {
+    let (s, q) = self.square_norm();
+    self.reverse() * dual_number(1.0 / s, -q / (s * s))
+  }
+
+  pub fn approx_eq(&self, other:Flector, epsilon:Float)->bool {
+    lanes_close(&self.p0, &other.p0, epsilon) && lanes_close(&self.p3, &other.p3, epsilon)
+  }
+
+  #[inline] pub fn e0(&self)->Float { self.p0[0] }
+  #[inline] pub fn e1(&self)->Float { self.p0[1] }
+  #[inline] pub fn e2(&self)->Float { self.p0[2] }
+  #[inline] pub fn e3(&self)->Float { self.p0[3] }
+  #[inline] pub fn e123(&self)->Float { self.p3[0] }
+  #[inline] pub fn e032(&self)->Float { self.p3[1] }
+  #[inline] pub fn e013(&self)->Float { self.p3[2] }
+  #[inline] pub fn e021(&self)->Float { self.p3[3] }
+
+  // Scalar and pseudoscalar of $F\widetilde{F}$.
+  fn square_norm(&self)->(Float, Float) {
+    let (p1, p2) = kernels::gp_03_03(&self.p0, &self.p3, &self.p0, &-self.p3);
+    (p1[0], p2[0])
+  }
+
+  // $FX\widetilde{F}$ of the partitions of an even element, the flector must be normalized.
+  fn sandwich_even(&self, x1:&Lanes, x2:&Lanes)->(Lanes, Lanes) {
+    let (p0, p3) = kernels::gp_03_12(&self.p0, &self.p3, x1, x2);
+    kernels::gp_03_03(&p0, &p3, &self.p0, &-self.p3)
+  }
+
+  // $FX\widetilde{F}$ of the partitions of an odd element, the flector must be normalized.
+  fn sandwich_odd(&self, x0:&Lanes, x3:&Lanes)->(Lanes, Lanes) {
+    let (p1, p2) = kernels::gp_03_03(&self.p0, &self.p3, x0, x3);
+    kernels::gp_12_03(&p1, &p2, &self.p0, &-self.p3)
+  }
+}
+
+// Scalar plus pseudoscalar, as a motor.
+fn dual_number(s:Float, q:Float)->Motor { Motor{p1: Lanes::from_array([s, 0.0, 0.0, 0.0]), p2: Lanes::from_array([q, 0.0, 0.0, 0.0])} }
+fn zero()->Lanes { Lanes::splat(0.0) }
+
+impl From<Plane> for Flector { fn from(p:Plane)->Flector { Flector{p0: p.0, p3: Lanes::splat(0.0)} } }
+impl From<Point> for Flector { fn from(a:Point)->Flector { Flector{p0: Lanes::splat(0.0), p3: a.0} } }
+/// The column-major 4×4 matrix of the reflection, from the images of the axes and the origin
+impl From<Flector> for [Float;16] {
+  fn from(f:Flector)->Self {
+    let column = |x:Float, y:Float, z:Float, w:Float| <[Float;4]>::from(f.apply(Point(Lanes::from_array([w, x, y, z]))));
+    let (x, y, z, o) = (column(1.0, 0.0, 0.0, 0.0), column(0.0, 1.0, 0.0, 0.0), column(0.0, 0.0, 1.0, 0.0), column(0.0, 0.0, 0.0, 1.0));
+    [x[0], x[1], x[2], x[3], y[0], y[1], y[2], y[3], z[0], z[1], z[2], z[3], o[0], o[1], o[2], o[3]]
+  }
+}
+#[cfg(feature = "mint")] impl From<Flector> for mint::ColumnMatrix4<Float> { fn from(f: Flector)->Self { <[Float;16]>::from(f).into() } }
+#[cfg(feature = "glam")] impl From<Flector> for Mat4 { fn from(f: Flector)->Self { Mat4::from_cols_array(&f.into()) } }
+
+impl Transform<Plane> for Flector { type Output = Plane; fn apply(&self, p:Plane)->Plane { Plane(self.sandwich_odd(&p.0, &zero()).0) } }
+impl Transform<Point> for Flector { type Output = Point; fn apply(&self, a:Point)->Point { Point(self.sandwich_odd(&zero(), &a.0).1) } }
+impl Transform<Origin> for Flector { type Output = Point; fn apply(&self, o:Origin)->Point { self.apply(Point::from(o)) } }
+impl Transform<Direction> for Flector { type Output = Direction; fn apply(&self, d:Direction)->Direction { Direction(self.sandwich_odd(&zero(), &d.0).1) } }
+impl Transform<Line> for Flector {
+  type Output = Line;
+  fn apply(&self, l:Line)->Line { let (p1, p2) = self.sandwich_even(&l.p1, &l.p2); Line{p1: zero_first(p1), p2: zero_first(p2)} }
+}
+impl Transform<Branch> for Flector {
+  type Output = Line;
+  fn apply(&self, b:Branch)->Line { let (p1, p2) = self.sandwich_even(&b.0, &zero()); Line{p1: zero_first(p1), p2: zero_first(p2)} }
+}
+impl Transform<Horizon> for Flector {
+  type Output = Horizon;
+  fn apply(&self, h:Horizon)->Horizon { Horizon{p2: zero_first(self.sandwich_even(&zero(), &h.p2).1)} }
+}
+/// The mirror image of a motor, `f.apply(m).apply(x)` is `f.apply(m.apply(f.inverse().apply(x)))`.
+impl Transform<Motor> for Flector {
+  type Output = Motor;
+  fn apply(&self, m:Motor)->Motor { let (p1, p2) = self.sandwich_even(&m.p1, &m.p2); Motor{p1, p2} }
+}
+
+impl Transform<Rotor> for Flector { type Output = Motor; fn apply(&self, r:Rotor)->Motor { self.apply(Motor::from(r)) } }
+impl Transform<Translator> for Flector {
+  type Output = Translator;
+  fn apply(&self, t:Translator)->Translator { Translator{p2: self.apply(Horizon{p2: t.p2}).p2} }
+}
+/// The mirror image of another flector, like for a motor
+/// `f.apply(g).apply(x)` is `f.apply(g.apply(f.inverse().apply(x)))`.
+impl Transform<Flector> for Flector {
+  type Output = Flector;
+  fn apply(&self, g:Flector)->Flector { let (p0, p3) = self.sandwich_odd(&g.p0, &g.p3); Flector{p0, p3} }
+}
+
+call_transform!(Flector: Plane, Point, Origin, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector);
+
+impl Add<Flector> for Flector { type Output = Flector; fn add(self, f:Flector)->Flector { Flector{p0: self.p0 + f.p0, p3: self.p3 + f.p3} } }
+impl AddAssign for Flector { fn add_assign(&mut self, f:Flector) { *self = *self + f } }
+impl Sub<Flector> for Flector { type Output = Flector; fn sub(self, f:Flector)->Flector { Flector{p0: self.p0 - f.p0, p3: self.p3 - f.p3} } }
+impl SubAssign for Flector { fn sub_assign(&mut self, f:Flector) { *self = *self - f } }
+impl Mul<Float> for Flector { type Output = Flector; fn mul(self, s:Float)->Flector { Flector{p0: self.p0 * Lanes::splat(s), p3: self.p3 * Lanes::splat(s)} } }
+impl MulAssign<Float> for Flector { fn mul_assign(&mut self, s:Float) { *self = *self * s } }
+impl Div<Float> for Flector { type Output = Flector; fn div(self, s:Float)->Flector { self * (1.0 / s) } }
+impl DivAssign<Float> for Flector { fn div_assign(&mut self, s:Float) { *self = *self / s } }
+impl Neg for Flector { type Output = Flector; fn neg(self)->Flector { self * -1.0 } }
+
+// Geometric products between versors, the parity of the factors decides if
+// the result is even (a motor) or odd (a flector). Translators go through a
+// motor for their implicit scalar.
+macro_rules! versor_products {
+  ($($a:ident * $b:ident = $out:ident{$o1:ident, $o2:ident} $kernel:ident($($p:tt),*; $($q:tt),*);)*) => {$(
+    impl Mul<$b> for $a {
+      type Output = $out;
+      fn mul(self, y:$b)->$out { let ($o1, $o2) = kernels::$kernel($(&self.$p),*, $(&y.$q),*); $out{$o1, $o2} }
+    }
+  )*}
+}
+
+versor_products! {
+  Flector * Flector = Motor{p1, p2} gp_03_03(p0, p3; p0, p3);
+  Flector * Plane = Motor{p1, p2} gp_03_0(p0, p3; 0);
+  Flector * Point = Motor{p1, p2} gp_03_3(p0, p3; 0);
+  Plane * Flector = Motor{p1, p2} gp_0_03(0; p0, p3);
+  Point * Flector = Motor{p1, p2} gp_3_03(0; p0, p3);
+  Flector * Motor = Flector{p0, p3} gp_03_12(p0, p3; p1, p2);
+  Flector * Rotor = Flector{p0, p3} gp_03_1(p0, p3; 0);
+  Motor * Flector = Flector{p0, p3} gp_12_03(p1, p2; p0, p3);
+  Rotor * Flector = Flector{p0, p3} gp_1_03(0; p0, p3);
+  Motor * Plane = Flector{p0, p3} gp_12_0(p1, p2; 0);
+  Motor * Point = Flector{p0, p3} gp_12_3(p1, p2; 0);
+  Rotor * Plane = Flector{p0, p3} gp_1_0(0; 0);
+  Rotor * Point = Flector{p0, p3} gp_1_3(0; 0);
+  Plane * Motor = Flector{p0, p3} gp_0_12(0; p1, p2);
+  Point * Motor = Flector{p0, p3} gp_3_12(0; p1, p2);
+  Plane * Rotor = Flector{p0, p3} gp_0_1(0; 0);
+  Point * Rotor = Flector{p0, p3} gp_3_1(0; 0);
+}
+
+macro_rules! through_motor {
+  ($($a:ident * $b:ident = $out:ident;)*) => {$(
+    impl Mul<$b> for $a { type Output = $out; fn mul(self, y:$b)->$out { Motor::from(self) * y } }
+  )*}
+}
+macro_rules! by_motor {
+  ($($a:ident * $b:ident = $out:ident;)*) => {$(
+    impl Mul<$b> for $a { type Output = $out; fn mul(self, y:$b)->$out { self * Motor::from(y) } }
+  )*}
+}
+through_motor! { Translator * Flector = Flector; Translator * Plane = Flector; Translator * Point = Flector; }
+by_motor! { Flector * Translator = Flector; Plane * Translator = Flector; Point * Translator = Flector; }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  const EPSILON: Float = 0.000_1;
+
+  #[test] fn flector_reflection() {
+    let p = plane(1.0, 2.0, -0.5, 3.0).normalized();
+    let f = Flector::from(p);
+    let a = point(1.0, -2.0, 0.3);
+    close(f.apply(a).normalized(), p.apply(a).normalized());
+    let q = plane(-0.3, 1.0, 2.0, -1.0);
+    close(f.apply(q), p.apply(q));
+    let l = line(0.3, -1.0, 2.0, 1.0, 0.5, -0.2);
+    close(f.apply(l), p.apply(l));
+    // A point reflection through the origin.
+    close(Flector::from(point(0.0, 0.0, 0.0)).apply(a).normalized(), point(-1.0, 2.0, -0.3));
+  }
+
+  #[test] fn flector_composition() {
+    let (p, q, r) = (plane(1.0, 0.0, 0.0, 1.0), plane(1.0, 1.0, 0.0, 0.0).normalized(), plane(0.0, 0.0, 1.0, -2.0));
+    let a = point(1.0, -2.0, 0.3);
+    let f = p * q * r;
+    close(f.apply(a).normalized(), p.apply(q.apply(r.apply(a))).normalized());
+    let m = screw();
+    let g = m * p;
+    close(g.apply(a).normalized(), m.apply(p.apply(a)).normalized());
+    close((f * g).apply(a).normalized(), f.apply(g.apply(a)).normalized());
+    close((g * m).apply(a).normalized(), g.apply(m.apply(a)).normalized());
+    // Mirroring a motor conjugates it by the reflection.
+    close(Flector::from(p).apply(m).apply(a).normalized(), p.apply(m.apply(p.apply(a))).normalized());
+  }
+
+  #[test] fn flector_inverse() {
+    let f = Motor::from_screw_axis(1.0, -0.5, line(1.0, 0.0, 2.0, 0.0, 1.0, 1.0).normalized()) * plane(2.0, 1.0, 0.0, 1.0) * 3.0;
+    let n = f.normalized();
+    let m:Motor = n * n.reverse();
+    close(m, Motor::one());
+    let m:Motor = f * f.inverse();
+    close(m, Motor::one());
+  }
+
+  #[test] fn flector_approx_eq() {
+    let f = Flector::from(plane(1.0, 2.0, -0.5, 3.0)) + Flector::from(point(1.0, -2.0, 0.3));
+    assert!(f.approx_eq(f, EPSILON));
+    for i in 0..8 {
+      let mut g = f;
+      if i < 4 { g.p0[i] += 1.0 } else { g.p3[i - 4] += 1.0 }
+      assert!(!f.approx_eq(g, EPSILON), "lane {} is not compared", i);
+    }
+  }
+}
@@ -0,0 +1,75 @@
+/// Write a formula in the notation of the textbooks, it expands to the same
+/// operators on the typed elements, so a product that does not exist for the
+/// operands is a compile error.
+///
+/// * `a ^ b` is the meet, `a & b` the join and `a | b` the inner product,
+///   the `∧` and `∨` of the books are not tokens that Rust can read.
+/// * `!a` is the Poincaré dual and `~a` the reverse of the name or the
+///   parenthesized group right after it.
+/// * The basis blades `e0` to `e3`, `e01` to `e12`, `e123` to `e021` and
+///   `e0123` are the planes, lines, points and dual number of those names,
+///   bivectors may also be written the other way around, `e21` is `-e12`.
+/// * A number right before a name or blade scales it, `2 e01` is `e01 * 2.0`.
+/// * The operators keep the precedence of Rust: `*`, then `+` and `-`, then
+///   `&`, `^` and last `|`. The books bind every product tighter than a sum
+///   and usually the inner product first, here `a ^ b & c` is `a ^ (b & c)`
+///   and `p | q ^ r` is `p | (q ^ r)`. Group a mix of operators with parentheses.
+///
+/// ```
+/// use g3::*;
+/// let (a, b) = (point(1.0, 0.0, 0.0), point(0.0, 1.0, 0.0));
+/// assert_eq!(pga!(a & b), a & b);
+/// assert_eq!(pga!(!(!a ^ !b)), a & b);
+/// let m = rotor(pi / 2.0, 0.0, 0.0, 1.0) * translator(1.0, 1.0, 0.0, 0.0);
+/// let l = pga!(e12 + 2 e01);
+/// assert_eq!(l, line(2.0, 0.0, 0.0, 0.0, 0.0, 1.0));
+/// assert!(pga!(m * l * ~m).approx_eq(m * l * m.reverse(), 1e-6));
+/// ```
+#[macro_export]
+macro_rules! pga {
+  // Done, and the end of a group whose tokens were munched in place.
+  (@ [$($o:tt)*]) => { $($o)* };
+  (@ [$($g:tt)*] @paren [$($o:tt)*] $($r:tt)*) => { $crate::pga!(@ [$($o)* ($($g)*)] $($r)*) };
+  (@ [$($g:tt)*] @bracket [$($o:tt)*] $($r:tt)*) => { $crate::pga!(@ [$($o)* [$($g)*]] $($r)*) };
+  (@ [$($g:tt)*] @reverse [$($o:tt)*] $($r:tt)*) => { $crate::pga!(@ [$($o)* ($($g)*).reverse()] $($r)*) };
+  (@ [$($g:tt)*] @scale $n:literal [$($o:tt)*] $($r:tt)*) => { $crate::pga!(@ [$($o)* (($($g)*) * ($n as $crate::Float))] $($r)*) };
+
+  // Groups, the reverse and numbers in front of a name.
+  (@ [$($o:tt)*] ($($g:tt)*) $($r:tt)*) => { $crate::pga!(@ [] $($g)* @paren [$($o)*] $($r)*) };
+  (@ [$($o:tt)*] [$($g:tt)*] $($r:tt)*) => { $crate::pga!(@ [] $($g)* @bracket [$($o)*] $($r)*) };
+  (@ [$($o:tt)*] ~ $x:tt $($r:tt)*) => { $crate::pga!(@ [] $x @reverse [$($o)*] $($r)*) };
+  // A literal also takes a minus in front, which is the operator here.
+  (@ [$($o:tt)*] - $n:literal $x:ident $($r:tt)*) => { $crate::pga!(@ [$($o)* -] $n $x $($r)*) };
+  (@ [$($o:tt)*] $n:literal $x:ident $($r:tt)*) => { $crate::pga!(@ [] $x @scale $n [$($o)*] $($r)*) };
+
+  // Methods, fields and paths keep their names, even if they are called e12.
+  (@ [$($o:tt)*] . $x:ident $($r:tt)*) => { $crate::pga!(@ [$($o)* . $x] $($r)*) };
+  (@ [$($o:tt)*] :: $x:ident $($r:tt)*) => { $crate::pga!(@ [$($o)* :: $x] $($r)*) };
+
+  // The basis blades.
+  (@ [$($o:tt)*] e0 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::e0] $($r)*) };
+  (@ [$($o:tt)*] e1 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::e1] $($r)*) };
+  (@ [$($o:tt)*] e2 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::e2] $($r)*) };
+  (@ [$($o:tt)*] e3 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::e3] $($r)*) };
+  (@ [$($o:tt)*] e01 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(1.0, 0.0, 0.0, 0.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e02 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 1.0, 0.0, 0.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e03 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 1.0, 0.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e23 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 0.0, 1.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e31 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 0.0, 0.0, 1.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e12 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 0.0, 0.0, 0.0, 1.0)] $($r)*) };
+  (@ [$($o:tt)*] e10 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(-1.0, 0.0, 0.0, 0.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e20 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, -1.0, 0.0, 0.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e30 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, -1.0, 0.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e32 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 0.0, -1.0, 0.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e13 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 0.0, 0.0, -1.0, 0.0)] $($r)*) };
+  (@ [$($o:tt)*] e21 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::line(0.0, 0.0, 0.0, 0.0, 0.0, -1.0)] $($r)*) };
+  (@ [$($o:tt)*] e123 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::O] $($r)*) };
+  (@ [$($o:tt)*] e032 $($r:tt)*) => { $crate::pga!(@ [$($o)* ($crate::X - $crate::O)] $($r)*) };
+  (@ [$($o:tt)*] e013 $($r:tt)*) => { $crate::pga!(@ [$($o)* ($crate::Y - $crate::O)] $($r)*) };
+  (@ [$($o:tt)*] e021 $($r:tt)*) => { $crate::pga!(@ [$($o)* ($crate::Z - $crate::O)] $($r)*) };
+  (@ [$($o:tt)*] e0123 $($r:tt)*) => { $crate::pga!(@ [$($o)* $crate::e0123] $($r)*) };
+
+  // Everything else as it is.
+  (@ [$($o:tt)*] $x:tt $($r:tt)*) => { $crate::pga!(@ [$($o)* $x] $($r)*) };
+  ($($t:tt)*) => { $crate::pga!(@ [] $($t)*) };
+}
@@ -1,46 +1,48 @@
-use std::{simd::{f32x4,mask32x4,SimdFloat},ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, BitXor, BitAnd, BitOr}};
-use crate::{Dual, Plane, Point, Line, Branch, Translator,maths::*};
+use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, BitXor, BitAnd, BitOr}};
+use super::{Dual, Plane, Point, Line, Branch, Translator, Direction, Rotor, Motor, Flector, maths::*};
 
 /// ae₀₁ + be₀₂ + ce₀₃
-pub const fn horizon(a:f32, b:f32, c:f32) -> Horizon { Horizon::new(a, b, c) }
+pub const fn horizon(a:Float, b:Float, c:Float) -> Horizon { Horizon::new(a, b, c) }
 
 /// A horizon represents a line at infinity and corresponds to the multivector: e₀₁ + e₀₂ + e₀₃
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Horizon { pub(crate) p2:f32x4 }
+pub struct Horizon { pub(crate) p2:Lanes }
+
+serde_blades!(Horizon { e01, e02, e03 } => |[a, b, c]| Horizon::new(a, b, c));
 
 impl Horizon {
   /// ae₀₁ + be₀₂ + ce₀₃
-  pub const fn new(a:f32,b:f32,c:f32)->Horizon { Horizon {p2: f32x4::from_array([0.0, a, b, c])} }
-  pub fn squared_ideal_norm(&self)->f32 {
+  pub const fn new(a:Float,b:Float,c:Float)->Horizon { Horizon {p2: Lanes::from_array([0.0, a, b, c])} }
+  pub fn squared_ideal_norm(&self)->Float {
     hi_dp(&self.p2, &self.p2)[0]
   }
-  pub fn ideal_norm(self)->f32 {
+  pub fn ideal_norm(self)->Float {
     self.squared_ideal_norm().sqrt()
   }
   /// Exponentiate a horizon to produce a translation.
   /// exp(ae₀₁ + be₀₂ + ce₀₃) = 1 + ae₀₁ + be₀₂ + ce₀₃
   #[inline] pub fn exp(self)->Translator { Translator{p2: self.p2} }
-  pub fn reverse(self)-> Horizon { Horizon {p2: flip_signs(&self.p2, mask32x4::from([false,true,true,true]))} }
-  pub fn approx_eq(&self, other:Translator, epsilon:f32)->bool {(&self.p2 - other.p2).abs() < f32x4::splat(epsilon)}
-  #[inline] pub fn e01(&self)->f32 { self.p2[1] }
-  #[inline] pub fn e10(&self)->f32 { -self.e01() }
-  #[inline] pub fn e02(&self)->f32 { self.p2[2] }
-  #[inline] pub fn e20(&self)->f32 { -self.e02() }
-  #[inline] pub fn e03(&self)->f32 { self.p2[3] }
-  #[inline] pub fn e30(&self)->f32 { -self.e03() }
+  pub fn reverse(self)-> Horizon { Horizon {p2: flip_signs(&self.p2, Mask::from([false,true,true,true]))} }
+  pub fn approx_eq(&self, other:Translator, epsilon:Float)->bool {lanes_close(&self.p2, &other.p2, epsilon)}
+  #[inline] pub fn e01(&self)->Float { self.p2[1] }
+  #[inline] pub fn e10(&self)->Float { -self.e01() }
+  #[inline] pub fn e02(&self)->Float { self.p2[2] }
+  #[inline] pub fn e20(&self)->Float { -self.e02() }
+  #[inline] pub fn e03(&self)->Float { self.p2[3] }
+  #[inline] pub fn e30(&self)->Float { -self.e03() }
 }
 
 impl Add<Horizon> for Horizon { type Output = Horizon;fn add(self, other: Horizon) -> Horizon { Horizon { p2: self.p2+other.p2 } } }
 impl AddAssign for Horizon { fn add_assign(&mut self, other: Self) { self.p2 += other.p2; } }
 impl Sub<Horizon> for Horizon { type Output = Horizon;fn sub(self, other: Horizon) -> Horizon { Horizon { p2: self.p2-other.p2 } } }
 impl SubAssign for Horizon { fn sub_assign(&mut self, other: Self) { self.p2 -= other.p2; } }
-impl Mul<f32> for Horizon { type Output = Horizon;fn mul(self, s: f32) -> Horizon { Horizon { p2:self.p2*f32x4::splat(s) } } }
-impl MulAssign<f32> for Horizon { fn mul_assign(&mut self, s: f32) {
-    self.p2 *= f32x4::splat(s)
+impl Mul<Float> for Horizon { type Output = Horizon;fn mul(self, s: Float) -> Horizon { Horizon { p2:self.p2*Lanes::splat(s) } } }
+impl MulAssign<Float> for Horizon { fn mul_assign(&mut self, s: Float) {
+    self.p2 *= Lanes::splat(s)
   } }
-impl Div<f32> for Horizon { type Output = Horizon;fn div(self, s: f32) -> Horizon { Horizon { p2:self.p2/f32x4::splat(s) } } }
-impl DivAssign<f32> for Horizon { fn div_assign(&mut self, s: f32) {
-    self.p2 /= f32x4::splat(s)
+impl Div<Float> for Horizon { type Output = Horizon;fn div(self, s: Float) -> Horizon { Horizon { p2:self.p2/Lanes::splat(s) } } }
+impl DivAssign<Float> for Horizon { fn div_assign(&mut self, s: Float) {
+    self.p2 /= Lanes::splat(s)
   } }
 /// Unary minus
 impl Neg for Horizon { type Output = Horizon;fn neg(self)-> Horizon { Horizon {p2: -self.p2} } }
@@ -55,9 +57,43 @@ impl BitAnd<Point> for Horizon { type Output = Plane;fn bitand(self, a:Point)->P
 /// Inner Product, |
 impl BitOr<Plane> for Horizon { type Output = Plane;fn bitor(self, p:Plane)->Plane { Plane(hi_dp(&p.0, &self.p2)) } }
 
+products! {
+  Horizon * Plane = Flector, gp_2_0(p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Horizon * Line = Motor, gp_2_12(p2; p1, p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Horizon * Branch = Motor, gp_2_1(p2; 0) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Horizon * Point = Direction, gp_2_3(p2; 0) -> (_, p3) => Direction(p3);
+  Horizon * Dual as Motor = Horizon, gp_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon * Rotor = Motor, gp_2_1(p2; 0) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Horizon * Translator as Motor = Horizon, gp_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon * Motor = Motor, gp_2_12(p2; p1, p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Horizon ^ Dual as Motor = Horizon, ext_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon ^ Rotor = Motor, ext_2_1(p2; 0) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Horizon ^ Translator as Motor = Horizon, ext_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon ^ Motor = Motor, ext_2_12(p2; p1, p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Horizon & Line = Float, reg_2_12(p2; p1, p2) -> (p1, _) => p1[0];
+  Horizon & Branch = Float, reg_2_1(p2; 0) -> p1 => p1[0];
+  Horizon & Direction = Plane, reg_2_3(p2; 0) -> (p0, _) => Plane(p0);
+  Horizon & Dual as Motor = Horizon, reg_2_12(p2; p1, p2) -> (_, p2) => Horizon{p2};
+  Horizon & Rotor = Float, reg_2_1(p2; 0) -> p1 => p1[0];
+  Horizon & Motor = Motor, reg_2_12(p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Horizon | Dual as Motor = Horizon, dot_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon | Rotor = Horizon, dot_2_1(p2; 0) -> p2 => Horizon{p2};
+  Horizon | Translator as Motor = Horizon, dot_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon | Motor = Horizon, dot_2_12(p2; p1, p2) -> p2 => Horizon{p2};
+  Horizon / Plane = Flector;
+  Horizon / Line = Motor;
+  Horizon / Branch = Motor;
+  Horizon / Point = Direction;
+  Horizon / Dual = Horizon;
+  Horizon / Rotor = Motor;
+  Horizon / Translator = Horizon;
+  Horizon / Motor = Motor;
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::*;
+  use super::*;
+  use super::super::*;
 
   #[test]
   fn meet_horizon_plane() {
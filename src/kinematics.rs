@@ -0,0 +1,322 @@
+use super::{Motor, Rotor, Line, Horizon, Point, EulerFrame, EulerOrder, Transform, maths::*};
+
+/// The motion a joint performs about its axis.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum JointKind {
+  /// Rotation about the axis by an angle, see `Motor::from_screw_axis`.
+  Revolute,
+  /// Translation along the direction of the axis by a distance, see `Horizon::exp`.
+  Prismatic,
+}
+
+/// Bounds on the motion of a joint.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Limit {
+  /// Bounds of the joint parameter, an angle or a distance.
+  Range(Float, Float),
+  /// Bounds of the angles `Rotor::to_euler` gives in `order` and `frame` for
+  /// the rotation of the joint. The clamped rotation is projected back onto
+  /// the joint axis. Prismatic joints ignore this limit.
+  Euler{order:EulerOrder, frame:EulerFrame, min:[Float;3], max:[Float;3]},
+}
+
+/// A joint of a kinematic chain, the axis is given in the base frame with all
+/// joints of the chain at zero.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Joint { axis:Line, kind:JointKind, limit:Option<Limit> }
+
+impl Joint {
+  pub fn revolute(axis:Line)->Joint { Joint{axis: axis.normalized(), kind: JointKind::Revolute, limit: None} }
+  pub fn prismatic(axis:Line)->Joint { Joint{axis: axis.normalized(), kind: JointKind::Prismatic, limit: None} }
+  /// Limit the motion of this joint, bounds given in the wrong order are swapped.
+  pub fn with_limit(self, limit:Limit)->Joint {
+    let limit = match limit {
+      Limit::Range(a, b) => Limit::Range(a.min(b), a.max(b)),
+      Limit::Euler{order, frame, min, max} =>
+        Limit::Euler{order, frame, min: [0, 1, 2].map(|i| min[i].min(max[i])), max: [0, 1, 2].map(|i| min[i].max(max[i]))},
+    };
+    Joint{limit: Some(limit), ..self}
+  }
+
+  pub fn axis(&self)->Line { self.axis }
+  pub fn kind(&self)->JointKind { self.kind }
+  pub fn limit(&self)->Option<Limit> { self.limit }
+
+  /// The motion of this joint for parameter `q`.
+  pub fn motor(&self, q:Float)->Motor {
+    match self.kind {
+      JointKind::Revolute => Motor::from_screw_axis(q, 0.0, self.axis),
+      JointKind::Prismatic => Horizon{p2: self.axis.p1 * Lanes::splat(-0.5 * q)}.exp().into(),
+    }
+  }
+
+  /// Clamp parameter `q` to the limit of this joint.
+  pub fn clamp(&self, q:Float)->Float {
+    match (self.limit, self.kind) {
+      (Some(Limit::Range(min, max)), _) => q.max(min).min(max),
+      (Some(Limit::Euler{order, frame, min, max}), JointKind::Revolute) => {
+        // A rotor turns the other way around its axis than a screw motion, its
+        // reverse turns the same way as the joint parameter.
+        let angles = Rotor(self.motor(q).p1).reverse().to_euler(order, frame).angles;
+        let r = Rotor::from_euler(order, frame, [0, 1, 2].map(|i| angles[i].max(min[i]).min(max[i])));
+        let s = dot3(direction(&self.axis), [r.e23(), r.e31(), r.e12()]);
+        2.0 * s.atan2(r.scalar())
+      }
+      _ => q,
+    }
+  }
+}
+
+/// A serial chain of joints ending in an end effector.
+///
+/// ```
+/// use g3::{Chain, Joint, line, point};
+/// let shoulder = Joint::revolute(line(0.0, 0.0, 0.0, 0.0, 0.0, 1.0));
+/// let elbow = Joint::revolute(point(1.0, 0.0, 0.0) & point(1.0, 0.0, 1.0));
+/// let arm = Chain::new(vec![shoulder, elbow], point(2.0, 0.0, 0.0));
+/// let mut q = [0.0, 0.0];
+/// arm.dls(&mut q, point(1.0, 1.0, 0.0), 0.1, 50);
+/// ```
+#[derive(Debug,Clone,PartialEq)]
+pub struct Chain { joints:Vec<Joint>, end:Point }
+
+impl Chain {
+  /// `end` is the end effector with all joints at zero.
+  pub fn new(joints:Vec<Joint>, end:Point)->Chain { Chain{joints, end: end.normalized()} }
+  pub fn joints(&self)->&[Joint] { &self.joints }
+  pub fn len(&self)->usize { self.joints.len() }
+  pub fn is_empty(&self)->bool { self.joints.is_empty() }
+
+  /// Forward kinematics, the composition of the joint motors for parameters `q`.
+  ///
+  /// # Panics
+  ///
+  /// This function will panic if `q` does not have one parameter per joint.
+  pub fn forward(&self, q:&[Float])->Motor { self.frames(q).pop().unwrap_or(Motor::one()) }
+
+  /// The composition of the first `i + 1` joint motors for every joint `i`.
+  ///
+  /// # Panics
+  ///
+  /// This function will panic if `q` does not have one parameter per joint.
+  pub fn frames(&self, q:&[Float])->Vec<Motor> {
+    assert_eq!(q.len(), self.joints.len(), "one parameter per joint");
+    let mut m = Motor::one();
+    self.joints.iter().zip(q).map(|(j, q)| { m *= j.motor(*q); m }).collect()
+  }
+
+  /// Position of the end effector for parameters `q`.
+  ///
+  /// # Panics
+  ///
+  /// This function will panic if `q` does not have one parameter per joint.
+  pub fn end_point(&self, q:&[Float])->Point { self.forward(q).apply(self.end).normalized() }
+
+  // Current axes of the joints and the end effector.
+  fn pose(&self, q:&[Float])->(Vec<Line>, [Float;3]) {
+    let mut m = Motor::one();
+    let axes = self.joints.iter().zip(q).map(|(j, q)| { let l = m.apply(j.axis); m *= j.motor(*q); l }).collect();
+    (axes, xyz(&m.apply(self.end)))
+  }
+
+  /// Cyclic coordinate descent towards `target`, every joint in turn from the
+  /// end effector to the base moves the end effector as close to the target
+  /// as it can. Returns the remaining distance.
+  ///
+  /// # Panics
+  ///
+  /// This function will panic if `q` does not have one parameter per joint.
+  pub fn ccd(&self, q:&mut [Float], target:Point, iterations:usize)->Float {
+    let t = xyz(&target.normalized());
+    for _ in 0..iterations {
+      if distance(self.end_point(q), t) < tolerance() { break }
+      for i in (0..self.joints.len()).rev() {
+        let (axes, e) = self.pose(q);
+        q[i] = self.joints[i].clamp(q[i] + self.joints[i].step(&axes[i], e, t));
+      }
+    }
+    distance(self.end_point(q), t)
+  }
+
+  /// Forward and backward reaching towards `target`. The joints are placed as
+  /// points on their axes, the reached positions are then fitted to the
+  /// joint parameters from the base outwards. The length of a link changes
+  /// with a prismatic joint, these move the end effector towards the target
+  /// as in `ccd` instead. Returns the remaining distance.
+  ///
+  /// # Panics
+  ///
+  /// This function will panic if `q` does not have one parameter per joint.
+  pub fn fabrik(&self, q:&mut [Float], target:Point, iterations:usize)->Float {
+    let t = xyz(&target.normalized());
+    let n = self.joints.len();
+    for _ in 0..iterations {
+      if n == 0 || distance(self.end_point(q), t) < tolerance() { break }
+      let (axes, e) = self.pose(q);
+      let mut p:Vec<[Float;3]> = axes.iter().map(center).chain([e]).collect();
+      let lengths:Vec<Float> = p.windows(2).map(|w| norm3(sub3(w[1], w[0]))).collect();
+      let base = p[0];
+      p[n] = t;
+      for i in (0..n).rev() { p[i] = reach(p[i+1], p[i], lengths[i]) }
+      p[0] = base;
+      for i in 0..n { p[i+1] = reach(p[i], p[i+1], lengths[i]) }
+      for i in 0..n {
+        let (axes, e) = self.pose(q);
+        let (from, to) = match self.joints[i].kind {
+          JointKind::Revolute if i + 1 < n => (center(&axes[i+1]), p[i+1]),
+          JointKind::Revolute => (e, p[n]),
+          JointKind::Prismatic => (e, t),
+        };
+        q[i] = self.joints[i].clamp(q[i] + self.joints[i].step(&axes[i], from, to));
+      }
+    }
+    distance(self.end_point(q), t)
+  }
+
+  /// Damped least squares towards `target`. The columns of the Jacobian are
+  /// the velocities the current joint lines give the end effector, `damping`
+  /// trades convergence for stability near singularities. Returns the
+  /// remaining distance.
+  ///
+  /// # Panics
+  ///
+  /// This function will panic if `q` does not have one parameter per joint.
+  pub fn dls(&self, q:&mut [Float], target:Point, damping:Float, iterations:usize)->Float {
+    let t = xyz(&target.normalized());
+    for _ in 0..iterations {
+      let (axes, e) = self.pose(q);
+      let err = sub3(t, e);
+      if norm3(err) < tolerance() { break }
+      let jacobian:Vec<[Float;3]> = self.joints.iter().zip(&axes).map(|(j, l)| j.velocity(l, e)).collect();
+      // J Jᵀ + λ²I
+      let mut a = [[0.0;3];3];
+      for (r, row) in a.iter_mut().enumerate() {
+        for (c, x) in row.iter_mut().enumerate() {
+          *x = jacobian.iter().map(|v| v[r] * v[c]).sum::<Float>() + if r == c { damping * damping } else { 0.0 };
+        }
+      }
+      let Some(y) = solve3(a, err) else { break };
+      for (i, v) in jacobian.iter().enumerate() { q[i] = self.joints[i].clamp(q[i] + dot3(*v, y)) }
+    }
+    distance(self.end_point(q), t)
+  }
+}
+
+impl Joint {
+  // Velocity of point `x` for a unit change of the parameter about the current axis `l`.
+  fn velocity(&self, l:&Line, x:[Float;3])->[Float;3] {
+    match self.kind {
+      JointKind::Revolute => add3(cross3(direction(l), x), [l.e01(), l.e02(), l.e03()]),
+      JointKind::Prismatic => direction(l),
+    }
+  }
+
+  // Change of the parameter that moves `e` closest to `t` about the current axis `l`.
+  fn step(&self, l:&Line, e:[Float;3], t:[Float;3])->Float {
+    let u = direction(l);
+    match self.kind {
+      JointKind::Revolute => {
+        let c = center(l);
+        let (e, t) = (reject(sub3(e, c), u), reject(sub3(t, c), u));
+        if norm3(e) < tolerance() || norm3(t) < tolerance() { return 0.0 }
+        dot3(u, cross3(e, t)).atan2(dot3(e, t))
+      }
+      JointKind::Prismatic => dot3(u, sub3(t, e)),
+    }
+  }
+}
+
+fn distance(p:Point, t:[Float;3])->Float { norm3(sub3(xyz(&p), t)) }
+// Component of `a` perpendicular to unit vector `u`.
+fn reject(a:[Float;3], u:[Float;3])->[Float;3] { sub3(a, scale3(u, dot3(a, u))) }
+// The point at distance `length` from `from` in the direction of `to`.
+fn reach(from:[Float;3], to:[Float;3], length:Float)->[Float;3] {
+  let d = sub3(to, from);
+  let n = norm3(d);
+  if n < tolerance() { to } else { add3(from, scale3(d, length / n)) }
+}
+
+// Solve the symmetric 3×3 system `a x = b` with Cramer's rule.
+fn solve3(a:[[Float;3];3], b:[Float;3])->Option<[Float;3]> {
+  let det = dot3(a[0], cross3(a[1], a[2]));
+  if det.abs() < Float::EPSILON { return None }
+  let (c0, c1, c2) = (cross3(a[1], a[2]), cross3(a[2], a[0]), cross3(a[0], a[1]));
+  Some(scale3([dot3(c0, b), dot3(c1, b), dot3(c2, b)], 1.0 / det))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  // A planar arm with two links of length one about the z-axis.
+  fn arm()->Chain {
+    let z = line(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+    Chain::new(vec![Joint::revolute(z), Joint::revolute(point(1.0, 0.0, 0.0) & point(1.0, 0.0, 1.0))], point(2.0, 0.0, 0.0))
+  }
+
+  #[test] fn forward_kinematics() {
+    let arm = arm();
+    approx_eq3(xyz(&arm.end_point(&[0.0, 0.0])), [2.0, 0.0, 0.0], 0.001);
+    approx_eq3(xyz(&arm.end_point(&[pi/2.0, 0.0])), [0.0, 2.0, 0.0], 0.001);
+    approx_eq3(xyz(&arm.end_point(&[pi/2.0, -pi/2.0])), [1.0, 1.0, 0.0], 0.001);
+    assert_eq!(arm.frames(&[0.1, 0.2]).len(), 2);
+    let slide = Chain::new(vec![Joint::prismatic(line(0.0, 0.0, 0.0, 0.0, 1.0, 0.0)), arm.joints()[0]], point(1.0, 0.0, 0.0));
+    approx_eq3(xyz(&slide.end_point(&[3.0, pi])), [-1.0, 3.0, 0.0], 0.001);
+  }
+
+  #[test] fn inverse_kinematics() {
+    let arm = arm();
+    let target = point(0.5, 1.2, 0.0);
+    let solvers:[fn(&Chain, &mut [Float])->Float; 3] = [
+      |c, q| c.ccd(q, point(0.5, 1.2, 0.0), 100),
+      |c, q| c.fabrik(q, point(0.5, 1.2, 0.0), 100),
+      |c, q| c.dls(q, point(0.5, 1.2, 0.0), 0.05, 100)];
+    for solve in solvers {
+      let mut q = [0.3, 0.3];
+      assert!(solve(&arm, &mut q) < 0.001);
+      approx_eq3(xyz(&arm.end_point(&q)), xyz(&target), 0.001);
+    }
+    // Out of reach, the arm stretches towards the target.
+    let mut q = [0.0, 0.0];
+    arm.ccd(&mut q, point(0.0, 3.0, 0.0), 100);
+    approx_eq3(xyz(&arm.end_point(&q)), [0.0, 2.0, 0.0], 0.001);
+  }
+
+  #[test] fn prismatic_ik() {
+    let chain = Chain::new(vec![Joint::prismatic(line(0.0, 0.0, 0.0, 1.0, 0.0, 0.0)), Joint::prismatic(line(0.0, 0.0, 0.0, 0.0, 1.0, 0.0))], point(0.0, 0.0, 0.0));
+    for solve in [Chain::ccd, Chain::fabrik] {
+      let mut q = [0.0, 0.0];
+      assert!(solve(&chain, &mut q, point(2.0, -1.0, 0.0), 10) < 0.001);
+    }
+  }
+
+  #[test] fn joint_limits() {
+    let mut arm = arm();
+    arm.joints[1] = arm.joints[1].with_limit(Limit::Range(-0.5, 0.5));
+    let mut q = [0.0, 0.0];
+    arm.ccd(&mut q, point(0.5, 1.2, 0.0), 100);
+    assert!(q[1] >= -0.5 && q[1] <= 0.5);
+    let yaw = |min, max| Limit::Euler{order: EulerOrder::XYZ, frame: EulerFrame::Intrinsic, min: [0.0, 0.0, min], max: [0.0, 0.0, max]};
+    let j = arm.joints[0].with_limit(yaw(-0.2, 0.3));
+    assert!((j.clamp(0.1) - 0.1).abs() < 0.001);
+    assert!((j.clamp(1.0) - 0.3).abs() < 0.001);
+    assert!((j.clamp(-1.0) + 0.2).abs() < 0.001);
+    // The same limit in the convention that turns about z first
+    let j = arm.joints[0].with_limit(Limit::Euler{order: EulerOrder::ZYX, frame: EulerFrame::Intrinsic, min: [-0.2, 0.0, 0.0], max: [0.3, 0.0, 0.0]});
+    assert!((j.clamp(1.0) - 0.3).abs() < 0.001);
+    assert!((j.clamp(-1.0) + 0.2).abs() < 0.001);
+  }
+
+  #[test] #[should_panic(expected = "one parameter per joint")] fn parameters_per_joint() {
+    arm().end_point(&[0.0]);
+  }
+
+  #[test] fn joint_limits_out_of_order() {
+    let j = arm().joints[1].with_limit(Limit::Range(1.0, -1.0));
+    assert_eq!(j.limit(), Some(Limit::Range(-1.0, 1.0)));
+    assert_eq!([j.clamp(2.0), j.clamp(0.5), j.clamp(-2.0)], [1.0, 0.5, -1.0]);
+    let j = arm().joints[0].with_limit(Limit::Euler{order: EulerOrder::XYZ, frame: EulerFrame::Intrinsic, min: [0.0, 0.0, 0.3], max: [0.0, 0.0, -0.2]});
+    assert!((j.clamp(1.0) - 0.3).abs() < 0.001);
+  }
+}
@@ -1,10 +1,107 @@
-#![feature(unboxed_closures)]
-#![feature(fn_traits)]
-#![feature(adt_const_params)]
-#![feature(portable_simd)]
-#![feature(associated_type_defaults)]
-#![allow(incomplete_features)]
+#![cfg_attr(feature = "nightly", feature(unboxed_closures, fn_traits, associated_type_defaults))]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![allow(non_upper_case_globals)]
+// Products in geometric algebra are written with `*`, `^`, `&` and `|` on top of
+// other arithmetic, and the kernels take their operands by reference.
+#![allow(clippy::suspicious_arithmetic_impl, clippy::suspicious_op_assign_impl, clippy::op_ref, clippy::too_many_arguments)]
+
+// Call syntax `a(b)` for every `Transform` of a versor, this needs the unstable `Fn` traits.
+macro_rules! call_transform {
+  ($versor:ty: $($t:ty),*) => {$(
+    #[cfg(feature = "nightly")]
+    impl FnOnce<($t,)> for $versor {
+      type Output = <$versor as $crate::Transform<$t>>::Output;
+      extern "rust-call" fn call_once(self, args:($t,))->Self::Output { $crate::Transform::apply(&self, args.0) }
+    }
+    #[cfg(feature = "nightly")]
+    impl FnMut<($t,)> for $versor {
+      extern "rust-call" fn call_mut(&mut self, args:($t,))->Self::Output { $crate::Transform::apply(self, args.0) }
+    }
+    #[cfg(feature = "nightly")]
+    impl Fn<($t,)> for $versor {
+      extern "rust-call" fn call(&self, args:($t,))->Self::Output { $crate::Transform::apply(self, args.0) }
+    }
+  )*}
+}
+
+// Serde for an element by the names of its component accessors and a
+// constructor from an array of those components, see `compact`.
+macro_rules! serde_blades {
+  ($t:ident { $($f:ident),* } => |$a:pat_param| $new:expr) => {
+    #[cfg(feature = "serde")]
+    impl $crate::compact::Blades for $t {
+      type Scalar = Float;
+      type Array = [Float; [$(stringify!($f)),*].len()];
+      const NAME:&'static str = stringify!($t);
+      const FIELDS:&'static [&'static str] = &[$(stringify!($f)),*];
+      fn to_blades(&self)->Self::Array { [$(self.$f()),*] }
+      fn from_blades($a:Self::Array)->Self { $new }
+    }
+    #[cfg(feature = "serde")]
+    impl ::serde::Serialize for $t {
+      fn serialize<S: ::serde::Serializer>(&self, s:S)->::std::result::Result<S::Ok, S::Error> { $crate::compact::serialize_named(self, s) }
+    }
+    #[cfg(feature = "serde")]
+    impl<'de> ::serde::Deserialize<'de> for $t {
+      fn deserialize<D: ::serde::Deserializer<'de>>(d:D)->::std::result::Result<Self, D::Error> { $crate::compact::deserialize_named(d) }
+    }
+  }
+}
+
+// `Pod` is not derived for structs with generic lanes, with the lanes of the
+// backend the elements are packed floats without padding, see `layout_test`.
+macro_rules! pod {
+  ($t:ident) => {
+    #[cfg(feature = "bytemuck")] unsafe impl bytemuck::Pod for $t {}
+  }
+}
+
+// Operators between elements on top of the kernels generated by `build.rs`, one
+// per line as `a op b = Output, kernel(fields of a; fields of b) -> partitions => result`.
+// Translators with their implicit scalar and dual numbers take part as a motor,
+// `a / b = Output` multiplies by the inverse of `b`. Products that vanish for
+// every input, like `Point ^ Line`, and divisions by horizons and directions,
+// which have no inverse, are left out. A leading `<S:Scalar>` makes the
+// operators generic over the components, for the elements of `diff`.
+macro_rules! products {
+  () => {};
+  (<$s:ident:$bound:ident>) => {};
+  (<$s:ident:$bound:ident> $a:ident * $($t:tt)*) => { products!(@generic <$s:$bound> Mul mul $a $($t)*); };
+  (<$s:ident:$bound:ident> $a:ident ^ $($t:tt)*) => { products!(@generic <$s:$bound> BitXor bitxor $a $($t)*); };
+  (<$s:ident:$bound:ident> $a:ident & $($t:tt)*) => { products!(@generic <$s:$bound> BitAnd bitand $a $($t)*); };
+  (<$s:ident:$bound:ident> $a:ident | $($t:tt)*) => { products!(@generic <$s:$bound> BitOr bitor $a $($t)*); };
+  (@generic <$s:ident:$bound:ident> $op:ident $f:ident $a:ident $b:ident = $out:ty,
+   $kernel:ident($($p:tt),*; $($q:tt),*) -> $parts:pat => $e:expr; $($rest:tt)*) => {
+    impl<$s:$bound> std::ops::$op<$b<$s>> for $a<$s> {
+      type Output = $out;
+      fn $f(self, b:$b<$s>)->$out {
+        let $parts = kernels::$kernel($(&self.$p),*, $(&b.$q),*);
+        $e
+      }
+    }
+    products!(<$s:$bound> $($rest)*);
+  };
+  ($a:ident / $b:ident = $out:ty; $($rest:tt)*) => {
+    impl std::ops::Div<$b> for $a { type Output = $out; fn div(self, b:$b)->$out { self * b.inverse() } }
+    products!($($rest)*);
+  };
+  ($a:ident $(as $ca:ident)? * $($t:tt)*) => { products!(@rhs Mul mul [$a $($ca)?] $($t)*); };
+  ($a:ident $(as $ca:ident)? ^ $($t:tt)*) => { products!(@rhs BitXor bitxor [$a $($ca)?] $($t)*); };
+  ($a:ident $(as $ca:ident)? & $($t:tt)*) => { products!(@rhs BitAnd bitand [$a $($ca)?] $($t)*); };
+  ($a:ident $(as $ca:ident)? | $($t:tt)*) => { products!(@rhs BitOr bitor [$a $($ca)?] $($t)*); };
+  (@rhs $op:ident $f:ident [$a:ident $($ca:ident)?] $b:ident $(as $cb:ident)? = $out:ty,
+   $kernel:ident($($p:tt),*; $($q:tt),*) -> $parts:pat => $e:expr; $($rest:tt)*) => {
+    impl std::ops::$op<$b> for $a {
+      type Output = $out;
+      fn $f(self, b:$b)->$out {
+        let (a, b) = ($($ca::from)?(self), $($cb::from)?(b));
+        let $parts = kernels::$kernel($(&a.$p),*, $(&b.$q),*);
+        $e
+      }
+    }
+    products!($($rest)*);
+  };
+}
 
 mod dual;
 mod point;
@@ -16,24 +113,45 @@ mod motor;
 mod rotor;
 mod direction;
 mod translator;
+mod flector;
+mod animation;
+mod kinematics;
+mod dynamics;
+mod metric;
+mod multivector;
+mod notation;
+mod formula;
 
 /// π
-pub const pi:f32 =  std::f32::consts::PI;
+pub const pi:Float = std::f32::consts::PI;
 /// τ = 2π
-pub const tau:f32 = pi*2.0;
+pub const tau:Float = pi*2.0;
 
 use std::ops::{Add, Div, Mul, Sub};
-pub use dual::{Dual, dual};
+pub use dual::{Dual, dual, e0123};
 pub use point::*;
 pub use line::{Line,line};
 pub use branch::{Branch,branch};
 pub use horizon::{Horizon,horizon};
 pub use plane::*;
-pub use motor::{Motor,motor};
-pub use rotor::{EulerAngles,Rotor,rotor};
+pub use motor::{Motor,Screw,motor};
+pub use rotor::{Euler,EulerAngles,EulerFrame,EulerOrder,Rotor,rotor};
 pub use direction::{Direction};
 pub use translator::{Translator,translator};
+pub use flector::Flector;
+pub use animation::{Track,Interpolation};
+pub use kinematics::{Chain,Joint,JointKind,Limit};
+pub use dynamics::{RigidBody,Inertia,Integrator};
+pub use metric::{Distance,Angle,distance,angle};
+pub use multivector::Multivector;
+pub use notation::ParseError;
+pub use backend::Float;
+pub(crate) mod backend;
 pub(crate) mod maths;
+pub mod double;
+pub mod diff;
+pub mod query;
+#[cfg(feature = "serde")] pub mod compact;
 
 #[cfg(feature = "renderer")] mod render;
 #[cfg(feature = "renderer")] pub use render::{Renderer,Color};
@@ -48,19 +166,29 @@ pub trait JoinProduct {}
 pub trait MeetProduct {}
 /// a(b) = -aba⁻¹
 pub trait SandwichProduct {}
+
+/// Apply a versor to an element with the sandwich product, `a.apply(b)` is `aba⁻¹`.
+///
+/// With the `nightly` feature the same can be written as a call, `a(b)`.
+pub trait Transform<T> {
+  type Output;
+  fn apply(&self, x:T)->Self::Output;
+}
 /// a|b
 pub trait InnerProduct {}
 /// k*a
 pub trait ScalarProduct: Mul + Div + Sized {
   type Output;
-  fn mul(self,k:f32)->Self;
-  fn div(self,k:f32)->Self;
-  // fn mul_assign(&mut self, s:f32);
-  // fn div_assign(&mut self, s:f32);
+  fn mul(self,k:Float)->Self;
+  fn div(self,k:Float)->Self;
+  // fn mul_assign(&mut self, s:Float);
+  // fn div_assign(&mut self, s:Float);
 }
 
 pub trait SumProduct: Add + Sub + Sized {
-  type Output=Self;
+  // Defaults of associated types are unstable, they need the `nightly` feature.
+  #[cfg(feature = "nightly")] type Output=Self;
+  #[cfg(not(feature = "nightly"))] type Output;
   fn add(self,k:Self)->Self;
   // fn sub(self,k:Self)->Self;
 }
@@ -1,26 +1,34 @@
-use std::{simd::{f32x4,mask32x4,SimdFloat},ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, BitXor, BitAnd, BitOr}};
-use crate::{Dual, Plane, Point, Motor, Branch, Horizon,maths::*};
+use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, BitXor, BitAnd, BitOr}};
+use super::{Dual, Plane, Point, Motor, Branch, Horizon, Direction, Rotor, Translator, Flector, maths::*};
 
 /// ae₂₃ + be₃₁ + ce₁₂ + de₀₁ + ee₀₂ + fe₀₃
-pub const fn line(a:f32,b:f32,c:f32,d:f32,e:f32,f:f32)->Line { Line::new(a,b,c,d,e,f) }
+pub const fn line(a:Float,b:Float,c:Float,d:Float,e:Float,f:Float)->Line { Line::new(a,b,c,d,e,f) }
 
 /// A Line
+///
+/// Laid out as eight floats `[0, e23, e31, e12, 0, e01, e02, e03]`, the
+/// zeros keep both halves aligned like a motor.
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable))]
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Line {pub(crate) p1:f32x4, pub(crate) p2:f32x4}
+pub struct Line<L = Lanes> {pub(crate) p1:L, pub(crate) p2:L}
+pod!(Line);
+
+serde_blades!(Line { e01, e02, e03, e23, e31, e12 } => |[a, b, c, d, e, f]| Line::new(a, b, c, d, e, f));
 
 impl Line {
-  pub const fn new(a:f32,b:f32,c:f32,d:f32,e:f32,f:f32)->Line {
-    Line{p1:f32x4::from_array([0.0,d,e,f]), p2:f32x4::from_array([0.0,a,b,c])}
+  pub const fn new(a:Float,b:Float,c:Float,d:Float,e:Float,f:Float)->Line {
+    Line{p1:Lanes::from_array([0.0,d,e,f]), p2:Lanes::from_array([0.0,a,b,c])}
   }
 
   // Returns the square root of the quantity produced by `squared_norm`.
-  pub fn norm(&self)->f32 { self.squared_norm().sqrt() }
+  pub fn norm(&self)->Float { self.squared_norm().sqrt() }
 
   // If a line is constructed as the regressive product (join) of
   // two points, the squared norm provided here is the squared
   // distance between the two points (provided the points are
   // normalized). Returns $d^2 + e^2 + f^2$.
-  pub fn squared_norm(&self)->f32 { hi_dp(&self.p1, &self.p1)[0] }
+  pub fn squared_norm(&self)->Float { hi_dp(&self.p1, &self.p1)[0] }
 
   /// Normalize a line such that $\ell^2 = -1$.
   pub fn normalized(&self)->Line {
@@ -46,9 +54,9 @@ impl Line {
     let b2 = &hi_dp_bc(&self.p1, &self.p1);
     let s = &rsqrt_nr1(b2);
     let bc = hi_dp_bc(&self.p1, &self.p2);
-    let b2_inv = &rcp_nr1(&b2);
+    let b2_inv = &rcp_nr1(b2);
     let t = bc * b2_inv * s;
-    let neg  = mask32x4::from_array([false, true, true, true]);
+    let neg  = Mask::from_array([false, true, true, true]);
 
     // p1 * (s + t e0123)^2 = (s * p1 - t p1_perp) * (s + t e0123)
     // = s^2 p1 - s t p1_perp - s t p1_perp
@@ -61,9 +69,8 @@ impl Line {
     Line{p1,p2}
   }
 
-  pub fn approx_eq(&self, other: Line, epsilon: f32) -> bool {
-    let eps = f32x4::splat(epsilon);
-    (&self.p1 - other.p1).abs() < eps && (&self.p2 - other.p2).abs() < eps
+  pub fn approx_eq(&self, other: Line, epsilon: Float) -> bool {
+    lanes_close(&self.p1, &other.p1, epsilon) && lanes_close(&self.p2, &other.p2, epsilon)
   }
 
   /// Exponentiate a line to produce a motor that has this line
@@ -72,14 +79,45 @@ impl Line {
   /// or accelerate the motor's action. The line need not be a _simple bivector_
   /// for the operation to be well-defined.
   pub fn exp(&self)->Motor {
-    let (p1,p2) = exp(&self.p1, &self.p2);
-    Motor{p1,p2}
+    let (p1,p2) = exp(self.p1.as_array(), self.p2.as_array());
+    Motor{p1: p1.into(), p2: p2.into()}
+  }
+
+  /// A line is _simple_, the join of two points or the meet of two planes,
+  /// when $\ell \wedge \ell = 0$. The sum of two lines mostly is not, it is
+  /// a screw. Checked up to $\sqrt\epsilon$ relative to the norms.
+  pub fn is_simple(&self)->bool {
+    let ab = hi_dp(&self.p1, &self.p2)[0];
+    ab.abs() <= Float::EPSILON.sqrt() * (self.squared_norm() * hi_dp(&self.p2, &self.p2)[0]).sqrt()
+  }
+
+  /// Split a general bivector into two simple lines that commute, its
+  /// Euclidean axis and an ideal line perpendicular to that axis, by the
+  /// invariant decomposition. Their sum is this line, and `exp` of this line
+  /// is the rotation about the axis times the translation along it,
+  /// `axis.exp() * ideal.exp()`. An ideal line has no axis, it comes back as
+  /// the ideal part with a zero axis.
+  pub fn decompose(&self)->(Line, Line) {
+    // With a the Euclidean and b the ideal part, the part of b along a
+    // carries all of a·b, what is left of b is perpendicular to a:
+    //
+    // ℓ = (a + b - (a·b)/(a·a) a) + (a·b)/(a·a) a
+    let a2 = self.squared_norm();
+    if a2 == 0.0 { return (Line::default(), *self) }
+    let ideal = &self.p1 * Lanes::splat(hi_dp(&self.p1, &self.p2)[0] / a2);
+    (Line{p1: self.p1, p2: &self.p2 - ideal}, Line{p1: Lanes::splat(0.0), p2: ideal})
+  }
+
+  /// The simple line closest to this one, its Euclidean axis without the
+  /// ideal part along it. Ideal lines are already simple.
+  pub fn simplify(&self)->Line {
+    match self.decompose() { (axis, ideal) if axis == Line::default() => ideal, (axis, _) => axis }
   }
 
   pub fn reverse(self)->Line {
     Line {
-      p1: flip_signs(&self.p1, mask32x4::from_array([false,true,true,true])),
-      p2: flip_signs(&self.p2, mask32x4::from_array([false,true,true,true]))
+      p1: flip_signs(&self.p1, Mask::from_array([false,true,true,true])),
+      p2: flip_signs(&self.p2, Mask::from_array([false,true,true,true]))
     }
   }
 
@@ -90,29 +128,29 @@ impl Line {
   // Project a line onto a plane
   pub fn project_plane(self, p:Plane)->Line { (self | p) ^ p }
 
-  #[inline] pub fn e12(&self)->f32 { self.p1[3] }
-  #[inline] pub fn e21(&self)->f32 { -self.e12() }
-  #[inline] pub fn e31(&self)->f32 { self.p1[2] }
-  #[inline] pub fn e13(&self)->f32 { -self.e31() }
-  #[inline] pub fn e23(&self)->f32 { self.p1[1] }
-  #[inline] pub fn e32(&self)->f32 { -self.e23() }
-  #[inline] pub fn e01(&self)->f32 { self.p2[1] }
-  #[inline] pub fn e10(&self)->f32 { -self.e01() }
-  #[inline] pub fn e02(&self)->f32 { self.p2[2] }
-  #[inline] pub fn e20(&self)->f32 { -self.e02() }
-  #[inline] pub fn e03(&self)->f32 { self.p2[3] }
-  #[inline] pub fn e30(&self)->f32 { -self.e03() }
+  #[inline] pub fn e12(&self)->Float { self.p1[3] }
+  #[inline] pub fn e21(&self)->Float { -self.e12() }
+  #[inline] pub fn e31(&self)->Float { self.p1[2] }
+  #[inline] pub fn e13(&self)->Float { -self.e31() }
+  #[inline] pub fn e23(&self)->Float { self.p1[1] }
+  #[inline] pub fn e32(&self)->Float { -self.e23() }
+  #[inline] pub fn e01(&self)->Float { self.p2[1] }
+  #[inline] pub fn e10(&self)->Float { -self.e01() }
+  #[inline] pub fn e02(&self)->Float { self.p2[2] }
+  #[inline] pub fn e20(&self)->Float { -self.e02() }
+  #[inline] pub fn e03(&self)->Float { self.p2[3] }
+  #[inline] pub fn e30(&self)->Float { -self.e03() }
 }
 
 impl From<Branch> for Line {
   fn from(b: Branch) -> Self {
-    Line{p1: b.0, p2: f32x4::splat(0.0)}
+    Line{p1: b.0, p2: Lanes::splat(0.0)}
   }
 }
 
 impl From<Horizon> for Line {
   fn from(h: Horizon) -> Self {
-    Line{p1: f32x4::splat(0.0), p2: h.p2}
+    Line{p1: Lanes::splat(0.0), p2: h.p2}
   }
 }
 
@@ -144,31 +182,31 @@ impl SubAssign for Line {
   }
 }
 
-impl Mul<f32> for Line {
+impl Mul<Float> for Line {
   type Output = Line;
-  fn mul(self, s: f32) -> Line {
-    Line { p1:self.p1*f32x4::splat(s), p2:self.p2*f32x4::splat(s) }
+  fn mul(self, s: Float) -> Line {
+    Line { p1:self.p1*Lanes::splat(s), p2:self.p2*Lanes::splat(s) }
   }
 }
 
-impl MulAssign<f32> for Line {
-  fn mul_assign(&mut self, s: f32) {
-    self.p1 *= f32x4::splat(s);
-    self.p2 *= f32x4::splat(s);
+impl MulAssign<Float> for Line {
+  fn mul_assign(&mut self, s: Float) {
+    self.p1 *= Lanes::splat(s);
+    self.p2 *= Lanes::splat(s);
   }
 }
 
-impl Div<f32> for Line {
+impl Div<Float> for Line {
   type Output = Line;
-  fn div(self, s: f32) -> Line {
-    Line { p1:self.p1/f32x4::splat(s), p2:self.p2/f32x4::splat(s) }
+  fn div(self, s: Float) -> Line {
+    Line { p1:self.p1/Lanes::splat(s), p2:self.p2/Lanes::splat(s) }
   }
 }
 
-impl DivAssign<f32> for Line {
-  fn div_assign(&mut self, s: f32) {
-    self.p1 /= f32x4::splat(s);
-    self.p2 /= f32x4::splat(s);
+impl DivAssign<Float> for Line {
+  fn div_assign(&mut self, s: Float) {
+    self.p1 /= Lanes::splat(s);
+    self.p2 /= Lanes::splat(s);
   }
 }
 
@@ -218,22 +256,12 @@ impl BitOr<Point> for Line {
   fn bitor(self, a:Point)->Plane { a | self }
 }
 
-impl BitOr<Line> for Line {
-  type Output = f32;
-  fn bitor(self, l:Line)->f32 { dot11(&self.p1, &l.p1)[0] }
-}
-
-impl BitOr<Plane> for Line {
-  type Output = Plane;
-  fn bitor(self, p:Plane)->Plane { Plane(dotlp(&p.0, &self.p1, &self.p2))}
-}
-
 // Geometric Product
 
 impl Mul<Line> for Line {
   type Output = Motor;
   fn mul(self, l: Line) -> Motor {
-    let (p1,p2) = gpll(&self.p1, &self.p2, &l.p1, &l.p2);
+    let (p1,p2) = kernels::gp_12_12(&self.p1, &self.p2, &l.p1, &l.p2);
     Motor{ p1, p2 }
   }
 }
@@ -246,25 +274,63 @@ impl Div<Line> for Line {
   }
 }
 
-fn dot11(a:&f32x4, b:&f32x4)->f32x4 {
-  f32x4_xor(&[-0.0, 0.0, 0.0, 0.0].into(), &hi_dp_ss(a, b))
-}
-
-
-fn dotlp(a:&f32x4, b:&f32x4, c:&f32x4)->f32x4 {
-  let mut p0 = a * b.xzwy();
-  p0 -= a.xzwy() * b;
-  add_ss(&(p0.xzwy()), &hi_dp_ss(a, c))
+products! {
+  Line * Plane = Flector, gp_12_0(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Line * Branch = Motor, gp_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Line * Horizon = Motor, gp_12_2(p1, p2; p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Line * Point = Flector, gp_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Line * Direction = Flector, gp_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Line * Dual as Motor = Line, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Line * Rotor = Motor, gp_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Line * Translator as Motor = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Line * Motor = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Line ^ Dual as Motor = Line, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Line ^ Rotor = Motor, ext_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Line ^ Translator as Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Line ^ Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Line & Line = Float, reg_12_12(p1, p2; p1, p2) -> (p1, _) => p1[0];
+  Line & Branch = Float, reg_12_1(p1, p2; 0) -> p1 => p1[0];
+  Line & Horizon = Float, reg_12_2(p1, p2; p2) -> (p1, _) => p1[0];
+  Line & Direction = Plane, reg_12_3(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Line & Dual as Motor = Line, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Line & Rotor = Float, reg_12_1(p1, p2; 0) -> p1 => p1[0];
+  Line & Translator as Motor = Float, reg_12_12(p1, p2; p1, p2) -> (p1, _) => p1[0];
+  Line & Motor = Motor, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Line | Plane = Plane, dot_12_0(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Line | Line = Float, dot_12_12(p1, p2; p1, p2) -> (p1, _) => p1[0];
+  Line | Branch = Float, dot_12_1(p1, p2; 0) -> (p1, _) => p1[0];
+  Line | Direction = Plane, dot_12_3(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Line | Dual as Motor = Line, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Line | Rotor = Motor, dot_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Line | Translator as Motor = Line, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Line | Motor = Motor, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Line / Plane = Flector;
+  Line / Branch = Motor;
+  Line / Point = Flector;
+  Line / Dual = Line;
+  Line / Rotor = Motor;
+  Line / Translator = Motor;
+  Line / Motor = Motor;
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::*;
+  use super::*;
+  use super::super::*;
 
   #[test] fn line_constructor() {
     let l = line(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
     assert_eq!(l,line(1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
   }
+  #[test] fn line_approx_eq() {
+    let l = line(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+    assert!(l.approx_eq(l, 0.000_1));
+    for i in 1..4 {
+      let (mut a, mut b) = (l, l);
+      a.p1[i] += 1.0; b.p2[i] += 1.0;
+      assert!(!l.approx_eq(a, 0.000_1) && !l.approx_eq(b, 0.000_1), "lane {} is not compared", i);
+    }
+  }
   #[test] fn line_eq() {
     let l1 = line(1.,2.,3.,4.,5.,6.);
     let l2 = line(6.,5.,4.,3.,2.,1.);
@@ -309,16 +375,36 @@ mod tests {
     assert_eq!([a.e0123(), a.scalar()], [0.0, 0.0]);
   }
 
-  const EPSILON: f32 = 0.02;
+  const EPSILON: Float = 0.02;
 
-  fn approx_eq(a: f32, b: f32) {
+  fn approx_eq(a: Float, b: Float) {
     assert!((a - b).abs() < EPSILON, "{:?} ≉ {:?}", a, b);
   }
 
+  #[test] fn decompose_screw() {
+    let l = line(1.0, -2.0, 0.5, 0.0, 0.0, 1.0) + line(0.5, 0.0, 1.0, 1.0, 2.0, 0.0);
+    assert!(!l.is_simple());
+    let (axis, ideal) = l.decompose();
+    assert!(axis.is_simple() && ideal.is_simple());
+    close(axis + ideal, l);
+    close(axis * ideal, ideal * axis);
+    close(l.exp(), axis.exp() * ideal.exp());
+    assert_eq!(ideal.squared_norm(), 0.0);
+    assert_eq!(l.simplify(), axis);
+
+    let k = point(1.0, 2.0, 3.0) & point(-1.0, 0.0, 2.0);
+    assert!(k.is_simple());
+    close(k.simplify(), k);
+    assert!(!k.approx_eq(line(0.0, 0.0, 1.0, 0.0, 0.0, 0.0), 0.000_1));
+    let h = Line::from(horizon(1.0, 2.0, 3.0));
+    assert_eq!(h.decompose(), (Line::default(), h));
+    assert_eq!(h.simplify(), h);
+  }
+
   #[test] fn measure_point_to_line() {
     let l = line(0.0, 1.0, 0.0, 1.0, 0.0, 0.0);
     let a = point(0.0, 1.0, 2.0);
     let d = (l & a).norm();
-    approx_eq(d, 2f32.sqrt());
+    approx_eq(d, Float::sqrt(2.0));
   }
 }
@@ -0,0 +1,162 @@
+use super::{Plane, Point, Line, Direction, maths::*};
+
+/// Euclidean distance between two elements, `distance(a, b)` is `a.distance(b)`.
+///
+/// Distances are signed when the orientation of the elements gives a side: a
+/// point is positive on the side the normal of a plane points to, skew lines
+/// take the sign of their meet. Distances to ideal elements are infinite.
+pub trait Distance<T> { fn distance(&self, x:T)->Float; }
+
+/// Angle between two elements, `angle(a, b)` is `a.angle(b)`.
+///
+/// Ideal lines and the ideal plane have no direction, they are parallel to
+/// every other element.
+pub trait Angle<T> { fn angle(&self, x:T)->Float; }
+
+pub fn distance<A:Distance<B>, B>(a:A, b:B)->Float { a.distance(b) }
+pub fn angle<A:Angle<B>, B>(a:A, b:B)->Float { a.angle(b) }
+
+impl Distance<Point> for Point {
+  fn distance(&self, p:Point)->Float {
+    if ideal_point(self) || ideal_point(&p) { return Float::INFINITY }
+    (self.normalized() & p.normalized()).norm()
+  }
+}
+
+/// Signed, positive on the side the normal of the plane points to.
+impl Distance<Plane> for Point {
+  fn distance(&self, p:Plane)->Float {
+    if ideal_point(self) || ideal_plane(&p) { return Float::INFINITY }
+    (p.normalized() ^ self.normalized()).e0123()
+  }
+}
+impl Distance<Point> for Plane { fn distance(&self, p:Point)->Float { p.distance(*self) } }
+
+impl Distance<Line> for Point {
+  fn distance(&self, l:Line)->Float {
+    if ideal_point(self) || ideal_line(&l) { return Float::INFINITY }
+    (l.normalized() & self.normalized()).norm()
+  }
+}
+impl Distance<Point> for Line { fn distance(&self, p:Point)->Float { p.distance(*self) } }
+
+/// Skew lines have a signed distance from their meet, divided by the sine of
+/// the angle between them. Parallel lines have an unsigned distance.
+impl Distance<Line> for Line {
+  fn distance(&self, l:Line)->Float {
+    if ideal_line(self) || ideal_line(&l) { return Float::INFINITY }
+    let (a, b) = (self.normalized(), l.normalized());
+    let sin_ang = norm3(cross3(direction(&a), direction(&b)));
+    if sin_ang < tolerance() { return Point::from(center(&a)).distance(b) }
+    (a ^ b).e0123() / sin_ang
+  }
+}
+
+/// Zero unless the line is parallel to the plane, then signed like a point.
+impl Distance<Plane> for Line {
+  fn distance(&self, p:Plane)->Float {
+    if ideal_line(self) || ideal_plane(&p) { return Float::INFINITY }
+    let (l, p) = (self.normalized(), p.normalized());
+    if dot3(direction(&l), normal(&p)).abs() < tolerance() { Point::from(center(&l)).distance(p) } else { 0.0 }
+  }
+}
+impl Distance<Line> for Plane { fn distance(&self, l:Line)->Float { l.distance(*self) } }
+
+/// Zero unless the planes are parallel, then the signed distance of `p` from this plane.
+impl Distance<Plane> for Plane {
+  fn distance(&self, p:Plane)->Float {
+    if ideal_plane(self) || ideal_plane(&p) { return Float::INFINITY }
+    let (a, b) = (self.normalized(), p.normalized());
+    let cos_ang = a | b;
+    if 1.0 - cos_ang.abs() < tolerance() { a.d() - cos_ang.signum() * b.d() } else { 0.0 }
+  }
+}
+
+/// Between the normals, from zero to π.
+impl Angle<Plane> for Plane {
+  fn angle(&self, p:Plane)->Float {
+    if ideal_plane(self) || ideal_plane(&p) { return 0.0 }
+    (self.normalized() | p.normalized()).clamp(-1.0, 1.0).acos()
+  }
+}
+
+/// Between the directions, from zero to π.
+impl Angle<Line> for Line {
+  fn angle(&self, l:Line)->Float {
+    if ideal_line(self) || ideal_line(&l) { return 0.0 }
+    dot3(direction(&self.normalized()), direction(&l.normalized())).clamp(-1.0, 1.0).acos()
+  }
+}
+
+/// From -π/2 to π/2, positive when the line points to the side of the normal.
+impl Angle<Plane> for Line {
+  fn angle(&self, p:Plane)->Float {
+    if ideal_line(self) || ideal_plane(&p) { return 0.0 }
+    dot3(direction(&self.normalized()), normal(&p.normalized())).clamp(-1.0, 1.0).asin()
+  }
+}
+impl Angle<Line> for Plane { fn angle(&self, l:Line)->Float { l.angle(*self) } }
+
+/// From zero to π.
+impl Angle<Direction> for Direction {
+  fn angle(&self, d:Direction)->Float {
+    dot3(self.normalized().into(), d.normalized().into()).clamp(-1.0, 1.0).acos()
+  }
+}
+
+fn ideal_point(p:&Point)->bool { p.w().abs() < tolerance() }
+fn ideal_line(l:&Line)->bool { l.norm() < tolerance() }
+fn ideal_plane(p:&Plane)->bool { p.norm() < tolerance() }
+fn normal(p:&Plane)->[Float;3] { [p.a(), p.b(), p.c()] }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  #[test] fn point_distances() {
+    close(distance(point(1.0, 2.0, 3.0), point(4.0, 6.0, 3.0)), 5.0);
+    close(distance(point(1.0, 2.0, 3.0) * 2.0, point(4.0, 6.0, 3.0)), 5.0);
+    close(distance(point(0.0, 0.0, -3.0), plane(0.0, 0.0, 2.0, 2.0)), -2.0);
+    close(distance(plane(0.0, 0.0, 2.0, 2.0), point(0.0, 5.0, 3.0)), 4.0);
+    let z = point(0.0, 0.0, 0.0) & point(0.0, 0.0, 2.0);
+    close(distance(point(3.0, 4.0, 7.0), z), 5.0);
+    close(distance(z, point(0.0, -1.0, 0.0)), 1.0);
+    assert_eq!(distance(Point(Lanes::from_array([0.0, 1.0, 0.0, 0.0])), point(0.0, 0.0, 0.0)), Float::INFINITY);
+  }
+
+  #[test] fn line_distances() {
+    let z = point(0.0, 0.0, 0.0) & point(0.0, 0.0, 1.0);
+    let x = point(0.0, 2.0, 0.0) & point(1.0, 2.0, 0.0);
+    let d = distance(z, x);
+    close(d.abs(), 2.0);
+    close(distance(x, z), d);
+    close(distance(z, point(0.0, -2.0, 0.0) & point(1.0, -2.0, 0.0)), -d);
+    close(distance(z, point(0.0, -2.0, 0.0) & point(1.0, -2.0, 1.0)), -d);
+    close(distance(z, point(3.0, 4.0, 0.0) & point(3.0, 4.0, -1.0)), 5.0);
+    close(distance(z, point(0.0, 0.0, 0.0) & point(1.0, 0.0, 0.0)), 0.0);
+    close(distance(x, plane(0.0, 1.0, 0.0, 1.0)), 3.0);
+    close(distance(plane(0.0, 0.0, 1.0, 1.0), x), 1.0);
+    close(distance(z, plane(0.0, 0.0, 1.0, 1.0)), 0.0);
+    assert_eq!(distance(z, Line::from(horizon(1.0, 0.0, 0.0))), Float::INFINITY);
+  }
+
+  #[test] fn plane_distances() {
+    close(distance(plane(0.0, 0.0, 1.0, 1.0), plane(0.0, 0.0, 2.0, -4.0)), 3.0);
+    close(distance(plane(0.0, 0.0, 1.0, 1.0), plane(0.0, 0.0, -2.0, 4.0)), 3.0);
+    close(distance(plane(0.0, 0.0, -1.0, -1.0), plane(0.0, 0.0, 1.0, -2.0)), -3.0);
+    close(distance(plane(0.0, 0.0, 1.0, 1.0), plane(1.0, 0.0, 0.0, 1.0)), 0.0);
+  }
+
+  #[test] fn angles() {
+    close(angle(plane(0.0, 0.0, 1.0, 1.0), plane(0.0, 2.0, 2.0, -4.0)), pi/4.0);
+    close(angle(plane(0.0, 0.0, 1.0, 1.0), plane(0.0, 0.0, -1.0, 0.0)), pi);
+    let z = point(0.0, 0.0, 0.0) & point(0.0, 0.0, 1.0);
+    close(angle(z, point(5.0, 0.0, 0.0) & point(5.0, 1.0, 1.0)), pi/4.0);
+    close(angle(z, plane(1.0, 0.0, 0.0, 0.0)), 0.0);
+    close(angle(plane(0.0, 0.0, 2.0, 0.0), z).abs(), pi/2.0);
+    close(angle(plane(0.0, 0.0, 2.0, 0.0), z), -angle(plane(0.0, 0.0, -2.0, 0.0), z));
+    close(angle(Direction::new(1.0, 0.0, 0.0), Direction::new(1.0, 1.0, 0.0)), pi/4.0);
+    close(angle(z, Line::from(horizon(1.0, 0.0, 0.0))), 0.0);
+  }
+}
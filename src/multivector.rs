@@ -0,0 +1,319 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg, Not, BitXor, BitAnd, BitOr};
+use super::{Plane, Point, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual, maths::*};
+
+/// General multivector of R(3,0,1) with all 16 components.
+///
+/// The components are kept in the same lanes as the specialized elements so
+/// converting from them is a copy: planes in `p0`, the scalar and the Euclidean
+/// bivectors in `p1`, the pseudoscalar and the ideal bivectors in `p2` and
+/// points in `p3`.
+///
+/// ```
+/// use g3::{Multivector, Motor, plane, point};
+/// let m = plane(1.0, 0.0, 0.0, 2.0) + point(0.0, 1.0, 0.0);
+/// assert_eq!(m.grade(1), plane(1.0, 0.0, 0.0, 2.0).into());
+/// let r = Multivector::from(plane(1.0, 0.0, 0.0, 0.0)) * plane(0.0, 1.0, 0.0, 0.0);
+/// assert!(Motor::try_from(r).is_ok());
+/// ```
+#[derive(Default,Debug,Clone,Copy,PartialEq)]
+pub struct Multivector { pub(crate) p0:Lanes, pub(crate) p1:Lanes, pub(crate) p2:Lanes, pub(crate) p3:Lanes }
+
+// Grade of every component in storage order.
+const GRADES:[usize; 16] = [1, 1, 1, 1, 0, 2, 2, 2, 4, 2, 2, 2, 3, 3, 3, 3];
+
+// A generated kernel between all partitions of two multivectors.
+type Kernel = fn(&Lanes, &Lanes, &Lanes, &Lanes, &Lanes, &Lanes, &Lanes, &Lanes)->(Lanes, Lanes, Lanes, Lanes);
+
+fn product(a:&Multivector, b:&Multivector, kernel:Kernel)->Multivector {
+  let (p0, p1, p2, p3) = kernel(&a.p0, &a.p1, &a.p2, &a.p3, &b.p0, &b.p1, &b.p2, &b.p3);
+  Multivector{p0, p1, p2, p3}
+}
+
+impl Multivector {
+  /// From the components in storage order: e0, e1, e2, e3, 1, e23, e31, e12,
+  /// e0123, e01, e02, e03, e123, e032, e013, e021.
+  pub fn from_array(a:[Float;16])->Multivector {
+    let lanes = |i:usize| Lanes::from_array([a[i], a[i+1], a[i+2], a[i+3]]);
+    Multivector{p0: lanes(0), p1: lanes(4), p2: lanes(8), p3: lanes(12)}
+  }
+
+  /// The components in the storage order of `from_array`.
+  pub fn to_array(&self)->[Float;16] {
+    let mut a = [0.0; 16];
+    for (i, p) in [self.p0, self.p1, self.p2, self.p3].iter().enumerate() { a[4*i..4*i+4].copy_from_slice(&p.to_array()) }
+    a
+  }
+
+  /// Part of grade `k`, from the scalar at 0 to the pseudoscalar at 4.
+  pub fn grade(&self, k:usize)->Multivector { self.map(|i, x| if GRADES[i] == k { x } else { 0.0 }) }
+
+  /// Reverse the order of the vectors in every blade, flips the sign of the
+  /// bivectors and trivectors.
+  pub fn reverse(&self)->Multivector { self.map(|i, x| if GRADES[i] % 4 >= 2 { -x } else { x }) }
+
+  /// Grade involution, flips the sign of the odd grades.
+  pub fn involute(&self)->Multivector { self.map(|i, x| if GRADES[i] % 2 == 1 { -x } else { x }) }
+
+  /// Clifford conjugate, the reverse of the grade involution.
+  pub fn conjugate(&self)->Multivector { self.map(|i, x| if matches!(GRADES[i], 1 | 2) { -x } else { x }) }
+
+  pub fn approx_eq(&self, other:Multivector, epsilon:Float)->bool {
+    self.to_array().iter().zip(other.to_array()).all(|(a, b)| (a - b).abs() < epsilon)
+  }
+
+  #[inline] pub fn scalar(&self)->Float { self.p1[0] }
+  #[inline] pub fn e0(&self)->Float { self.p0[0] }
+  #[inline] pub fn e1(&self)->Float { self.p0[1] }
+  #[inline] pub fn e2(&self)->Float { self.p0[2] }
+  #[inline] pub fn e3(&self)->Float { self.p0[3] }
+  #[inline] pub fn e23(&self)->Float { self.p1[1] }
+  #[inline] pub fn e31(&self)->Float { self.p1[2] }
+  #[inline] pub fn e12(&self)->Float { self.p1[3] }
+  #[inline] pub fn e0123(&self)->Float { self.p2[0] }
+  #[inline] pub fn e01(&self)->Float { self.p2[1] }
+  #[inline] pub fn e02(&self)->Float { self.p2[2] }
+  #[inline] pub fn e03(&self)->Float { self.p2[3] }
+  #[inline] pub fn e123(&self)->Float { self.p3[0] }
+  #[inline] pub fn e032(&self)->Float { self.p3[1] }
+  #[inline] pub fn e013(&self)->Float { self.p3[2] }
+  #[inline] pub fn e021(&self)->Float { self.p3[3] }
+
+  fn map(&self, f:impl Fn(usize, Float)->Float)->Multivector {
+    let mut a = self.to_array();
+    for (i, x) in a.iter_mut().enumerate() { *x = f(i, *x) }
+    Multivector::from_array(a)
+  }
+}
+
+impl Add<Multivector> for Multivector { type Output = Multivector; fn add(self, m:Multivector)->Multivector { Multivector{p0: self.p0 + m.p0, p1: self.p1 + m.p1, p2: self.p2 + m.p2, p3: self.p3 + m.p3} } }
+impl Sub<Multivector> for Multivector { type Output = Multivector; fn sub(self, m:Multivector)->Multivector { self + -m } }
+impl Neg for Multivector { type Output = Multivector; fn neg(self)->Multivector { self * -1.0 } }
+impl Mul<Float> for Multivector {
+  type Output = Multivector;
+  fn mul(self, s:Float)->Multivector { let s = Lanes::splat(s); Multivector{p0: self.p0 * s, p1: self.p1 * s, p2: self.p2 * s, p3: self.p3 * s} }
+}
+impl Div<Float> for Multivector { type Output = Multivector; fn div(self, s:Float)->Multivector { self * (1.0 / s) } }
+impl MulAssign<Float> for Multivector { fn mul_assign(&mut self, s:Float) { *self = *self * s } }
+impl DivAssign<Float> for Multivector { fn div_assign(&mut self, s:Float) { *self = *self / s } }
+impl Mul<Multivector> for Float { type Output = Multivector; fn mul(self, m:Multivector)->Multivector { m * self } }
+
+/// Poincaré dual, the same map as `!` on the specialized elements.
+impl Not for Multivector { type Output = Multivector; fn not(self)->Multivector { Multivector{p0: self.p3, p1: self.p2, p2: self.p1, p3: self.p0} } }
+
+// Every element converts into a multivector, so they can be the right operand
+// of any product or sum with one.
+macro_rules! operators {
+  ($($t:ty),*) => {$(
+    impl Add<$t> for Multivector { type Output = Multivector; fn add(self, x:$t)->Multivector { self + Multivector::from(x) } }
+    impl Sub<$t> for Multivector { type Output = Multivector; fn sub(self, x:$t)->Multivector { self - Multivector::from(x) } }
+    impl AddAssign<$t> for Multivector { fn add_assign(&mut self, x:$t) { *self = *self + x } }
+    impl SubAssign<$t> for Multivector { fn sub_assign(&mut self, x:$t) { *self = *self - x } }
+    /// Geometric product
+    impl Mul<$t> for Multivector { type Output = Multivector; fn mul(self, x:$t)->Multivector { self * Multivector::from(x) } }
+    /// Meet, the outer product.
+    impl BitXor<$t> for Multivector { type Output = Multivector; fn bitxor(self, x:$t)->Multivector { self ^ Multivector::from(x) } }
+    /// Join, the regressive product.
+    impl BitAnd<$t> for Multivector { type Output = Multivector; fn bitand(self, x:$t)->Multivector { self & Multivector::from(x) } }
+    /// Symmetric inner product
+    impl BitOr<$t> for Multivector { type Output = Multivector; fn bitor(self, x:$t)->Multivector { self | Multivector::from(x) } }
+  )*}
+}
+
+operators!(Plane, Point, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual);
+
+// Sums of two different elements, like a plane and a point, have no element of
+// their own and are multivectors.
+macro_rules! sums {
+  (@row $a:ident: $($b:ident),*) => {$(
+    impl Add<$b> for $a { type Output = Multivector; fn add(self, x:$b)->Multivector { Multivector::from(self) + x } }
+    impl Sub<$b> for $a { type Output = Multivector; fn sub(self, x:$b)->Multivector { Multivector::from(self) - x } }
+  )*};
+  ($($a:ident: [$($b:ident),*]);*) => {$( sums!(@row $a: $($b),*); )*};
+}
+
+sums! {
+  Plane: [Point, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual];
+  Point: [Plane, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual];
+  Direction: [Plane, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual];
+  Line: [Plane, Point, Direction, Flector];
+  Branch: [Plane, Point, Direction, Flector];
+  Horizon: [Plane, Point, Direction, Flector];
+  Motor: [Plane, Point, Direction, Flector];
+  Rotor: [Plane, Point, Direction, Flector];
+  Translator: [Plane, Point, Direction, Flector];
+  Flector: [Plane, Point, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Dual];
+  Dual: [Plane, Point, Direction, Flector]
+}
+
+// Sums that stay within one grade or within the even subalgebra keep an
+// element, both sides are taken to the element of the sum and added there.
+// Translators and horizons are in `translator.rs`, the scalar of a translator
+// is fixed.
+macro_rules! closed_sums {
+  ($($out:ident: [$($a:ident + $b:ident),*]);*) => {$($(
+    impl Add<$b> for $a { type Output = $out; fn add(self, x:$b)->$out { $out::from(self) + $out::from(x) } }
+    impl Sub<$b> for $a { type Output = $out; fn sub(self, x:$b)->$out { $out::from(self) - $out::from(x) } }
+  )*)*}
+}
+
+closed_sums! {
+  Point: [Point + Direction, Direction + Point];
+  Line: [Line + Branch, Branch + Line, Line + Horizon, Horizon + Line, Branch + Horizon, Horizon + Branch];
+  Rotor: [Rotor + Branch, Branch + Rotor];
+  Motor: [Motor + Line, Line + Motor, Motor + Branch, Branch + Motor, Motor + Horizon, Horizon + Motor,
+    Motor + Rotor, Rotor + Motor, Motor + Translator, Translator + Motor, Motor + Dual, Dual + Motor,
+    Line + Rotor, Rotor + Line, Line + Translator, Translator + Line, Line + Dual, Dual + Line,
+    Branch + Translator, Translator + Branch, Branch + Dual, Dual + Branch,
+    Horizon + Rotor, Rotor + Horizon, Horizon + Dual, Dual + Horizon,
+    Rotor + Translator, Translator + Rotor, Rotor + Dual, Dual + Rotor, Translator + Dual, Dual + Translator]
+}
+
+impl AddAssign<Multivector> for Multivector { fn add_assign(&mut self, m:Multivector) { *self = *self + m } }
+impl SubAssign<Multivector> for Multivector { fn sub_assign(&mut self, m:Multivector) { *self = *self - m } }
+/// Geometric product
+impl Mul<Multivector> for Multivector { type Output = Multivector; fn mul(self, m:Multivector)->Multivector { product(&self, &m, kernels::gp_0123_0123) } }
+/// Meet, the outer product.
+impl BitXor<Multivector> for Multivector { type Output = Multivector; fn bitxor(self, m:Multivector)->Multivector { product(&self, &m, kernels::ext_0123_0123) } }
+/// Join, the regressive product, `a & b = !(!a ^ !b)`.
+impl BitAnd<Multivector> for Multivector { type Output = Multivector; fn bitand(self, m:Multivector)->Multivector { product(&self, &m, kernels::reg_0123_0123) } }
+/// Symmetric inner product, the part of grade `|r - s|` of the product of grades `r` and `s`.
+impl BitOr<Multivector> for Multivector { type Output = Multivector; fn bitor(self, m:Multivector)->Multivector { product(&self, &m, kernels::dot_0123_0123) } }
+
+impl From<Float> for Multivector { fn from(s:Float)->Multivector { Multivector{p1: Lanes::from_array([s, 0.0, 0.0, 0.0]), ..Default::default()} } }
+impl From<Plane> for Multivector { fn from(p:Plane)->Multivector { Multivector{p0: p.0, ..Default::default()} } }
+impl From<Point> for Multivector { fn from(p:Point)->Multivector { Multivector{p3: p.0, ..Default::default()} } }
+impl From<Direction> for Multivector { fn from(d:Direction)->Multivector { Multivector{p3: d.0, ..Default::default()} } }
+impl From<Line> for Multivector { fn from(l:Line)->Multivector { Multivector{p1: zero_first(l.p1), p2: zero_first(l.p2), ..Default::default()} } }
+impl From<Branch> for Multivector { fn from(b:Branch)->Multivector { Multivector{p1: zero_first(b.0), ..Default::default()} } }
+impl From<Horizon> for Multivector { fn from(h:Horizon)->Multivector { Multivector{p2: zero_first(h.p2), ..Default::default()} } }
+impl From<Motor> for Multivector { fn from(m:Motor)->Multivector { Multivector{p1: m.p1, p2: m.p2, ..Default::default()} } }
+impl From<Rotor> for Multivector { fn from(r:Rotor)->Multivector { Multivector{p1: r.0, ..Default::default()} } }
+impl From<Translator> for Multivector { fn from(t:Translator)->Multivector { Motor::from(t).into() } }
+impl From<Flector> for Multivector { fn from(f:Flector)->Multivector { Multivector{p0: f.p0, p3: f.p3, ..Default::default()} } }
+impl From<Dual> for Multivector { fn from(d:Dual)->Multivector { Multivector{p1: Lanes::from_array([d.scalar(), 0.0, 0.0, 0.0]), p2: Lanes::from_array([d.e0123(), 0.0, 0.0, 0.0]), ..Default::default()} } }
+
+// Converting back is exact and only succeeds when every component outside of
+// the element is zero, otherwise the multivector is handed back unchanged.
+macro_rules! try_from {
+  ($($t:ty => |$m:ident| $zero:expr, $value:expr;)*) => {$(
+    impl TryFrom<Multivector> for $t {
+      type Error = Multivector;
+      fn try_from($m:Multivector)->Result<$t, Multivector> { if $zero { Ok($value) } else { Err($m) } }
+    }
+  )*}
+}
+
+fn is_zero(p:Lanes)->bool { p == Lanes::splat(0.0) }
+fn first_is_zero(p:Lanes)->bool { p[0] == 0.0 }
+
+try_from! {
+  Float => |m| is_zero(m.p0) && is_zero(zero_first(m.p1)) && is_zero(m.p2) && is_zero(m.p3), m.scalar();
+  Plane => |m| is_zero(m.p1) && is_zero(m.p2) && is_zero(m.p3), Plane(m.p0);
+  Point => |m| is_zero(m.p0) && is_zero(m.p1) && is_zero(m.p2), Point(m.p3);
+  Direction => |m| is_zero(m.p0) && is_zero(m.p1) && is_zero(m.p2) && first_is_zero(m.p3), Direction(m.p3);
+  Line => |m| is_zero(m.p0) && first_is_zero(m.p1) && first_is_zero(m.p2) && is_zero(m.p3), Line{p1: m.p1, p2: m.p2};
+  Branch => |m| is_zero(m.p0) && first_is_zero(m.p1) && is_zero(m.p2) && is_zero(m.p3), Branch(m.p1);
+  Horizon => |m| is_zero(m.p0) && is_zero(m.p1) && first_is_zero(m.p2) && is_zero(m.p3), Horizon{p2: m.p2};
+  Motor => |m| is_zero(m.p0) && is_zero(m.p3), Motor{p1: m.p1, p2: m.p2};
+  Rotor => |m| is_zero(m.p0) && is_zero(m.p2) && is_zero(m.p3), Rotor(m.p1);
+  Translator => |m| is_zero(m.p0) && m.p1 == Lanes::from_array([1.0, 0.0, 0.0, 0.0]) && first_is_zero(m.p2) && is_zero(m.p3), Translator{p2: m.p2};
+  Flector => |m| is_zero(m.p1) && is_zero(m.p2), Flector{p0: m.p0, p3: m.p3};
+  Dual => |m| is_zero(m.p0) && is_zero(zero_first(m.p1)) && is_zero(zero_first(m.p2)) && is_zero(m.p3), Dual::new(m.scalar(), m.e0123());
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  fn mv<T:Into<Multivector>>(x:T)->Multivector { x.into() }
+
+  #[test] fn multivector_basis() {
+    let e = |i:usize| { let mut a = [0.0; 16]; a[i] = 1.0; Multivector::from_array(a) };
+    close(e(0) * e(0), Multivector::default());
+    for v in [e(1), e(2), e(3)] { close(v * v, 1.0) }
+    close(e(2) * e(3), e(5));
+    close(e(3) * e(1), e(6));
+    close(e(0) * e(1) * e(2) * e(3), e(8));
+    close(e(0) * e(3) * e(2), e(13));
+    close(e(0) * e(2) * e(1), e(15));
+    close(e(12) * e(12), -1.0);
+    close(e(8) * e(8), Multivector::default());
+  }
+
+  #[test] fn multivector_matches_elements() {
+    let (p, q) = (plane(1.0, -2.0, 0.5, 3.0), plane(-0.3, 1.0, 2.0, -1.0));
+    let (a, b) = (point(1.0, 2.0, -3.0), point(-0.5, 0.2, 4.0));
+    let l = line(0.3, -1.0, 2.0, 1.0, 0.5, -0.2);
+    let m = screw();
+    let n = Motor::from_screw_axis(-0.4, 1.0, line(-1.0, 2.0, 0.0, 0.0, 1.0, 1.0).normalized());
+    close(mv(p) * q, p * q);
+    close(mv(p) * a, p * a);
+    close(mv(m) * n, m * n);
+    close(mv(p) ^ q, p ^ q);
+    close(mv(p) ^ l, p ^ l);
+    close(mv(p) ^ a, p ^ a);
+    close(mv(a) & b, a & b);
+    close(mv(a) & l, a & l);
+    close(mv(p) | q, p | q);
+    close(mv(p) | l, p | l);
+    close(mv(p) | a, p | a);
+    close(mv(a) | l, a | l);
+    close(mv(a) | b, a | b);
+    close(mv(l) | l, l | l);
+    close(mv(m) * a * m.reverse(), m.apply(a));
+    close(mv(m) * l * m.reverse(), m.apply(l));
+  }
+
+  #[test] fn multivector_involutions() {
+    let x = Multivector::from_array(std::array::from_fn(|i| i as Float + 1.0));
+    let y = Multivector::from_array(std::array::from_fn(|i| 3.0 - 0.5 * i as Float));
+    close((0..5).map(|k| x.grade(k)).fold(Multivector::default(), |s, g| s + g), x);
+    close((x * y).reverse(), y.reverse() * x.reverse());
+    close((x * y).involute(), x.involute() * y.involute());
+    close((x * y).conjugate(), y.conjugate() * x.conjugate());
+    close(x * y * x, x * (y * x));
+    assert_eq!(mv(rotor(1.0, 1.0, 2.0, 3.0)).reverse(), mv(rotor(1.0, 1.0, 2.0, 3.0).reverse()));
+  }
+
+  #[test] fn multivector_conversions() {
+    let t = translator(2.0, 1.0, 0.0, 0.0);
+    assert_eq!(Translator::try_from(mv(t)), Ok(t));
+    let l = line(0.3, -1.0, 2.0, 1.0, 0.5, -0.2);
+    assert_eq!(Line::try_from(mv(l)), Ok(l));
+    assert_eq!(Branch::try_from(mv(l)), Err(mv(l)));
+    let p = plane(1.0, 0.0, 0.0, 2.0);
+    assert_eq!(Plane::try_from(mv(p)), Ok(p));
+    assert_eq!(Point::try_from(mv(p) + point(0.0, 1.0, 0.0)).map_err(|m| m.grade(3)), Err(mv(point(0.0, 1.0, 0.0))));
+    assert_eq!(Dual::try_from(mv(dual(2.0, 3.0))), Ok(dual(2.0, 3.0)));
+    assert_eq!(Float::try_from(mv(2.0)), Ok(2.0));
+    assert!(Direction::try_from(mv(point(1.0, 0.0, 0.0))).is_err());
+  }
+
+  #[test] fn multivector_sums_of_elements() {
+    let (p, a, l) = (plane(1.0, 0.0, 0.0, 2.0), point(0.0, 1.0, 0.0), line(0.3, -1.0, 2.0, 1.0, 0.5, -0.2));
+    let (r, t) = (rotor(1.0, 1.0, 2.0, 3.0), translator(2.0, 1.0, 0.0, 0.0));
+    assert_eq!(p + a, mv(p) + mv(a));
+    assert_eq!(a - p, mv(a) - mv(p));
+    assert_eq!(l + p + a, mv(l) + mv(p) + mv(a));
+    assert_eq!(mv(r + t), mv(r) + mv(t));
+    assert_eq!(mv(dual(1.0, 2.0) - l), mv(dual(1.0, 2.0)) - mv(l));
+  }
+
+  #[test] fn closed_sums_of_elements() {
+    let (l, b, h) = (line(0.3, -1.0, 2.0, 1.0, 0.5, -0.2), branch(1.0, 2.0, -3.0), horizon(0.5, 0.0, 4.0));
+    let (r, t, m, d) = (rotor(1.0, 1.0, 2.0, 3.0), translator(2.0, 1.0, 0.0, 0.0), motor(1.0, 4.0, 3.0, 2.0, 5.0, 6.0, 7.0, 8.0), dual(1.0, 2.0));
+    let (a, u) = (point(1.0, 2.0, 3.0), Direction::new(0.0, 1.0, 0.0));
+    let lines:[(Line, Multivector);4] = [(l + b, mv(l) + mv(b)), (h - l, mv(h) - mv(l)), (b + h, mv(b) + mv(h)), (h - b, mv(h) - mv(b))];
+    for (x, y) in lines { assert_eq!(mv(x), y) }
+    let motors:[(Motor, Multivector);8] = [(m + l, mv(m) + mv(l)), (t + m, mv(t) + mv(m)), (m - t, mv(m) - mv(t)), (r - l, mv(r) - mv(l)),
+      (d + h, mv(d) + mv(h)), (t - b, mv(t) - mv(b)), (h - t, mv(h) - mv(t)), (r + t, mv(r) + mv(t))];
+    for (x, y) in motors { assert_eq!(mv(x), y) }
+    let r2:Rotor = b - r;
+    assert_eq!(mv(r2), mv(b) - mv(r));
+    let t2:Translator = h + t;
+    assert_eq!(mv(t2), mv(h) + mv(t));
+    let (p, q):(Point, Point) = (a + u, u - a);
+    assert_eq!((mv(p), mv(q)), (mv(a) + mv(u), mv(u) - mv(a)));
+  }
+}
@@ -0,0 +1,187 @@
+use std::{fmt::{self, Display, Formatter}, str::FromStr};
+use super::{Plane, Point, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual, Multivector};
+
+// Names of the components in the storage order of `Multivector::to_array`,
+// the scalar is written as a bare number.
+const NAMES:[&str; 16] = ["e0", "e1", "e2", "e3", "1", "e23", "e31", "e12", "e0123", "e01", "e02", "e03", "e123", "e032", "e013", "e021"];
+
+// Order of the written terms, by grade with e0 after the Euclidean vectors,
+// like the constructors: `plane(a, b, c, d)` is ae1 + be2 + ce3 + de0.
+const ORDER:[usize; 16] = [4, 1, 2, 3, 0, 5, 6, 7, 9, 10, 11, 13, 14, 15, 12, 8];
+
+/// Why a string is not an element in blade notation.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum ParseError {
+  /// Something other than a number, a blade or a sign at this byte offset.
+  Syntax(usize),
+  /// A blade with a repeated basis vector or one outside e0 to e3, as written.
+  Blade(String),
+  /// A valid blade, as written, that the element has no component for.
+  NotInElement { blade:String, element:&'static str },
+  /// All blades fit but the values don't, like a translator whose scalar is not 1.
+  Invalid(&'static str),
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f:&mut Formatter<'_>)->fmt::Result {
+    match self {
+      ParseError::Syntax(at) => write!(f, "expected a number or a blade at byte {}", at),
+      ParseError::Blade(b) => write!(f, "{} is not a blade of e0, e1, e2 and e3", b),
+      ParseError::NotInElement{blade, element} => write!(f, "{} has no {} component", element, blade),
+      ParseError::Invalid(element) => write!(f, "not a valid {}", element),
+    }
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+fn subscript(c:char)->char { char::from_u32('₀' as u32 + c.to_digit(10).unwrap()).unwrap() }
+
+fn write_number(f:&mut Formatter<'_>, x:impl Display)->fmt::Result {
+  match f.precision() { Some(p) => write!(f, "{:.*}", p, x), None => write!(f, "{}", x) }
+}
+
+// The non-zero components as a sum, subscripted with `{:#}`.
+fn write_terms(f:&mut Formatter<'_>, m:Multivector)->fmt::Result {
+  let a = m.to_array();
+  let mut first = true;
+  for i in ORDER {
+    if a[i] == 0.0 { continue }
+    if first { write_number(f, a[i])? } else {
+      f.write_str(if a[i] < 0.0 { " - " } else { " + " })?;
+      write_number(f, a[i].abs())?;
+    }
+    first = false;
+    if i == 4 { continue }
+    f.write_str("e")?;
+    for c in NAMES[i][1..].chars() { write!(f, "{}", if f.alternate() { subscript(c) } else { c })? }
+  }
+  if first { write_number(f, 0.0)? }
+  Ok(())
+}
+
+// The index into `NAMES` of a blade written as its basis vectors in any
+// order and whether that order is odd, e10 is -e01.
+fn blade(vectors:&[u32])->Option<(usize, bool)> {
+  if vectors.is_empty() { return Some((4, false)) }
+  NAMES.iter().enumerate().filter(|(i, _)| *i != 4).find_map(|(i, name)| {
+    let canonical:Vec<u32> = name[1..].chars().map(|c| c.to_digit(10).unwrap()).collect();
+    if canonical.len() != vectors.len() { return None }
+    let at:Option<Vec<usize>> = vectors.iter().map(|v| canonical.iter().position(|c| c == v)).collect();
+    let at = at?;
+    if (0..at.len()).any(|j| at[..j].contains(&at[j])) { return None }
+    let swaps = (0..at.len()).flat_map(|j| (j+1..at.len()).map(move |k| (j, k))).filter(|&(j, k)| at[j] > at[k]).count();
+    Some((i, swaps % 2 == 1))
+  })
+}
+
+// A sum of terms like `0.5e12` or `-e0`, keeping only the blades of the element.
+fn parse(s:&str, blades:&[&str], element:&'static str)->Result<Multivector, ParseError> {
+  let mut a = [0.0; 16];
+  let mut chars = s.char_indices().peekable();
+  let skip = |chars:&mut std::iter::Peekable<std::str::CharIndices>| while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {};
+  let mut first = true;
+  loop {
+    skip(&mut chars);
+    let at = chars.peek().map_or(s.len(), |(i, _)| *i);
+    let negative = match chars.next_if(|(_, c)| *c == '+' || *c == '-') {
+      Some((_, c)) => c == '-',
+      None if first => false,
+      None => return Err(ParseError::Syntax(at)),
+    };
+    skip(&mut chars);
+    let start = chars.peek().map_or(s.len(), |(i, _)| *i);
+    while chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.').is_some() {}
+    let end = chars.peek().map_or(s.len(), |(i, _)| *i);
+    let number = if start < end { Some(s[start..end].parse().map_err(|_| ParseError::Syntax(start))?) } else { None };
+    let mut vectors = vec![];
+    if chars.next_if(|(_, c)| *c == 'e').is_some() {
+      while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || ('₀'..='₉').contains(c)) {
+        vectors.push(if c.is_ascii_digit() { c.to_digit(10).unwrap() } else { c as u32 - '₀' as u32 });
+      }
+      if vectors.is_empty() { return Err(ParseError::Syntax(end)) }
+    } else if number.is_none() {
+      return Err(ParseError::Syntax(start))
+    }
+    let written = &s[end..chars.peek().map_or(s.len(), |(i, _)| *i)];
+    let (i, odd) = blade(&vectors).ok_or_else(|| ParseError::Blade(written.to_string()))?;
+    if !blades.contains(&NAMES[i]) {
+      let blade = if i == 4 { "scalar" } else { written };
+      return Err(ParseError::NotInElement{blade: blade.to_string(), element})
+    }
+    let x = number.unwrap_or(1.0);
+    a[i] += if negative != odd { -x } else { x };
+    first = false;
+    skip(&mut chars);
+    if chars.peek().is_none() { return Ok(Multivector::from_array(a)) }
+  }
+}
+
+// Every element is written as the sum of its non-zero components in blade
+// notation, `{:.2}` sets the precision of the numbers and `{:#}` writes the
+// blades with subscripts. Parsing takes the same notation with the basis
+// vectors of a blade in any order, e10 is -e01.
+macro_rules! notation {
+  ($($t:ident [$($b:tt)*]),*) => {$(
+    impl Display for $t {
+      #[allow(clippy::clone_on_copy)]
+      fn fmt(&self, f:&mut Formatter<'_>)->fmt::Result { write_terms(f, Multivector::from(self.clone())) }
+    }
+    impl FromStr for $t {
+      type Err = ParseError;
+      fn from_str(s:&str)->Result<$t, ParseError> {
+        let m = parse(s, &[$(stringify!($b)),*], stringify!($t))?;
+        $t::try_from(m).map_err(|_| ParseError::Invalid(stringify!($t)))
+      }
+    }
+  )*}
+}
+
+notation! {
+  Plane [e1 e2 e3 e0],
+  Point [e032 e013 e021 e123],
+  Direction [e032 e013 e021],
+  Line [e23 e31 e12 e01 e02 e03],
+  Branch [e23 e31 e12],
+  Horizon [e01 e02 e03],
+  Motor [1 e23 e31 e12 e01 e02 e03 e0123],
+  Rotor [1 e23 e31 e12],
+  Translator [1 e01 e02 e03],
+  Flector [e1 e2 e3 e0 e032 e013 e021 e123],
+  Dual [1 e0123],
+  Multivector [1 e1 e2 e3 e0 e23 e31 e12 e01 e02 e03 e032 e013 e021 e123 e0123]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::{plane, point, rotor, translator};
+
+  #[test] fn write_blades() {
+    assert_eq!(plane(1.0, 2.0, 3.0, 4.0).to_string(), "1e1 + 2e2 + 3e3 + 4e0");
+    assert_eq!(point(1.0, -2.0, 0.0).to_string(), "1e032 - 2e013 + 1e123");
+    assert_eq!(format!("{:.2}", rotor(1.0, 0.0, 0.0, 1.0)), "0.88 + 0.48e12");
+    assert_eq!(format!("{:#}", translator(2.0, 1.0, 0.0, 0.0)), "1 - 1e₀₁");
+    assert_eq!(Multivector::default().to_string(), "0");
+  }
+
+  #[test] fn read_blades() {
+    assert_eq!("1e1 + 2e2 + 3e3 + 4e0".parse(), Ok(plane(1.0, 2.0, 3.0, 4.0)));
+    assert_eq!(" 4e0+3e3 +2e2+ e1 ".parse(), Ok(plane(1.0, 2.0, 3.0, 4.0)));
+    assert_eq!("1 - 1e₀₁".parse(), Ok(translator(2.0, 1.0, 0.0, 0.0)));
+    assert_eq!("1 + 1e10".parse(), Ok(translator(2.0, 1.0, 0.0, 0.0)));
+    assert_eq!("0.7 + 0.7e12 + 1e01".parse::<Motor>().map(|m| [m.scalar(), m.e12(), m.e01()]), Ok([0.7, 0.7, 1.0]));
+    assert_eq!("e321".parse::<Multivector>().map(|m| m.e123()), Ok(-1.0));
+  }
+
+  #[test] fn read_errors() {
+    assert_eq!("1e1 + 1e123".parse::<Plane>(), Err(ParseError::NotInElement{blade: "e123".into(), element: "Plane"}));
+    assert_eq!("2 + 1e1".parse::<Plane>(), Err(ParseError::NotInElement{blade: "scalar".into(), element: "Plane"}));
+    assert_eq!("1e11".parse::<Plane>(), Err(ParseError::Blade("e11".into())));
+    assert_eq!("1e4".parse::<Plane>(), Err(ParseError::Blade("e4".into())));
+    assert_eq!("1e1 2e2".parse::<Plane>(), Err(ParseError::Syntax(4)));
+    assert_eq!("1e1 + x".parse::<Plane>(), Err(ParseError::Syntax(6)));
+    assert_eq!("".parse::<Plane>(), Err(ParseError::Syntax(0)));
+    assert_eq!("2e01".parse::<Translator>(), Err(ParseError::Invalid("Translator")));
+  }
+}
@@ -1,5 +1,5 @@
-use std::{fmt::{Display, Formatter, Result},simd::{f32x4,mask32x4, SimdFloat},ops::*};
-use crate::{*,maths::*};
+use std::ops::*;
+use super::{*,maths::*};
 
 /// e₀
 pub const e0:Plane = plane(0.0, 0.0, 0.0, 1.0);
@@ -11,26 +11,34 @@ pub const e2:Plane = plane(0.0, 1.0, 0.0, 0.0);
 pub const e3:Plane = plane(0.0, 0.0, 1.0, 0.0);
 
 /// ae₁ + be₂ + ce₃ + de₀
-#[inline] pub const fn plane(a:f32,b:f32,c:f32,d:f32)->Plane { Plane::new(a,b,c,d) }
+#[inline] pub const fn plane(a:Float,b:Float,c:Float,d:Float)->Plane { Plane::new(a,b,c,d) }
 
 /// ae₁ + be₂ + ce₃ + de₀
+///
+/// Laid out as four floats `[d, a, b, c]`, the distance e₀ first.
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable))]
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Plane (pub(crate) f32x4);
+pub struct Plane<L = Lanes>(pub(crate) L);
+pod!(Plane);
+
+serde_blades!(Plane { a, b, c, d } => |[a, b, c, d]| Plane::new(a, b, c, d));
 
 impl Plane {
   /// The constructor performs the rearrangement so the plane can be specified
   /// in the familiar form: ax + by + cz + d
-  #[inline] pub const fn new(a:f32,b:f32,c:f32,d:f32)->Plane { Plane(f32x4::from_array([d,a,b,c]))}
+  #[inline] pub const fn new(a:Float,b:Float,c:Float,d:Float)->Plane { Plane(Lanes::from_array([d,a,b,c]))}
 
   /// Normalize this plane $p$ such that $p \cdot p = 1$.
   /// In order to compute the cosine of the angle between planes via the
   /// inner product operator `|`, the planes must be normalized. Producing a
   /// normalized rotor between two planes with the geometric product `*` also
   /// requires that the planes are normalized.
+  ///
+  /// Every component is divided by the norm of the normal, $e_0$ included, so
+  /// the plane stays where it is.
   pub fn normalized(&self)->Plane {
-    let mut inv_norm  = rsqrt_nr1(&hi_dp_bc(&self.0, &self.0));
-    inv_norm[0] = 1.0;
-    Plane(inv_norm * &self.0)
+    Plane(rsqrt_nr1(&hi_dp_bc(&self.0, &self.0)) * &self.0)
   }
 
   /// Compute the plane norm, which is often used to compute distances
@@ -38,7 +46,7 @@ impl Plane {
   /// Given a normalized point $P$ and normalized line $\ell$, the plane
   /// $P\vee\ell$ containing both $\ell$ and $P$ will have a norm equivalent
   /// to the distance between $P$ and $\ell$.
-  #[inline] pub fn norm(&self)->f32 {
+  #[inline] pub fn norm(&self)->Float {
     sqrt_nr1(&hi_dp(&self.0, &self.0))[0]
   }
 
@@ -47,7 +55,10 @@ impl Plane {
     Plane(inv_norm * inv_norm * &self.0)
   }
 
-  pub fn approx_eq(&self, other:Plane, epsilon:f32)->bool {(&self.0 - other.0).abs() < f32x4::splat(epsilon)}
+  /// A vector is its own reverse.
+  #[inline] pub fn reverse(&self)->Plane { *self }
+
+  pub fn approx_eq(&self, other:Plane, epsilon:Float)->bool {lanes_close(&self.0, &other.0, epsilon)}
 
   /// Project a plane onto a point. Given a plane $p$ and point $P$, produces the
   /// plane through $P$ that is parallel to $p$.
@@ -65,257 +76,128 @@ impl Plane {
   /// possible).
   pub fn project_line(self, l:Line)->Plane {  (self | l) | l }
 
-  #[inline] pub fn a(&self) ->f32 { self.0[1] }
-  #[inline] pub fn b(&self) ->f32 { self.0[2] }
-  #[inline] pub fn c(&self) ->f32 { self.0[3] }
-  #[inline] pub fn d(&self)->f32 { self.0[0] }
-  #[inline] pub fn e0(&self)->f32 { self.d() }
-  #[inline] pub fn e1(&self)->f32 { self.a() }
-  #[inline] pub fn e2(&self)->f32 { self.b() }
-  #[inline] pub fn e3(&self)->f32 { self.c() }
+  #[inline] pub fn a(&self) ->Float { self.0[1] }
+  #[inline] pub fn b(&self) ->Float { self.0[2] }
+  #[inline] pub fn c(&self) ->Float { self.0[3] }
+  #[inline] pub fn d(&self)->Float { self.0[0] }
+  #[inline] pub fn e0(&self)->Float { self.d() }
+  #[inline] pub fn e1(&self)->Float { self.a() }
+  #[inline] pub fn e2(&self)->Float { self.b() }
+  #[inline] pub fn e3(&self)->Float { self.c() }
 }
 
-impl Display for Plane {fn fmt(&self, f: &mut Formatter<'_>) -> Result {write!(f, "{}e1 + {}e2 + {}e2 + {}e0)", self.a(), self.b(), self.c(), self.d())}}
+/// From the coefficients `(a, b, c, d)` of $ax + by + cz + d = 0$
+#[cfg(feature = "mint")] impl From<mint::Vector4<Float>> for Plane { fn from(v: mint::Vector4<Float>)->Plane { plane(v.x, v.y, v.z, v.w) } }
+#[cfg(feature = "mint")] impl From<Plane> for mint::Vector4<Float> { fn from(p: Plane)->Self { [p.a(), p.b(), p.c(), p.d()].into() } }
+/// From the coefficients `(a, b, c, d)` of $ax + by + cz + d = 0$
+#[cfg(feature = "glam")] impl From<Vec4> for Plane { fn from(v: Vec4)->Plane { plane(v.x, v.y, v.z, v.w) } }
+#[cfg(feature = "glam")] impl From<Plane> for Vec4 { fn from(p: Plane)->Self { Vec4::new(p.a(), p.b(), p.c(), p.d()) } }
 
 /// Reflect another plane $p_2$ through this plane $p_1$. The operation
 /// performed via this call operator is an optimized routine equivalent to
 /// the expression $p_1 p_2 p_1$.
-impl FnMut<(Plane,)> for Plane { extern "rust-call" fn call_mut(&mut self, args: (Plane,))->Plane { self.call(args) }}
-impl FnOnce<(Plane,)> for Plane { type Output = Plane; extern "rust-call" fn call_once(self, args: (Plane,))->Plane {self.call(args)} }
-impl Fn<(Plane,)> for Plane {extern "rust-call" fn call(&self, args: (Plane,))->Plane { Plane(sw00(&self.0, &args.0.0)) } }
+impl Transform<Plane> for Plane { type Output = Plane; fn apply(&self, p:Plane)->Plane { Plane(kernels::sw_0_0(&kernels::sw_0_0_terms(&self.0), &p.0)) } }
 
 /// Reflect the point $P$ through this plane $p$. The operation
 /// performed via this call operator is an optimized routine equivalent to
 /// the expression $p P p$.
-impl FnMut<(Point,)> for Plane { extern "rust-call" fn call_mut(&mut self, args: (Point,))->Point {self.call(args)} }
-impl FnOnce<(Point,)> for Plane { type Output = Point; extern "rust-call" fn call_once(self, args: (Point,))->Point { self.call(args) }}
-impl Fn<(Point,)> for Plane {extern "rust-call" fn call(&self, args: (Point,))->Point { Point(sw30(&self.0, &args.0.0)) } }
+impl Transform<Point> for Plane { type Output = Point; fn apply(&self, a:Point)->Point { Point(kernels::sw_0_3(&kernels::sw_0_3_terms(&self.0), &a.0)) } }
+
+/// Reflect the origin through this plane $p$.
+impl Transform<Origin> for Plane { type Output = Point; fn apply(&self, _o:Origin)->Point { Point(kernels::sw_0_3_terms(&self.0)[0]) } }
+
+/// Reflect the direction $d$ through this plane $p$, a direction is a point with
+/// a homogeneous coordinate of zero so this is the same routine as for points.
+impl Transform<Direction> for Plane { type Output = Direction; fn apply(&self, d:Direction)->Direction { Direction(kernels::sw_0_3(&kernels::sw_0_3_terms(&self.0), &d.0)) } }
 
 /// Reflect line $\ell$ through this plane $p$. The operation
 /// performed via this call operator is an optimized routine equivalent to
 /// the expression $p \ell p$.
-impl FnMut<(Line,)> for Plane { extern "rust-call" fn call_mut(&mut self, args: (Line,))->Line {self.call(args)} }
-impl FnOnce<(Line,)> for Plane { type Output = Line; extern "rust-call" fn call_once(self, args: (Line,))->Line { self.call(args) }}
-impl Fn<(Line,)> for Plane { extern "rust-call" fn call(&self, args: (Line,)) -> Line { let (p1, p2) = sw10(&self.0, &args.0.p1);Line{p1,p2:p2 + sw20(&self.0, &args.0.p2)} } }
+impl Transform<Line> for Plane { type Output = Line; fn apply(&self, l:Line) -> Line { let (p1, p2) = kernels::sw_0_12(&kernels::sw_0_12_terms(&self.0), &l.p1, &l.p2);Line{p1,p2} } }
+
+/// Reflect the branch $b$ through this plane $p$, the result only passes
+/// through the origin if $p$ does too.
+impl Transform<Branch> for Plane { type Output = Line; fn apply(&self, b:Branch) -> Line { let (p1, p2) = kernels::sw_0_12(&kernels::sw_0_12_terms(&self.0), &b.0, &Lanes::splat(0.0));Line{p1,p2} } }
+
+/// Reflect the horizon $h$ through this plane $p$.
+impl Transform<Horizon> for Plane { type Output = Horizon; fn apply(&self, h:Horizon) -> Horizon { Horizon{p2:kernels::sw_0_12(&kernels::sw_0_12_terms(&self.0), &Lanes::splat(0.0), &h.p2).1} } }
+
+/// Reflect the motor $m$ through this plane $p$, the expression $p m p$. The
+/// mirror image turns the other way around the reflected axis.
+impl Transform<Motor> for Plane { type Output = Motor; fn apply(&self, m:Motor)->Motor { *self * m * *self } }
+
+/// Reflect the rotor $r$ through this plane $p$, the result only fixes the
+/// origin if $p$ passes through it.
+impl Transform<Rotor> for Plane { type Output = Motor; fn apply(&self, r:Rotor)->Motor { *self * r * *self } }
+
+/// Reflect the translator $t$ through this plane $p$, the translation is
+/// mirrored in the direction of the plane.
+impl Transform<Translator> for Plane { type Output = Translator; fn apply(&self, t:Translator)->Translator { Translator{p2: self.apply(Horizon{p2: t.p2}).p2} } }
 
-impl Add<f32> for Plane {type Output = Plane;fn add(self, s: f32) -> Plane { self+plane(s,s,s,s)}}
-impl Add<i32> for Plane {type Output = Plane;fn add(self, s: i32) -> Plane { self+plane(s as f32,s as f32,s as f32,s as f32)}}
+/// Reflect the flector $f$ through this plane $p$, the expression $p f p$. A
+/// rotoreflection stays one about the reflected axis.
+impl Transform<Flector> for Plane { type Output = Flector; fn apply(&self, f:Flector)->Flector { Flector::new(self.apply(f.plane()), self.apply(f.point())) } }
+
+call_transform!(Plane: Plane, Point, Origin, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector);
+
+impl Add<Float> for Plane {type Output = Plane;fn add(self, s: Float) -> Plane { self+plane(s,s,s,s)}}
+impl Add<i32> for Plane {type Output = Plane;fn add(self, s: i32) -> Plane { self+plane(s as Float,s as Float,s as Float,s as Float)}}
 
 impl Add<Plane> for Plane {type Output = Plane;fn add(self, p: Plane) -> Plane { Plane(self.0+p.0)}}
 impl AddAssign for Plane {fn add_assign(&mut self, p: Self) { self.0 += p.0}}
 impl Sub<Plane> for Plane {type Output = Plane;fn sub(self, p: Plane) -> Plane { Plane(self.0-p.0)}}
 impl SubAssign for Plane {fn sub_assign(&mut self, p: Self) { self.0 -= p.0}}
-impl Mul<f32> for Plane {type Output = Plane;fn mul(self, s: f32) -> Plane { Plane(self.0*f32x4::splat(s))}}
-impl Mul<i32> for Plane {type Output = Plane;fn mul(self, s: i32) -> Plane { Plane(self.0*f32x4::splat(s as f32))}}
-impl Mul<Plane> for f32 {type Output = Plane;fn mul(self, p: Plane) -> Plane { p*self }}
+impl Mul<Float> for Plane {type Output = Plane;fn mul(self, s: Float) -> Plane { Plane(self.0*Lanes::splat(s))}}
+impl Mul<i32> for Plane {type Output = Plane;fn mul(self, s: i32) -> Plane { Plane(self.0*Lanes::splat(s as Float))}}
+impl Mul<Plane> for Float {type Output = Plane;fn mul(self, p: Plane) -> Plane { p*self }}
 impl Mul<Plane> for i32 {type Output = Plane;fn mul(self, p: Plane) -> Plane { p*self }}
-impl MulAssign<f32> for Plane {fn mul_assign(&mut self, s: f32) { self.0 *= f32x4::splat(s)}}
-impl Div<f32> for Plane {type Output = Plane;fn div(self, s: f32) -> Plane { Plane(self.0/f32x4::splat(s))}}
-impl DivAssign<f32> for Plane {fn div_assign(&mut self, s: f32) { self.0 /= f32x4::splat(s)}}
+impl MulAssign<Float> for Plane {fn mul_assign(&mut self, s: Float) { self.0 *= Lanes::splat(s)}}
+impl Div<Float> for Plane {type Output = Plane;fn div(self, s: Float) -> Plane { Plane(self.0/Lanes::splat(s))}}
+impl DivAssign<Float> for Plane {fn div_assign(&mut self, s: Float) { self.0 /= Lanes::splat(s)}}
 /// Unary minus (leaves displacement from origin untouched, changing orientation only)
 impl Neg for Plane {type Output = Self;fn neg(self)->Self::Output { Plane(flip_signs(&self.0, [false,true,true,true].into()) ) } }
-/// Geometric Product *
-impl Mul<Point> for Plane { type Output = Motor;fn mul(self, a: Point) -> Motor { let (p1,p2) = gp03(&self.0,&a.0);Motor{p1,p2} } }
-impl Mul<Plane> for Plane { type Output = Motor;fn mul(self, p: Plane) -> Motor { let (p1,p2) = gp00(&self.0,&p.0);Motor{p1,p2} } }
-impl Div<Plane> for Plane {type Output = Motor;fn div(self, p: Plane) -> Motor {self * p.inverse()}}
-/// Inner Product |
-impl BitOr<Plane> for Plane {type Output = f32;fn bitor(self, p:Plane) -> f32 { dot00(&self.0,&p.0)[0]}}
-impl BitOr<Line> for Plane {type Output = Plane;fn bitor(self, l:Line) -> Plane {let p0 = dotpl(&self.0,&l.p1,&l.p2);Plane(p0)}}
-impl BitOr<Horizon> for Plane {type Output = Plane;fn bitor(self, l: Horizon) -> Plane {let p0 = dotpil(&self.0,&l.p2);Plane(p0)}}
-impl BitOr<Point> for Plane {type Output = Line;fn bitor(self, a:Point) -> Line { let (p1,p2) = dot03(&self.0,&a.0);Line{p1,p2}} }
-/// Meet Operator, Exterior Product, ^
-impl BitXor<Plane> for Plane {type Output = Line;fn bitxor(self, p:Plane) -> Line {let (p1,p2) = ext00(&self.0,&p.0);Line{p1,p2}} }
-impl BitXor<Line> for Plane {type Output = Point;fn bitxor(self, l:Line) -> Point {Point(extpb(&self.0,&l.p1)+ext02(&self.0,&l.p2))}}
-impl BitXor<Horizon> for Plane {type Output = Point;fn bitxor(self, l: Horizon) -> Point { Point(ext02(&self.0, &l.p2)) }}
-impl BitXor<Branch> for Plane {type Output = Point;fn bitxor(self, b:Branch) -> Point {Point(extpb(&self.0, &b.0)) }}
-/// (a0 b0 + a1 b1 + a2 b2 + a3 b3) e0123
-impl BitXor<Point> for Plane {type Output = Dual;fn bitxor(self, p:Point) -> Dual{Dual::new(0.0, dp(&self.0,&p.0)[0])}}
 impl BitAnd<Point> for Plane {type Output = Dual;fn bitand(self, p: Point) -> Dual {!(!self ^ !p)}}
 impl Not for Plane {type Output = Point;fn not(self)->Point{Point(self.0)}}
 
-// TODO this is not in klein
-// impl Div<Point> for Plane {
-//   type Output = Motor;
-//   fn div(self, a: Point) -> Motor {
-//     self * a.inverse()
-//   }
-// }
-
-// plane * plane
-fn gp00(a:&f32x4, b:&f32x4)->(f32x4,f32x4) {
-  // (a1b1 + a2b2 + a3b3) + (a2b3 - a3b2)e23 + (a3b1 - a1b3)e31 + (a1b2 - a2b1)e12 +
-  // (a0b1 - a1b0)e01 + (a0b2 - a2b0)e02 + (a0b3 - a3b0)e03
-  let mut p1_out = a.yzwy() * b.ywyz();
-  p1_out = p1_out - (f32x4_xor(&[-0.0, 0.0, 0.0, 0.0].into(), &(a.zwyz() * b.zzwy())));
-  // Add a3 b3 to the lowest component
-  p1_out = add_ss(&p1_out, &(a.wxxx() * b.wxxx()));
-  // (a0 b0, a0 b1, a0 b2, a0 b3)
-  let mut p2_out = a.xxxx() * b;
-  // Sub (a0 b0, a1 b0, a2 b0, a3 b0)
-  // Note that the lowest component cancels
-  p2_out = p2_out - a * b.xxxx();
-  return (p1_out, p2_out);
-}
-
-// a1 b1 + a2 b2 + a3 b3
-fn dot00(a:&f32x4, b:&f32x4)->f32x4 {
-  hi_dp(a,b)
-}
-
-// (a2b1 - a1b2)e03 + (a3b2 - a2b3)e01 + (a1b3 - a3b1)e02 + a1b0e23 + a2b0e31 + a3b0e12
-fn dot03(a:&f32x4, b:&f32x4)->(f32x4,f32x4) {
-  let mut p1_out = a * b.xxxx();
-  p1_out = zero_first(p1_out);
-  (p1_out, (a.xzwy()*b - a*b.xzwy()).xzwy())
-}
-
-fn dotpl(a:&f32x4, b:&f32x4, c:&f32x4)->f32x4 {
-  let mut p0 = a.xzwy() * b;
-  p0 -= a * b.xzwy();
-  sub_ss(&(p0.xzwy()), hi_dp_ss(a, c))
-}
-
-fn dotpil(a:&f32x4, c:&f32x4)->f32x4 {
-  f32x4_xor(&hi_dp(a, c), &[-0.0, 0.0, 0.0, 0.0].into())
-}
-
-// (a1 b2 - a2 b1) e12 + (a2 b3 - a3 b2) e23 + (a3 b1 - a1 b3) e31 +
-// (a0 b1 - a1 b0) e01 + (a0 b2 - a2 b0) e02 + (a0 b3 - a3 b0) e03
-fn ext00(a:&f32x4, b:&f32x4)->(f32x4,f32x4) {
-  // For both outputs above, we don't zero the lowest component because
-  // we've arranged a cancelation TODO wdym???
-  ((a * b.xzwy() - a.xzwy() * b).xzwy(),
-   a.xxxx() * b - a * b.xxxx())
-}
-
-// p0 ^ p2 = p2 ^ p0
-fn ext02(a:&f32x4, b:&f32x4)->f32x4 {
-  // (a1 b2 - a2 b1) e021 + (a2 b3 - a3 b2) e032 + (a3 b1 - a1 b3) e013 +
-  (a * b.xzwy() - a.xzwy() * b).xzwy()
-}
-
-fn extpb(a:&f32x4, b:&f32x4)->f32x4 {
-  // (a1 b1 + a2 b2 + a3 b3) e123 + (-a0 b1) e032 + (-a0 b2) e013 + (-a0 b3) e021
-  let p3_out = &flip_signs(&(a.yxxx() * b), mask32x4::from_array([false,true,true,true]));
-  return add_ss(p3_out, &hi_dp(a,b));
-}
-
-// reflect point through plane
-fn sw30(a:&f32x4, b:&f32x4) ->f32x4 {
-  //                                b0(a1^2 + a2^2 + a3^2)  e123 +
-  // (-2a1(a0 b0 + a3 b3 + a2 b2) + b1(a2^2 + a3^2 - a1^2)) e032 +
-  // (-2a2(a0 b0 + a1 b1 + a3 b3) + b2(a3^2 + a1^2 - a2^2)) e013 +
-  // (-2a3(a0 b0 + a2 b2 + a1 b1) + b3(a1^2 + a2^2 - a3^2)) e021
-
-  let a_zwyz = a.zwyz(); // a2, a3, a1, a2
-  let a_yzwy = a.yzwy(); // a1, a2, a3, a1
-  let a_wyzw = a.wyzw(); // a3, a1, a2, a3
-
-  //     a0 b0              |      a0 b0              |      a0 b0              |      a0 b0
-  let mut p3_out = a.xxxx() * b.xxxx();
-  //     a0 b0+a2 b0        |      a0 b0+a3 b3        |      a0 b0+a1 b1        |      a0 b0+a3 b2
-  p3_out += a_zwyz * b.xwyz();
-  //     a0 b0+a2 b0+a1 b0  |      a0 b0+a3 b3+a2 b2  |      a0 b0+a1 b0+a3 b3  |      a0 b0+a3 b2+a1 b1
-  p3_out += a_yzwy * b.xzwy();
-  // 0b0(a0 b0+a2 b0+a1 b0) | -2a1(a0 b0+a3 b3+a2 b2) | -2a2(a0 b0+a1 b0+a3 b3) | -2a3(a0 b0+a3 b2+a1 b1)
-  p3_out *= a * f32x4::from_array([0.0,-2.0,-2.0,-2.0]);
-  //                        | -2a1(a0 b0+a3 b3+a2 b2) | -2a2(a0 b0+a1 b0+a3 b3) | -2a3(a0 b0+a3 b2+a1 b1)
-
-  // a1^2           | a2^2           | a3^2           | a1^2
-  let mut tmp = a_yzwy * a_yzwy;
-  // a1^2+a2^2      | a2^2+a3^2      | a3^2+a1^2      | a1^2+a2^2
-  tmp += a_zwyz * a_zwyz;
-  // a1^2+a2^2+a3^2 | a2^2+a3^2-a1^2 | a3^2+a1^2-a2^2 | a1^2+a2^2-a3^2
-  tmp -= f32x4_xor(&(a_wyzw * a_wyzw), &f32x4::from_array([-0.0,0.0,0.0,0.0]));
-
-  p3_out = p3_out + b * tmp;
-
-  p3_out
-}
-
-// Reflect a plane through another plane
-// b * a * b
-fn sw00(a:&f32x4,b:&f32x4)->f32x4 {
-  // (2a0(a2 b2 + a3 b3 + a1 b1) - b0(a1^2 + a2^2 + a3^2)) e0 +
-  // (2a1(a2 b2 + a3 b3)         + b1(a1^2 - a2^2 - a3^2)) e1 +
-  // (2a2(a3 b3 + a1 b1)         + b2(a2^2 - a3^2 - a1^2)) e2 +
-  // (2a3(a1 b1 + a2 b2)         + b3(a3^2 - a1^2 - a2^2)) e3
-  let a_zzwy = a.zzwy();
-  let a_wwyz = a.wwyz();
-
-  // Left block
-  let mut tmp = a_zzwy * b.zzwy();
-  tmp += a_wwyz * b.wwyz();
-
-  let a1 = &a.yyww();
-  let b1 = &b.yyww();
-  tmp = add_ss(&tmp, &mul_ss(a1, b1));
-  tmp *= a + a;
-
-  // Right block
-  let a_yyzw = &a.yyzw();
-  let mut tmp2 = f32x4_xor(&(a_yyzw * a_yyzw), &[-0.0, 0.0, 0.0, 0.0].into());
-  tmp2 -= a_zzwy * a_zzwy;
-  tmp2 -= a_wwyz * a_wwyz;
-  tmp2 *= b;
-
-  tmp + tmp2
-}
-
-fn sw10(a:&f32x4,b:&f32x4)->(f32x4,f32x4) {
-  //                       b0(a1^2 + a2^2 + a3^2) +
-  // (2a3(a1 b1 + a2 b2) + b3(a3^2 - a1^2 - a2^2)) e12 +
-  // (2a1(a2 b2 + a3 b3) + b1(a1^2 - a2^2 - a3^2)) e23 +
-  // (2a2(a3 b3 + a1 b1) + b2(a2^2 - a3^2 - a1^2)) e31 +
-  //
-  // 2a0(a1 b2 - a2 b1) e03
-  // 2a0(a2 b3 - a3 b2) e01 +
-  // 2a0(a3 b1 - a1 b3) e02
-
-  let a_zyzw = &a.zyzw();
-  let a_ywyz = &a.ywyz();
-  let a_wzwy = &a.wzwy();
-  let b_xzwy = &b.xzwy();
-
-  let two_zero:f32x4 = [0.0, 2.0, 2.0, 2.0].into(); // TODO is this right?
-  let mut p1 = a * b;
-  p1 += a_wzwy * b_xzwy;
-  p1 *= a_ywyz * two_zero;
-
-  let mut tmp = a_zyzw * a_zyzw;
-  tmp += a_wzwy * a_wzwy;
-  tmp = f32x4_xor(&tmp, &[-0.0, 0.0, 0.0, 0.0].into());
-  tmp = (a_ywyz * a_ywyz) - tmp;
-  tmp = b.xwyz() * tmp;
-
-  let p1 = (p1 + tmp).xzwy();
-
-  let mut p2 = a_zyzw * b_xzwy;
-  p2 = p2 - a_wzwy * b;
-  p2 = p2 * a.xxxx() * two_zero;
-  p2 = p2.xzwy();
-
-  (p1,p2)
-}
-
-fn sw20(a:&f32x4,b:&f32x4)->f32x4 {
-  //                       -b0(a1^2 + a2^2 + a3^2) e0123 +
-  // (-2a3(a1 b1 + a2 b2) + b3(a1^2 + a2^2 - a3^2)) e03
-  // (-2a1(a2 b2 + a3 b3) + b1(a2^2 + a3^2 - a1^2)) e01 +
-  // (-2a2(a3 b3 + a1 b1) + b2(a3^2 + a1^2 - a2^2)) e02 +
-  let a_zzwy = a.zzwy();
-  let a_wwyz = a.wwyz();
-
-  let mut p2 = a * b;
-  p2 += a_zzwy * b.xzwy();
-  p2 *= a_wwyz * &[0.0, -2.0, -2.0, -2.0].into();
-
-  let a_yyzw = a.yyzw();
-  let mut tmp = a_yyzw * a_yyzw;
-  tmp = f32x4_xor(&[-0.0, 0.0, 0.0, 0.0].into(), &(tmp + a_zzwy * a_zzwy));
-  tmp -= a_wwyz * a_wwyz;
-  p2 += tmp * b.xwyz();
-  p2.xzwy()
+products! {
+  Plane * Plane = Motor, gp_0_0(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Plane * Point = Motor, gp_0_3(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Plane * Line = Flector, gp_0_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Plane * Branch = Flector, gp_0_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Plane * Horizon = Flector, gp_0_2(0; p2) -> (p0, p3) => Flector{p0, p3};
+  Plane * Direction = Motor, gp_0_3(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Plane * Dual as Motor = Flector, gp_0_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Plane ^ Plane = Line, ext_0_0(0; 0) -> (p1, p2) => Line{p1, p2};
+  Plane ^ Line = Point, ext_0_12(0; p1, p2) -> (_, p3) => Point(p3);
+  Plane ^ Branch = Point, ext_0_1(0; 0) -> (_, p3) => Point(p3);
+  Plane ^ Horizon = Point, ext_0_2(0; p2) -> p3 => Point(p3);
+  Plane ^ Point = Dual, ext_0_3(0; 0) -> p2 => Dual::new(0.0, p2[0]);
+  Plane ^ Direction = Dual, ext_0_3(0; 0) -> p2 => Dual::new(0.0, p2[0]);
+  Plane ^ Dual as Motor = Plane, ext_0_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Plane ^ Rotor = Flector, ext_0_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Plane ^ Translator as Motor = Flector, ext_0_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Plane ^ Motor = Flector, ext_0_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Plane & Direction = Dual, reg_0_3(0; 0) -> p1 => Dual::new(p1[0], 0.0);
+  Plane & Dual as Motor = Plane, reg_0_12(0; p1, p2) -> p0 => Plane(p0);
+  Plane & Motor = Plane, reg_0_12(0; p1, p2) -> p0 => Plane(p0);
+  Plane | Plane = Float, dot_0_0(0; 0) -> p1 => p1[0];
+  Plane | Line = Plane, dot_0_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Plane | Branch = Plane, dot_0_1(0; 0) -> p0 => Plane(p0);
+  Plane | Horizon = Plane, dot_0_2(0; p2) -> (p0, _) => Plane(p0);
+  Plane | Point = Line, dot_0_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Plane | Direction = Line, dot_0_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Plane | Dual as Motor = Flector, dot_0_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Plane | Rotor = Plane, dot_0_1(0; 0) -> p0 => Plane(p0);
+  Plane | Translator as Motor = Plane, dot_0_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Plane | Motor = Flector, dot_0_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Plane / Plane = Motor;
+  Plane / Point = Motor;
+  Plane / Line = Flector;
+  Plane / Branch = Flector;
+  Plane / Dual = Flector;
+  Plane / Rotor = Flector;
+  Plane / Translator = Flector;
+  Plane / Motor = Flector;
 }
 
 #[cfg(test)]
@@ -328,13 +210,13 @@ mod tests {
     let a  = plane(1.0, 1.0, 1.0, 1.0);
     let b = plane(0.9, 0.9, 0.9, 0.9);
     let c = plane(0.8, 0.8, 0.8, 0.8);
-    assert_eq!(a.approx_eq(b, 0.1001), true, "{:?} eq {:?} approx 0.1", a.0, b.0);
-    assert_eq!(a.approx_eq(b, 0.099), false, "{:?} eq {:?} approx 0.11", a.0, b.0);
-    assert_eq!(a.approx_eq(c, 0.1), false, "{:?} eq {:?} approx 0.09", a.0, c.0);
-    assert_eq!(a.approx_eq(c, 0.2), true, "{:?} eq {:?} approx 0.1", a.0, c.0);
+    assert!(a.approx_eq(b, 0.1001), "{:?} eq {:?} approx 0.1", a.0, b.0);
+    assert!(!a.approx_eq(b, 0.099), "{:?} eq {:?} approx 0.11", a.0, b.0);
+    assert!(!a.approx_eq(c, 0.1), "{:?} eq {:?} approx 0.09", a.0, c.0);
+    assert!(a.approx_eq(c, 0.2), "{:?} eq {:?} approx 0.1", a.0, c.0);
     let a1  = plane(1.0, 2.0, 3.0, 4.0);
     let b1 = plane(0.9, 2.0, 3.0, 4.0);
-    assert_eq!(a1.approx_eq(b1, 0.1001), true, "{:?} eq {:?} approx 0.1", a1.0, b1.0);
+    assert!(a1.approx_eq(b1, 0.1001), "{:?} eq {:?} approx 0.1", a1.0, b1.0);
   }
   #[test] fn plane_getters() { assert_eq!([e1.e1(),e2.e2(),e3.e3(),e0.e0()], [1.0,1.0,1.0,1.0]) }
   #[test] fn plane_abcd() { assert_eq!([e1.a(),e2.b(),e3.c(),e0.d()], [1.0,1.0,1.0,1.0]) }
@@ -364,7 +246,13 @@ mod tests {
     let p = plane(1.0,2.0,3.0,4.0);
     assert_plane(-p, -1.0,-2.0,-3.0,4.0);
   }
-  #[test] #[ignore] fn plane_normalized() {}
+  #[test] fn plane_normalized() {
+    assert_plane(plane(0.0,2.0,0.0,-4.0).normalized(), 0.0,1.0,0.0,-2.0);
+    // Points on the plane stay on it, with e0 left as it was they would not
+    let p = plane(3.0,0.0,4.0,10.0).normalized();
+    assert!((p.norm() - 1.0).abs() < EPSILON && (p.d() - 2.0).abs() < EPSILON);
+    assert!((p ^ point(-2.0,5.0,-1.0)).e0123().abs() < EPSILON);
+  }
   #[test] #[ignore] fn plane_invserse() {}
   #[test] #[ignore] fn plane_reverse() {}
   #[test] fn plane_not() {
@@ -379,7 +267,7 @@ mod tests {
     approx_eq1(p2 | p2, 1.0);
   }
 
-  fn assert_plane(p:Plane,x:f32,y:f32,z:f32,d:f32) {
+  fn assert_plane(p:Plane,x:Float,y:Float,z:Float,d:Float) {
     assert_eq!(p.a(), x);
     assert_eq!(p.b(), y);
     assert_eq!(p.c(), z);
@@ -415,8 +303,8 @@ mod tests {
     assert_eq!([d.scalar(), d.e0123()], [0.0, 16.0]);
   }
 
-  const EPSILON: f32 = 0.02;
-  fn approx_eq1(a: f32, b: f32) {
+  const EPSILON: Float = 0.02;
+  fn approx_eq1(a: Float, b: Float) {
     assert!((a - b).abs() < EPSILON, "{:?} ≉ {:?}", a, b);
   }
 
@@ -430,7 +318,7 @@ mod tests {
     let a = point(2.0, 0.0, 0.0);
     let p = plane(1.0, -1.0, 0.0, 0.0).normalized();
     // Distance from point p1 to plane p2
-    let root_two = 2f32.sqrt();
+    let root_two = Float::sqrt(2.0);
     approx_eq1((a & p).scalar().abs(), root_two);
     approx_eq1((a ^ p).e0123().abs(), root_two);
   }
@@ -438,14 +326,14 @@ mod tests {
   #[test] fn reflect_pane() {
     let p1 = plane(3.0, 2.0, 1.0, -1.0);
     let p2 = plane(1.0, 2.0, -1.0, -3.0);
-    let p3 = p1(p2);
+    let p3 = p1.apply(p2);
     assert_eq!([p3.e0(), p3.e1(), p3.e2(), p3.e3()], [30.0, 22.0, -4.0, 26.0]);
   }
 
   #[test] fn reflect_line() {
     let p1 = plane(3.0, 2.0, 1.0, -1.0);
     let l1 = line(1.0, -2.0, 3.0, 6.0, 5.0, -4.0);
-    let l2 = p1(l1);
+    let l2 = p1.apply(l1);
     assert_eq!([l2.e01(), l2.e02(), l2.e03(), l2.e12(), l2.e31(), l2.e23()],
                [28.0, -72.0, 32.0, 104.0, 26.0, 60.0]);
   }
@@ -453,7 +341,7 @@ mod tests {
   #[test] fn reflect_point() {
     let p = plane(3.0, 2.0, 1.0, -1.0);
     let a = point(4.0, -2.0, -1.0);
-    let b = p(a);
+    let b = p.apply(a);
     assert_eq!([b.e021(), b.e013(), b.e032(), b.e123()], [-26.0, -52.0, 20.0, 14.0]);
   }
 }
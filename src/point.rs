@@ -1,5 +1,5 @@
-use std::{fmt::{Display, Formatter, Result},simd::{f32x4,mask32x4,simd_swizzle,SimdFloat},mem::transmute,ops::{Add,AddAssign,Sub,SubAssign,Mul,MulAssign,Div,DivAssign,BitAnd,BitOr,BitXor,Not,Neg}};
-use crate::{*,maths::*};
+use std::{ops::{Add,AddAssign,Sub,SubAssign,Mul,MulAssign,Div,DivAssign,BitAnd,BitOr,Not,Neg}};
+use super::{*,maths::*};
 
 /// e₀₃₂ + e₁₂₃
 pub const X:Point = point(1.0, 0.0, 0.0);
@@ -11,119 +11,178 @@ pub const Z:Point = point(0.0, 0.0, 1.0);
 pub const O:Point = point(0.0, 0.0, 0.0);
 /// Origin
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Origin {} impl Into<Point> for Origin { fn into(self)->Point { Point::new(0.0,0.0,0.0) } }
+pub struct Origin {} impl From<Origin> for Point { fn from(_val: Origin)->Self { Point::new(0.0,0.0,0.0) } }
 
 /// xe₀₃₂ + ye₀₁₃ + ze₀₂₁ + e₁₂₃
-pub const fn point(x:f32,y:f32,z:f32)->Point { Point::new(x,y,z) }
+pub const fn point(x:Float,y:Float,z:Float)->Point { Point::new(x,y,z) }
 
 /// xe₀₃₂ + ye₀₁₃ + ze₀₂₁ + e₁₂₃
-#[cfg_attr(feature = "bytemuck", repr(C), derive(bytemuck::Pod, bytemuck::Zeroable))]
+///
+/// Laid out as four floats `[w, x, y, z]`, the weight e₁₂₃ first, so a
+/// `&[Point]` can be cast into a vertex buffer and read as `.yzwx` in a shader.
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable))]
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Point (pub(crate) f32x4);
+pub struct Point<L = Lanes>(pub(crate) L);
+pod!(Point);
+
+serde_blades!(Point { x, y, z, w } => |[x, y, z, w]| Point(Lanes::from_array([w, x, y, z])));
 
 impl Point {
   /// Component-wise constructor where homogeneous coordinate is automatically initialized to 1.
-  pub const fn new(x:f32,y:f32,z:f32)->Self{ Point(f32x4::from_array([1.0,x,y,z])) }
+  pub const fn new(x:Float,y:Float,z:Float)->Self{ Point(Lanes::from_array([1.0,x,y,z])) }
   /// x/w + y/w + z/w + w/w
   pub fn normalized(&self)->Self{Self(&self.0 * rcp_nr1(&self.0.xxxx()))}
-  /// x(1/w) + y(1/w) + z(1/w) + w(1/w)
-  pub fn inverse(&self)->Self { let inv_norm = &rcp_nr1(&(self.0.xxxx()));Self(inv_norm * inv_norm * &self.0) }
-  pub fn reverse(&self)->Point { Point(flip_signs(&self.0, mask32x4::from_array([false,true,true,true]))) }
+  /// $P^{-1} = -P/w^2$, a point squares to $-w^2$.
+  pub fn inverse(&self)->Self { let inv_norm = &rcp_nr1(&(self.0.xxxx()));Self(inv_norm * inv_norm * &-self.0) }
+  pub fn reverse(&self)->Point { Point(flip_signs(&self.0, Mask::from_array([false,true,true,true]))) }
   /// Project a point onto a line
   pub fn project_line(self, l:Line)->Point { (self | l) ^ l }
   /// Project a point onto a plane
   pub fn project_plane(self, p:Plane)->Point { (self | p) ^ p }
-  pub fn approx_eq(&self, other:Point, epsilon:f32)->bool {(&self.0 - other.0).abs() < f32x4::splat(epsilon)}
-  #[inline] pub fn x(&self)->f32 { self.0[1] }
-  #[inline] pub fn y(&self)->f32 { self.0[2] }
-  #[inline] pub fn z(&self)->f32 { self.0[3] }
-  #[inline] pub fn w(&self)->f32 { self.0[0] }
-  #[inline] pub fn e032(&self)->f32 { self.x() }
-  #[inline] pub fn e013(&self)->f32 { self.y() }
-  #[inline] pub fn e021(&self)->f32 { self.z() }
-  #[inline] pub fn e123(&self)->f32 { self.w() }
+  pub fn approx_eq(&self, other:Point, epsilon:Float)->bool {lanes_close(&self.0, &other.0, epsilon)}
+  #[inline] pub fn x(&self)->Float { self.0[1] }
+  #[inline] pub fn y(&self)->Float { self.0[2] }
+  #[inline] pub fn z(&self)->Float { self.0[3] }
+  #[inline] pub fn w(&self)->Float { self.0[0] }
+  #[inline] pub fn e032(&self)->Float { self.x() }
+  #[inline] pub fn e013(&self)->Float { self.y() }
+  #[inline] pub fn e021(&self)->Float { self.z() }
+  #[inline] pub fn e123(&self)->Float { self.w() }
 }
 impl GeometricProduct for Point {} impl JoinProduct for Point {} impl MeetProduct for Point {}
 /// Dual operator
 impl Not for Point {type Output = Plane;fn not(self)->Plane {Plane(self.0)}}
 /// Unary minus (leaves homogeneous coordinate untouched)
-impl Neg for Point {type Output = Self;fn neg(self)->Point{Point(self.0 * <f32x4>::from([1.0, -1.0, -1.0, -1.0]))}}
+impl Neg for Point {type Output = Self;fn neg(self)->Point{Point(self.0 * <Lanes>::from([1.0, -1.0, -1.0, -1.0]))}}
 impl Add for Point {type Output=Self;fn add(self,p:Self)->Self{Self(self.0+p.0)}}
-impl Add<f32> for Point {type Output = Point;fn add(self, s: f32) -> Point { self+point(s,s,s)}}
-impl Add<i32> for Point {type Output = Point;fn add(self, s: i32) -> Point { self+point(s as f32,s as f32,s as f32)}}
+impl Add<Float> for Point {type Output = Point;fn add(self, s: Float) -> Point { self+point(s,s,s)}}
+impl Add<i32> for Point {type Output = Point;fn add(self, s: i32) -> Point { self+point(s as Float,s as Float,s as Float)}}
 impl Sub for Point {type Output=Self;fn sub(self,p:Self)->Self{Self(self.0-p.0)}}
 impl AddAssign for Point {fn add_assign(&mut self,p:Self){self.0+=p.0}}
 impl SubAssign for Point {fn sub_assign(&mut self,p:Self){self.0-=p.0}}
 /// Point uniform scale
-impl Mul<f32> for Point {type Output=Self;fn mul(self, s:f32) -> Self { Point(self.0*f32x4::splat(s)) } }
-impl Mul<i32> for Point {type Output=Self;fn mul(self, s:i32) -> Self { self * s as f32 } }
+impl Mul<Float> for Point {type Output=Self;fn mul(self, s:Float) -> Self { Point(self.0*Lanes::splat(s)) } }
+impl Mul<i32> for Point {type Output=Self;fn mul(self, s:i32) -> Self { self * s as Float } }
 /// Point uniform inverse scale
-impl Div<f32> for Point {type Output=Self;fn div(self, s:f32) -> Self { Point(self.0 / f32x4::splat(s)) } }
-impl MulAssign<f32> for Point {fn mul_assign(&mut self, s: f32) { self.0 = self.0 * f32x4::splat(s) }}
-impl DivAssign<f32> for Point {fn div_assign(&mut self, s: f32) { self.0 = self.0 / f32x4::splat(s) }}
-impl Mul<Point> for i32 {type Output=Point;fn mul(self, p:Point) -> Point { p*self as f32 } }
-impl Mul<Point> for f32 {type Output=Point;fn mul(self, p:Point) -> Point { p*self } }
-impl Mul<Point> for Point {type Output=Translator;fn mul(self,p:Point)->Translator{Translator{p2:gp33(&self.0, &p.0)}}}
-impl Mul<Plane> for Point {type Output=Motor;fn mul(self,p:Plane)->Motor{let(p1,p2)=gp30(&p.0,&self.0);Motor{p1,p2}}}
-impl Div<Point> for Point {type Output=Translator;fn div(self,p:Point)->Translator{self*p.inverse()}}
+impl Div<Float> for Point {type Output=Self;fn div(self, s:Float) -> Self { Point(self.0 / Lanes::splat(s)) } }
+impl MulAssign<Float> for Point {fn mul_assign(&mut self, s: Float) { self.0 = self.0 * Lanes::splat(s) }}
+impl DivAssign<Float> for Point {fn div_assign(&mut self, s: Float) { self.0 = self.0 / Lanes::splat(s) }}
+impl Mul<Point> for i32 {type Output=Point;fn mul(self, p:Point) -> Point { p*self as Float } }
+impl Mul<Point> for Float {type Output=Point;fn mul(self, p:Point) -> Point { p*self } }
+impl Mul<Point> for Point {type Output=Translator;fn mul(self,p:Point)->Translator{
+  // Divided by the scalar -w₁w₂, which leaves the ideal part.
+  let (p1, p2) = kernels::gp_3_3(&self.0, &p.0);
+  Translator{p2: zero_first(p2 / p1.xxxx())}
+}}
 impl BitOr<Plane> for Point {type Output=Line;fn bitor(self, p:Plane) -> Line { p | self }}
-impl BitOr<Line> for Point {type Output=Plane;fn bitor(self, l:Line) -> Plane { Plane(dotptl(&self.0,&l.p1)) }}
-/// a|b = -a0 b0
-impl BitOr<Point> for Point {type Output=f32;fn bitor(self, a:Point) -> f32 {let out = dot33(&self.0,&a.0);out[0]}}
-/// a^b = (a0 b0 + a1 b1 + a2 b2 + a3 b3) e0123
-impl BitXor<Plane> for Point {type Output=Dual;fn bitxor(self, p:Plane) -> Dual {let out = -dp(&p.0,&self.0);Dual::new(0.0,out[0])} }
 impl BitAnd<Point> for Point {type Output=Line;fn bitand(self, p: Point) -> Line { !(!self ^ (!p))}}
 impl BitAnd<Line> for Point {type Output=Plane;fn bitand(self, l: Line) -> Plane { !(!self ^ !l)}}
 impl BitAnd<Horizon> for Point {type Output=Plane;fn bitand(self, l: Horizon) -> Plane { !(!self ^ !l)}}
 impl BitAnd<Branch> for Point {type Output=Plane;fn bitand(self, b: Branch) -> Plane { !(!self ^ !b)}}
 impl BitAnd<Plane> for Point {type Output=Dual;fn bitand(self, p: Plane)->Dual { !(!self ^ !p)}}
-impl Display for Point { fn fmt(&self, f: &mut Formatter<'_>) -> Result { write!(f, "(x:{}, y:{}, z:{}, w:{})", self.x(), self.y(), self.z(), self.w()) } }
 /// Convert point to an array
-impl From<Point> for [f32;3] { fn from(p:Point) -> Self {[p.x(), p.y(), p.z()]} }
+impl From<Point> for [Float;3] { fn from(p:Point) -> Self {[p.x(), p.y(), p.z()]} }
 /// Convert array to a point
-impl From<[f32;3]> for Point { fn from([x,y,z]:[f32;3]) -> Self {point(x, y, z)} }
+impl From<[Float;3]> for Point { fn from([x,y,z]:[Float;3]) -> Self {point(x, y, z)} }
 /// Convert point to a tuple
-impl From<Point> for (f32,f32,f32) { fn from(p:Point) -> Self {(p.x(), p.y(), p.z())} }
+impl From<Point> for (Float,Float,Float) { fn from(p:Point) -> Self {(p.x(), p.y(), p.z())} }
 /// Convert tuple to a point
-impl From<(f32,f32,f32)> for Point { fn from((x,y,z):(f32,f32,f32)) -> Self {point(x, y, z)} }
+impl From<(Float,Float,Float)> for Point { fn from((x,y,z):(Float,Float,Float)) -> Self {point(x, y, z)} }
+/// The ideal point of a direction.
+impl From<Direction> for Point { fn from(d:Direction) -> Self {Point(zero_first(d.0))} }
 /// Returns `&[x,y,z,w]`
-impl From<&Point> for [f32;4] { #[inline(always)] fn from(v: &Point) -> Self { unsafe { transmute::<f32x4,[f32;4]>(simd_swizzle!(v.0, [1,2,3,0])) } } }
+impl From<&Point> for [Float;4] { #[inline(always)] fn from(v: &Point) -> Self { v.0.yzwx().to_array() } }
 /// Returns `[x,y,z,w]`
-impl From<Point> for [f32;4] { #[inline(always)] fn from(v: Point) -> Self { unsafe { transmute::<f32x4,[f32;4]>(simd_swizzle!(v.0, [1,2,3,0])) } } }
-#[cfg(feature = "mint")] impl From<mint::Point2<f32>> for Point { #[inline] fn from(v: mint::Point2<f32>)->Point { Self::new(v.x,v.y,0.0) } }
-#[cfg(feature = "mint")] impl From<Point> for mint::Point2<f32> { #[inline] fn from(v: Point) -> Self { Self { x: v.x(), y: v.y() } } }
-#[cfg(feature = "mint")] impl From<mint::Point3<f32>> for Point { #[inline] fn from(v: mint::Point3<f32>)->Point { Self::new(v.x,v.y,v.z) } }
-#[cfg(feature = "mint")] impl From<Point> for mint::Point3<f32> { #[inline] fn from(v: Point) -> Self { Self { x: v.x(), y: v.y(), z: v.z() } } }
-
-// TODO Does not exist in klein?
-// impl Div<Plane> for Point { type Output = Motor;fn div(self, p: Plane) -> Motor { other.invert();self * other } }
-//Do not exist because
-// impl BitXor<Line> for Point { type Output = Point;fn bitxor(self, l:Line) -> Point {} }}
-// impl BitXor<Point> for Point { type Output = Dual;fn bitxor(self, a:Point) -> Dual {} }
-
-fn dot33(a:&f32x4, b:&f32x4)->f32x4 {
-  // -a0 b0
-  f32x4::from_array([-1.0, 0.0, 0.0, 0.0]) * mul_ss(a, b)
+impl From<Point> for [Float;4] { #[inline(always)] fn from(v: Point) -> Self { v.0.yzwx().to_array() } }
+#[cfg(feature = "mint")] impl From<mint::Point2<Float>> for Point { #[inline] fn from(v: mint::Point2<Float>)->Point { Self::new(v.x,v.y,0.0) } }
+#[cfg(feature = "mint")] impl From<Point> for mint::Point2<Float> { #[inline] fn from(v: Point) -> Self { Self { x: v.x(), y: v.y() } } }
+#[cfg(feature = "mint")] impl From<mint::Point3<Float>> for Point { #[inline] fn from(v: mint::Point3<Float>)->Point { Self::new(v.x,v.y,v.z) } }
+#[cfg(feature = "mint")] impl From<Point> for mint::Point3<Float> { #[inline] fn from(v: Point) -> Self { Self { x: v.x(), y: v.y(), z: v.z() } } }
+#[cfg(feature = "mint")] impl From<mint::Vector4<Float>> for Point { #[inline] fn from(v: mint::Vector4<Float>)->Point { Point(Lanes::from_array([v.w,v.x,v.y,v.z])) } }
+#[cfg(feature = "mint")] impl From<Point> for mint::Vector4<Float> { #[inline] fn from(v: Point) -> Self { <[Float;4]>::from(v).into() } }
+#[cfg(feature = "glam")] impl From<Vec3> for Point { #[inline] fn from(v: Vec3)->Point { Self::new(v.x,v.y,v.z) } }
+#[cfg(feature = "glam")] impl From<Point> for Vec3 { #[inline] fn from(v: Point) -> Self { Self::new(v.x(), v.y(), v.z()) } }
+/// From homogeneous coordinates `(x, y, z, w)`
+#[cfg(feature = "glam")] impl From<Vec4> for Point { #[inline] fn from(v: Vec4)->Point { Point(Lanes::from_array([v.w,v.x,v.y,v.z])) } }
+#[cfg(feature = "glam")] impl From<Point> for Vec4 { #[inline] fn from(v: Point) -> Self { Self::from_array(v.into()) } }
+
+// Point ^ Line and Point ^ Point are zero, their grades add up past the pseudoscalar.
+
+/// Points stored as a structure of arrays, every coordinate is kept in its own
+/// array of four lane vectors so that batches are transformed four points per
+/// step, see [`Motor::apply_soa`].
+#[derive(Default,Debug,Clone,PartialEq)]
+pub struct PointsSoA { len:usize, pub(crate) x:Vec<Lanes>, pub(crate) y:Vec<Lanes>, pub(crate) z:Vec<Lanes>, pub(crate) w:Vec<Lanes> }
+
+impl PointsSoA {
+  pub fn new()->Self { Self::default() }
+  pub fn with_capacity(n:usize)->Self {
+    let lanes = n.div_ceil(4);
+    PointsSoA{len:0, x:Vec::with_capacity(lanes), y:Vec::with_capacity(lanes), z:Vec::with_capacity(lanes), w:Vec::with_capacity(lanes)}
+  }
+  pub fn len(&self)->usize { self.len }
+  pub fn is_empty(&self)->bool { self.len == 0 }
+  pub fn push(&mut self, p:Point) {
+    let (i, j) = (self.len / 4, self.len % 4);
+    if j == 0 {
+      // Unused lanes hold the origin
+      self.x.push(Lanes::splat(0.0)); self.y.push(Lanes::splat(0.0));
+      self.z.push(Lanes::splat(0.0)); self.w.push(Lanes::splat(1.0));
+    }
+    self.x[i][j] = p.x(); self.y[i][j] = p.y(); self.z[i][j] = p.z(); self.w[i][j] = p.w();
+    self.len += 1;
+  }
+  pub fn get(&self, n:usize)->Option<Point> {
+    if n >= self.len { return None }
+    let (i, j) = (n / 4, n % 4);
+    Some(Point(Lanes::from_array([self.w[i][j], self.x[i][j], self.y[i][j], self.z[i][j]])))
+  }
+  pub fn iter(&self)->impl Iterator<Item=Point> + '_ { (0..self.len).filter_map(|n| self.get(n)) }
 }
 
-fn dotptl(a:&f32x4, b:&f32x4)->f32x4 {
-  let dp = &hi_dp_ss(a, b);
-  let p0 = &a.xxxx() * b;
-  let p0 = &f32x4_xor(&p0, &[0.0, -0.0, -0.0, -0.0].into());
-  add_ss(p0, dp)
+impl FromIterator<Point> for PointsSoA {
+  fn from_iter<I:IntoIterator<Item=Point>>(iter:I)->Self {
+    let mut soa = PointsSoA::new();
+    for p in iter { soa.push(p) }
+    soa
+  }
 }
 
-fn gp33(a:&f32x4, b:&f32x4)->f32x4 {
-  // (-a0 b0) +
-  // (-a0 b1 + a1 b0) e01 +
-  // (-a0 b2 + a2 b0) e02 +
-  // (-a0 b3 + a3 b0) e03
-  let tmp = a.xxxx() * b * f32x4::from_array([-2.0, -1.0, -1.0, -1.0]) + a * b.xxxx();
-  tmp * rcp_nr1(&tmp.xxzz().xyxy())
-  // TODO, in klein their is an extra `and`
-  // flip_signs(tmp, mask32x4::from([false, true, true, true]))
-  // mask32x4::from_array([false, true, true, true]).select(tmp, f32x4::splat(0.0))
-  // f32x4_and(tmp, f32x4::from([0.0, -1.0, -1.0, -1.0]))
+impl From<&[Point]> for PointsSoA { fn from(points:&[Point])->Self { points.iter().copied().collect() } }
+
+products! {
+  Point * Plane = Motor, gp_3_0(0; 0) -> (p1, p2) => Motor{p1, p2};
+  Point * Line = Flector, gp_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Point * Branch = Flector, gp_3_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Point * Horizon = Direction, gp_3_2(0; p2) -> (_, p3) => Direction(p3);
+  Point * Direction = Horizon, gp_3_3(0; 0) -> (_, p2) => Horizon{p2};
+  Point * Dual as Motor = Flector, gp_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Point ^ Plane = Dual, ext_3_0(0; 0) -> p2 => Dual::new(0.0, p2[0]);
+  Point ^ Dual as Motor = Point, ext_3_12(0; p1, p2) -> p3 => Point(p3);
+  Point ^ Rotor = Point, ext_3_1(0; 0) -> p3 => Point(p3);
+  Point ^ Translator as Motor = Point, ext_3_12(0; p1, p2) -> p3 => Point(p3);
+  Point ^ Motor = Point, ext_3_12(0; p1, p2) -> p3 => Point(p3);
+  Point & Direction = Line, reg_3_3(0; 0) -> (p1, p2) => Line{p1, p2};
+  Point & Dual as Motor = Point, reg_3_12(0; p1, p2) -> (_, p3) => Point(p3);
+  Point & Rotor = Plane, reg_3_1(0; 0) -> p0 => Plane(p0);
+  Point & Translator as Motor = Plane, reg_3_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Point & Motor = Flector, reg_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Point | Line = Plane, dot_3_12(0; p1, p2) -> (p0, _) => Plane(p0);
+  Point | Point = Float, dot_3_3(0; 0) -> p1 => p1[0];
+  Point | Branch = Plane, dot_3_1(0; 0) -> (p0, _) => Plane(p0);
+  Point | Dual as Motor = Flector, dot_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Point | Rotor = Flector, dot_3_1(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Point | Translator as Motor = Point, dot_3_12(0; p1, p2) -> (_, p3) => Point(p3);
+  Point | Motor = Flector, dot_3_12(0; p1, p2) -> (p0, p3) => Flector{p0, p3};
+  Point / Plane = Motor;
+  Point / Point = Translator;
+  Point / Line = Flector;
+  Point / Branch = Flector;
+  Point / Dual = Flector;
+  Point / Rotor = Flector;
+  Point / Translator = Flector;
+  Point / Motor = Flector;
 }
 
 #[cfg(test)]
@@ -179,19 +238,19 @@ mod tests {
   #[test] #[ignore] fn point_normalized() {}
   #[test] #[ignore] fn point_inverse() {}
 
-  fn assert_point(p:Point,x:f32,y:f32,z:f32,w:f32) {
+  fn assert_point(p:Point,x:Float,y:Float,z:Float,w:Float) {
     assert_eq!([p.x(), p.y(), p.z(), p.w()],[x, y, z, w]);
   }
 
   #[test] fn point_xyzw() {
-    assert_eq!(&<[f32;4]>::from(&point(4.0,3.0,2.0)), &[4f32,3.0,2.0,1.0]);
-    assert_eq!(<[f32;4]>::from(point(4.0,3.0,2.0)), [4f32,3.0,2.0,1.0]);
+    assert_eq!(&<[Float;4]>::from(&point(4.0,3.0,2.0)), &[4.0,3.0,2.0,1.0]);
+    assert_eq!(<[Float;4]>::from(point(4.0,3.0,2.0)), [4.0,3.0,2.0,1.0]);
   }
   #[test] fn z_line() { assert_eq!((O & Z).e12(), 1.0); }
 
   #[test] fn y_line() { assert_eq!((-Y & O).e31(), 1.0); }
 
-  #[test] fn x_line() { assert_eq!((-2.0*X & -X).normalized().e23(), 1.0); }
+  #[test] fn x_line() { assert_eq!(((-2.0*X) & -X).normalized().e23(), 1.0); }
 
   #[test] fn plane_construction() {
     let a = point(1.0, 3.0, 2.0);
@@ -222,11 +281,11 @@ mod tests {
   }
 
   #[test] fn to_array() {
-    let a: [f32; 3] = Point::new(1.0, 2.0, 3.0).into();
-    assert_eq!(a, [1.0f32, 2.0, 3.0]);
+    let a: [Float; 3] = Point::new(1.0, 2.0, 3.0).into();
+    assert_eq!(a, [1.0, 2.0, 3.0]);
   }
   #[test] fn to_tuple() {
-    let a: (f32,f32,f32) = Point::new(1.0, 2.0, 3.0).into();
-    assert_eq!(a, (1.0f32, 2.0f32, 3.0f32));
+    let a: (Float,Float,Float) = Point::new(1.0, 2.0, 3.0).into();
+    assert_eq!(a, (1.0, 2.0, 3.0));
   }
 }
@@ -0,0 +1,254 @@
+//! Intersection and closest point queries with typed results.
+//!
+//! The queries are built on the meet `^` and join `&`, the returned
+//! [`Intersection`] tells a proper hit apart from an ideal meet (parallel
+//! elements, `w ≈ 0`) and a vanishing meet (coincident elements).
+//!
+//! ```
+//! use g3::{plane, point, Direction};
+//! use g3::query::{ray_plane, Intersection, Ray};
+//! let ray = Ray::new(point(0.0, 0.0, 5.0), Direction::new(0.0, 0.0, -1.0));
+//! let Intersection::Hit(hit) = ray_plane(&ray, plane(0.0, 0.0, 1.0, 0.0)) else { panic!() };
+//! assert_eq!(hit.distance, 5.0);
+//! ```
+
+use super::{Plane, Point, Line, Direction, maths::*};
+
+/// Outcome of a query.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Intersection<T> {
+  /// The elements meet in `T`.
+  Hit(T),
+  /// The meet is ideal, the elements are parallel and only meet at infinity.
+  Parallel,
+  /// The meet vanishes, the elements coincide or one lies in the other.
+  Coincident,
+  /// The elements do not meet, for example the meet lies behind a ray.
+  Miss,
+}
+
+impl<T> Intersection<T> {
+  pub fn is_hit(&self)->bool { matches!(self, Intersection::Hit(_)) }
+  /// The hit, if any.
+  pub fn hit(self)->Option<T> { if let Intersection::Hit(t) = self { Some(t) } else { None } }
+}
+
+/// A half line from `origin` in `direction`. The queries measure distances
+/// from a normalized origin along a unit direction, so a ray is only made by
+/// [`Ray::new`].
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Ray { origin:Point, direction:Direction }
+
+impl Ray {
+  pub fn new(origin:Point, direction:Direction)->Ray { Ray{origin: origin.normalized(), direction: direction.normalized()} }
+  /// The normalized origin.
+  #[inline] pub fn origin(&self)->Point { self.origin }
+  /// The unit direction.
+  #[inline] pub fn direction(&self)->Direction { self.direction }
+  /// The point at `distance` along the ray.
+  pub fn at(&self, distance:Float)->Point { Point(self.origin.0 + self.direction.0 * Lanes::splat(distance)) }
+  /// The line carrying the ray, joined from the origin and the ideal point of the direction.
+  pub fn line(&self)->Line { self.origin & Point(self.direction.0) }
+}
+
+/// Where a ray hits an element.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct RayHit { pub distance:Float, pub point:Point }
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Sphere { pub center:Point, pub radius:Float }
+
+/// The points from `a` to `b`.
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub struct Segment { pub a:Point, pub b:Point }
+
+/// Ray against plane. A ray in the plane is `Coincident`, as is every ray for
+/// the zero plane. Every ray meets the ideal plane at infinity, `Parallel`.
+pub fn ray_plane(ray:&Ray, plane:Plane)->Intersection<RayHit> {
+  if plane.norm() < tolerance() {
+    return if plane.d().abs() < tolerance() { Intersection::Coincident } else { Intersection::Parallel }
+  }
+  on_ray(ray, plane.normalized() ^ ray.line())
+}
+
+/// Ray against the surface of a sphere, a ray from inside hits on the way out.
+pub fn ray_sphere(ray:&Ray, sphere:&Sphere)->Intersection<RayHit> {
+  let oc = sub3(xyz(&ray.origin), xyz(&sphere.center.normalized()));
+  let d:[Float;3] = ray.direction.into();
+  let b = dot3(oc, d);
+  let disc = b*b - dot3(oc, oc) + sphere.radius*sphere.radius;
+  if disc < 0.0 { return Intersection::Miss }
+  let distance = [-b - disc.sqrt(), -b + disc.sqrt()].into_iter().find(|t| *t >= 0.0);
+  match distance {
+    Some(distance) => Intersection::Hit(RayHit{distance, point: ray.at(distance)}),
+    None => Intersection::Miss,
+  }
+}
+
+/// Ray against triangle `abc` from either side. The hit point of the plane
+/// through `a & b & c` is inside when the ray passes every edge line on the same
+/// side, that is when the meets of the ray with the edges share their sign. A
+/// ray in the plane of the triangle or a degenerate triangle, with collinear or
+/// coincident corners, is `Coincident`.
+pub fn ray_triangle(ray:&Ray, a:Point, b:Point, c:Point)->Intersection<RayHit> {
+  let (a, b, c) = (a.normalized(), b.normalized(), c.normalized());
+  // The norm of the join is twice the area, relative to the edges from a
+  let p = a & b & c;
+  let (ab, ac) = (sub3(xyz(&b), xyz(&a)), sub3(xyz(&c), xyz(&a)));
+  if p.norm() <= tolerance() * (dot3(ab, ab) * dot3(ac, ac)).sqrt() { return Intersection::Coincident }
+  let l = ray.line();
+  match on_ray(ray, p.normalized() ^ l) {
+    Intersection::Hit(hit) => {
+      let sides = [(l ^ (a & b)).e0123(), (l ^ (b & c)).e0123(), (l ^ (c & a)).e0123()];
+      if sides.iter().all(|s| *s >= 0.0) || sides.iter().all(|s| *s <= 0.0) { Intersection::Hit(hit) } else { Intersection::Miss }
+    }
+    other => other,
+  }
+}
+
+/// Closest points of two segments, the first on `s` and the second on `t`.
+/// Segments on parallel lines have no unique closest points, they are
+/// `Parallel` or `Coincident` when on the same line. A segment shorter than the
+/// tolerance is a point, its closest point on the other segment is a `Hit`.
+pub fn segment_segment(s:&Segment, t:&Segment)->Intersection<(Point, Point)> {
+  let (p, q) = (xyz(&s.a.normalized()), xyz(&t.a.normalized()));
+  let (d1, d2) = (sub3(xyz(&s.b.normalized()), p), sub3(xyz(&t.b.normalized()), q));
+  let r = sub3(p, q);
+  let (a, e, b) = (dot3(d1, d1), dot3(d2, d2), dot3(d1, d2));
+  let at = |o:[Float;3], d:[Float;3], s:Float| { let [x, y, z] = add3(o, scale3(d, s)); Point::new(x, y, z) };
+  // Either segment a point, then the other is clamped onto it
+  let point_like = tolerance() * tolerance();
+  if a <= point_like || e <= point_like {
+    let (u, v) = if a <= point_like && e <= point_like { (0.0, 0.0) }
+      else if a <= point_like { (0.0, (dot3(d2, r) / e).clamp(0.0, 1.0)) }
+      else { ((-dot3(d1, r) / a).clamp(0.0, 1.0), 0.0) };
+    return Intersection::Hit((at(p, d1, u), at(q, d2, v)))
+  }
+  let n = cross3(d1, d2);
+  if dot3(n, n) <= tolerance() * tolerance() * a * e {
+    let offset = cross3(r, d1);
+    return if dot3(offset, offset) <= tolerance() * tolerance() * a { Intersection::Coincident } else { Intersection::Parallel }
+  }
+  let (c, f) = (dot3(d1, r), dot3(d2, r));
+  // Closest points of the lines, then clamped to the segments (Ericson, Real-Time Collision Detection 5.1.9)
+  let denom = a*e - b*b;
+  let mut u = ((b*f - c*e) / denom).clamp(0.0, 1.0);
+  let mut v = (b*u + f) / e;
+  if v < 0.0 { v = 0.0; u = (-c / a).clamp(0.0, 1.0) } else if v > 1.0 { v = 1.0; u = ((b - c) / a).clamp(0.0, 1.0) }
+  Intersection::Hit((at(p, d1, u), at(q, d2, v)))
+}
+
+/// The point `a ^ b ^ c` where three planes meet. Planes through a common line
+/// are `Coincident`, planes that only meet at infinity are `Parallel`. Like in
+/// [`ray_plane`] the zero plane is `Coincident` and the ideal plane `Parallel`.
+pub fn plane_plane_plane(a:Plane, b:Plane, c:Plane)->Intersection<Point> {
+  for p in [a, b, c] {
+    if p.norm() < tolerance() {
+      return if p.d().abs() < tolerance() { Intersection::Coincident } else { Intersection::Parallel }
+    }
+  }
+  let p = a.normalized() ^ b.normalized() ^ c.normalized();
+  if vanishes(&p) { Intersection::Coincident }
+  else if p.w().abs() < tolerance() { Intersection::Parallel }
+  else { Intersection::Hit(p.normalized()) }
+}
+
+// Interpret the meet `p` of a ray with a plane.
+fn on_ray(ray:&Ray, p:Point)->Intersection<RayHit> {
+  if vanishes(&p) { return Intersection::Coincident }
+  if p.w().abs() < tolerance() { return Intersection::Parallel }
+  let point = p.normalized();
+  let distance = dot3(sub3(xyz(&point), xyz(&ray.origin)), ray.direction.into());
+  if distance < 0.0 { Intersection::Miss } else { Intersection::Hit(RayHit{distance, point}) }
+}
+
+fn vanishes(p:&Point)->bool { p.0.to_array().iter().all(|x| x.abs() < tolerance()) }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::*;
+
+  fn ray(o:[Float;3], d:[Float;3])->Ray { Ray::new(point(o[0], o[1], o[2]), Direction::new(d[0], d[1], d[2])) }
+  #[test] fn ray_plane_cases() {
+    let floor = plane(0.0, 1.0, 0.0, 1.0); // y = -1
+    let hit = ray_plane(&ray([1.0, 2.0, 0.0], [0.0, -1.0, 1.0]), floor).hit().unwrap();
+    approx_eq3(xyz(&hit.point), [1.0, -1.0, 3.0], 0.000_1);
+    assert!((hit.distance - 3.0 * Float::sqrt(2.0)).abs() < 0.0001);
+    assert_eq!(ray_plane(&ray([1.0, 2.0, 0.0], [0.0, 1.0, 1.0]), floor), Intersection::Miss);
+    assert_eq!(ray_plane(&ray([1.0, 2.0, 0.0], [1.0, 0.0, 1.0]), floor), Intersection::Parallel);
+    assert_eq!(ray_plane(&ray([1.0, -1.0, 0.0], [1.0, 0.0, 1.0]), floor), Intersection::Coincident);
+    assert_eq!(ray_plane(&ray([1.0, 2.0, 0.0], [0.0, -1.0, 1.0]), plane(0.0, 0.0, 0.0, 0.0)), Intersection::Coincident);
+    assert_eq!(ray_plane(&ray([1.0, 2.0, 0.0], [0.0, -1.0, 1.0]), plane(0.0, 0.0, 0.0, 1.0)), Intersection::Parallel);
+  }
+
+  #[test] fn ray_sphere_cases() {
+    let s = Sphere{center: point(0.0, 0.0, 5.0), radius: 1.0};
+    let hit = ray_sphere(&ray([0.0, 0.0, 0.0], [0.0, 0.0, 1.0]), &s).hit().unwrap();
+    assert!((hit.distance - 4.0).abs() < 0.0001);
+    approx_eq3(xyz(&hit.point), [0.0, 0.0, 4.0], 0.000_1);
+    assert!((ray_sphere(&ray([0.0, 0.0, 5.0], [1.0, 0.0, 0.0]), &s).hit().unwrap().distance - 1.0).abs() < 0.0001);
+    assert_eq!(ray_sphere(&ray([0.0, 0.0, 0.0], [0.0, 0.0, -1.0]), &s), Intersection::Miss);
+    assert_eq!(ray_sphere(&ray([0.0, 2.0, 0.0], [0.0, 0.0, 1.0]), &s), Intersection::Miss);
+    // Distances are along the unit direction from the normalized origin
+    let r = Ray::new(point(0.0, 0.0, 0.0) * 2.0, Direction::new(0.0, 0.0, 3.0));
+    assert!((ray_sphere(&r, &s).hit().unwrap().distance - 4.0).abs() < 0.0001);
+    approx_eq3(xyz(&r.origin()), [0.0, 0.0, 0.0], 0.000_1);
+    assert!((<[Float;3]>::from(r.direction())[2] - 1.0).abs() < 0.0001);
+  }
+
+  #[test] fn ray_triangle_cases() {
+    let (a, b, c) = (point(0.0, 0.0, 0.0), point(1.0, 0.0, 0.0), point(0.0, 1.0, 0.0));
+    for d in [-1.0, 1.0] {
+      let hit = ray_triangle(&ray([0.2, 0.3, -d], [0.0, 0.0, d]), a, b, c).hit().unwrap();
+      approx_eq3(xyz(&hit.point), [0.2, 0.3, 0.0], 0.000_1);
+      assert!((hit.distance - 1.0).abs() < 0.0001);
+    }
+    assert_eq!(ray_triangle(&ray([0.8, 0.8, 1.0], [0.0, 0.0, -1.0]), a, b, c), Intersection::Miss);
+    assert_eq!(ray_triangle(&ray([0.2, 0.3, 1.0], [0.0, 0.0, 1.0]), a, b, c), Intersection::Miss);
+    assert_eq!(ray_triangle(&ray([0.2, 0.3, 1.0], [1.0, 0.0, 0.0]), a, b, c), Intersection::Parallel);
+    assert_eq!(ray_triangle(&ray([-1.0, 0.2, 0.0], [1.0, 0.0, 0.0]), a, b, c), Intersection::Coincident);
+    // Degenerate triangles
+    let down = ray([0.5, 0.0, 1.0], [0.0, 0.0, -1.0]);
+    assert_eq!(ray_triangle(&down, a, b, point(2.0, 0.0, 0.0)), Intersection::Coincident);
+    assert_eq!(ray_triangle(&down, b, b, b), Intersection::Coincident);
+  }
+
+  #[test] fn segment_segment_cases() {
+    let seg = |a:[Float;3], b:[Float;3]| Segment{a: point(a[0], a[1], a[2]), b: point(b[0], b[1], b[2])};
+    let (p, q) = segment_segment(&seg([0.0, 0.0, 0.0], [2.0, 0.0, 0.0]), &seg([1.0, -1.0, 1.0], [1.0, 1.0, 1.0])).hit().unwrap();
+    approx_eq3(xyz(&p), [1.0, 0.0, 0.0], 0.000_1);
+    approx_eq3(xyz(&q), [1.0, 0.0, 1.0], 0.000_1);
+    // The closest points of the lines are outside of the segments.
+    let (p, q) = segment_segment(&seg([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]), &seg([3.0, 2.0, 0.0], [3.0, 4.0, 0.0])).hit().unwrap();
+    approx_eq3(xyz(&p), [1.0, 0.0, 0.0], 0.000_1);
+    approx_eq3(xyz(&q), [3.0, 2.0, 0.0], 0.000_1);
+    assert_eq!(segment_segment(&seg([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]), &seg([0.0, 1.0, 0.0], [2.0, 1.0, 0.0])), Intersection::Parallel);
+    assert_eq!(segment_segment(&seg([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]), &seg([2.0, 0.0, 0.0], [3.0, 0.0, 0.0])), Intersection::Coincident);
+    // Segments of zero length are points, however far away
+    let (p, q) = segment_segment(&seg([0.0, 0.0, 0.0], [0.0, 0.0, 0.0]), &seg([5.0, 5.0, 5.0], [6.0, 5.0, 5.0])).hit().unwrap();
+    approx_eq3(xyz(&p), [0.0, 0.0, 0.0], 0.000_1);
+    approx_eq3(xyz(&q), [5.0, 5.0, 5.0], 0.000_1);
+    let (p, q) = segment_segment(&seg([0.0, 2.0, 0.0], [4.0, 2.0, 0.0]), &seg([1.0, 0.0, 0.0], [1.0, 0.0, 0.0])).hit().unwrap();
+    approx_eq3(xyz(&p), [1.0, 2.0, 0.0], 0.000_1);
+    approx_eq3(xyz(&q), [1.0, 0.0, 0.0], 0.000_1);
+    let (p, q) = segment_segment(&seg([1.0, 1.0, 1.0], [1.0, 1.0, 1.0]), &seg([3.0, 1.0, 1.0], [3.0, 1.0, 1.0])).hit().unwrap();
+    approx_eq3(xyz(&p), [1.0, 1.0, 1.0], 0.000_1);
+    approx_eq3(xyz(&q), [3.0, 1.0, 1.0], 0.000_1);
+  }
+
+  #[test] fn three_planes() {
+    let (x, y, z) = (plane(1.0, 0.0, 0.0, -1.0), plane(0.0, 2.0, 0.0, -4.0), plane(0.0, 0.0, 1.0, 3.0));
+    approx_eq3(xyz(&plane_plane_plane(x, y, z).hit().unwrap()), [1.0, 2.0, -3.0], 0.000_1);
+    assert_eq!(plane_plane_plane(x, plane(1.0, 0.0, 0.0, 2.0), z), Intersection::Parallel);
+    assert_eq!(plane_plane_plane(x, z, plane(1.0, 0.0, 1.0, 2.0)), Intersection::Coincident);
+  }
+
+  #[test] fn three_planes_degenerate() {
+    let (x, y, o) = (plane(1.0, 0.0, 0.0, -1.0), plane(0.0, 2.0, 0.0, -4.0), plane(0.0, 0.0, 0.0, 0.0));
+    for [a, b, c] in [[o, x, y], [x, o, y], [x, y, o]] {
+      assert_eq!(plane_plane_plane(a, b, c), Intersection::Coincident);
+    }
+    assert_eq!(plane_plane_plane(x, y, plane(0.0, 0.0, 0.0, 1.0)), Intersection::Parallel);
+  }
+}
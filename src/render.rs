@@ -38,18 +38,17 @@ impl Renderer {
   }
   fn draw_planes(&mut self, gl:&glow::Context) {
     let mut planes = vec![];
-    let mut colors = vec![];
+    let mut colors:Vec<[f32;4]> = vec![];
     // for f in self.world.query_mut::<(&Box<dyn Fn()->Plane + Send + Sync>,)>() {
     //
     // }
     for (_id, (p,color)) in self.world.query_mut::<(&Plane, &Color)>() {
       let m = (p.normalized()* e2).sqrt();
-      let a = m(point(-1.0,0.0,-1.0));
-      let b = m(point(-1.0,0.0,1.0));
-      let c = m(point(1.0,0.0,1.0));
-      let d = m(point(1.0,0.0,-1.0));
+      let mut quad = [Point::default();4];
+      m.apply_points(&[point(-1.0,0.0,-1.0), point(-1.0,0.0,1.0), point(1.0,0.0,1.0), point(1.0,0.0,-1.0)], &mut quad);
+      let [a, b, c, d] = quad;
       planes.extend_from_slice(&[a, b, c, c, d, a]);
-      colors.extend_from_slice(&[*color,*color,*color,*color,*color,*color]);
+      colors.extend_from_slice(&[(*color).into();6]);
     };
     unsafe {
       gl.use_program(Some(self.plane.raw));
@@ -60,12 +59,7 @@ impl Renderer {
       gl.vertex_attrib_pointer_f32(0, 4, glow::FLOAT, false, 0 as i32, 0);
       gl.vertex_attrib_pointer_f32(1, 4, glow::FLOAT, false, 0 as i32, (std::mem::size_of::<f32>()*4*planes.len()) as i32);
       self.plane.load(gl, &self.uniforms);
-      let mut l = vec![];
-      for p in &planes { l.push([p.x(), p.y(), p.z(), p.w()]) }
-      for c in &colors { l.push([c.red(), c.green(), c.blue(), c.alpha()]) }
-      let buffer = bytemuck::cast_slice(&l);
-      gl.bind_buffer(glow::ARRAY_BUFFER, self.plane.vbo);
-      gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, buffer, glow::DYNAMIC_DRAW);
+      self.plane.upload(gl, &planes, &colors);
       gl.bind_vertex_array(self.plane.vao);
       gl.draw_arrays(glow::TRIANGLES, 0, planes.len() as i32);
     }
@@ -75,11 +69,11 @@ impl Renderer {
     let eps = 0.001;
     let cube = &[plane(size, 0.0, 0.0, 1.0), plane(size, 0.0, 0.0, -1.0), plane(0.0, size, 0.0, 1.0), plane(0.0, size, 0.0, -1.0), plane(0.0, 0.0, size, 1.0), plane(0.0, 0.0, size, -1.0)];
     let mut points = vec![];
-    let mut colors = vec![];
+    let mut colors:Vec<[f32;4]> = vec![];
     for (_id, (l,c)) in self.world.query_mut::<(&Line, &Color)>() {
       cube.iter().map(|p|(*l^*p).normalized())
         .filter(|p|p.x()<=size&&p.y()<=size&&p.z()<=size)
-        .for_each(|p|{points.push(p); colors.push(*c);});
+        .for_each(|p|{points.push(p); colors.push((*c).into());});
     };
     unsafe {
       gl.use_program(Some(self.line.raw));
@@ -90,12 +84,7 @@ impl Renderer {
       gl.vertex_attrib_pointer_f32(0, 4, glow::FLOAT, false, 0 as i32, 0);
       gl.vertex_attrib_pointer_f32(1, 4, glow::FLOAT, false, 0 as i32, (std::mem::size_of::<f32>()*4*points.len()) as i32);
       self.line.load(gl, &self.uniforms);
-      let mut l = vec![];
-      for p in &points { l.push([p.x(), p.y(), p.z(), p.w()]) }
-      for c in &colors { l.push([c.red(), c.green(), c.blue(), c.alpha()]) }
-      let buffer = bytemuck::cast_slice(&l);
-      gl.bind_buffer(glow::ARRAY_BUFFER, self.line.vbo);
-      gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, buffer, glow::DYNAMIC_DRAW);
+      self.line.upload(gl, &points, &colors);
       gl.enable(glow::PROGRAM_POINT_SIZE);
       gl.bind_vertex_array(self.line.vao);
       gl.draw_arrays(glow::LINES, 0, points.len() as i32);
@@ -103,10 +92,10 @@ impl Renderer {
   }
   fn draw_points(&mut self, gl:&glow::Context) {
     let mut points = vec![];
-    let mut colors = vec![];
+    let mut colors:Vec<[f32;4]> = vec![];
     for (_id, (p,c)) in self.world.query_mut::<(&Point, &Color)>() {
       points.push(*p);
-      colors.push(*c);
+      colors.push((*c).into());
     };
     unsafe {
       gl.use_program(Some(self.point.raw));
@@ -117,12 +106,7 @@ impl Renderer {
       gl.vertex_attrib_pointer_f32(0, 4, glow::FLOAT, false, 0 as i32, 0);
       gl.vertex_attrib_pointer_f32(1, 4, glow::FLOAT, false, 0 as i32, (std::mem::size_of::<f32>()*4*points.len()) as i32);
       self.point.load(gl, &self.uniforms);
-      let mut l = vec![];
-      for p in &points { l.push([p.x(), p.y(), p.z(), p.w()]) }
-      for c in &colors { l.push([c.red(), c.green(), c.blue(), c.alpha()]) }
-      let buffer = bytemuck::cast_slice(&l);
-      gl.bind_buffer(glow::ARRAY_BUFFER, self.point.vbo);
-      gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, buffer, glow::DYNAMIC_DRAW);
+      self.point.upload(gl, &points, &colors);
       gl.enable(glow::PROGRAM_POINT_SIZE);
       gl.bind_vertex_array(self.point.vao);
       gl.draw_arrays(glow::POINTS, 0, points.len() as i32);
@@ -196,6 +180,15 @@ impl Program {
     gl.uniform_matrix_4_f32_slice(self.locations.projection.as_ref(), false, &uniforms.projection);
     // gl.bind_buffer(glow::ARRAY_BUFFER, self.vbo);
   }
+
+  // The positions and after them the colors, attributes 0 and 1.
+  unsafe fn upload(&self, gl: &glow::Context, positions:&[Point], colors:&[[f32;4]]) {
+    let (positions, colors):(&[u8], &[u8]) = (bytemuck::cast_slice(positions), bytemuck::cast_slice(colors));
+    gl.bind_buffer(glow::ARRAY_BUFFER, self.vbo);
+    gl.buffer_data_size(glow::ARRAY_BUFFER, (positions.len() + colors.len()) as i32, glow::DYNAMIC_DRAW);
+    gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, 0, positions);
+    gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER, positions.len() as i32, colors);
+  }
 }
 
 struct Mesh {
@@ -204,9 +197,7 @@ struct Mesh {
 
 impl Mesh {
   unsafe fn vertex_attribute(&self, gl: &glow::Context, mode: u32, vao: Option<NativeVertexArray>, vbo: Option<NativeBuffer>) {
-    let mut l = vec![];
-    for p in &self.positions { l.push([p.x(), p.y(), p.z(), p.w()]) }
-    let buffer = bytemuck::cast_slice(&l);
+    let buffer = bytemuck::cast_slice(&self.positions);
     gl.bind_buffer(glow::ARRAY_BUFFER, vbo);
     gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, buffer, glow::DYNAMIC_DRAW);
     // unsafe { gl.vertex_attrib_4_f32(0, 0.0, 0.0, 0.0, 1.0); }
@@ -296,7 +287,7 @@ impl Camera {
 
   /// Converts a normalized mouse position into 3D
   pub fn mouse_pos(&self, pos_norm: Point) -> Point {
-    self.mat_i()(pos_norm)
+    self.mat_i().apply(pos_norm)
   }
 
   pub fn mouse_move(&mut self, new_pos: Point) {
@@ -406,7 +397,8 @@ const COLOR_VERTEX_SHADER:&str = r#"
   uniform mat4 projection;
   out vec4 f_color;
   void main() {
-      gl_Position = projection * view * model * in_position;
+      // Points are stored as (w, x, y, z)
+      gl_Position = projection * view * model * in_position.yzwx;
       f_color = color;
   }
 "#;
@@ -427,7 +419,8 @@ const POINT_VERTEX_SHADER:&str = r#"
   out vec4 f_color;
   void main() {
       gl_PointSize = 20.0;
-      gl_Position = projection * view * model * in_position;
+      // Points are stored as (w, x, y, z)
+      gl_Position = projection * view * model * in_position.yzwx;
       f_color = color;
   }
 "#;
@@ -444,6 +437,7 @@ const POINT_FRAGMENT_SHADER:&str = r#"
 "#;
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color(pub u32);
 
 impl Color {
@@ -1,26 +1,36 @@
-use std::{simd::{mask32x4,f32x4,SimdFloat,simd_swizzle as swizzle},ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg, Fn}};
-use crate::{*, maths::*};
+use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg}};
+use super::{*, maths::*};
 
 /// angle x y z
-pub fn rotor(ang_rad:f32,x:f32,y:f32,z:f32)->Rotor {
+pub fn rotor(ang_rad:Float,x:Float,y:Float,z:Float)->Rotor {
   Rotor::new(ang_rad, x, y, z)
 }
 
 /// The rotor is an entity that represents a rigid rotation about an axis.
+///
+/// Laid out as four floats `[scalar, e23, e31, e12]`.
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable))]
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Rotor(pub f32x4);
+pub struct Rotor<L = Lanes>(pub(crate) L);
+pod!(Rotor);
+
+serde_blades!(Rotor { scalar, e23, e31, e12 } => |a| Rotor(Lanes::from_array(a)));
 
 impl Rotor {
-  pub fn new(ang_rad:f32,x:f32,y:f32,z:f32)->Rotor {
+  pub fn new(ang_rad:Float,x:Float,y:Float,z:Float)->Rotor {
     let norm  = (x*x + y*y + z*z).sqrt();
     let inv_norm = 1.0 / norm;
     let half = 0.5 * ang_rad;
     let sin_ang = half.sin();
     let scale = sin_ang * inv_norm;
-    Rotor(f32x4::from_array([half.cos(),x,y,z]) * f32x4::from_array([1.0,scale,scale,scale]))
+    Rotor(Lanes::from_array([half.cos(),x,y,z]) * Lanes::from_array([1.0,scale,scale,scale]))
   }
 
-  pub fn from_euler_angles(roll:f32,pitch:f32,yaw:f32)->Rotor {
+  /// Rotor with only scalar component set to one
+  pub fn one()->Rotor { Rotor(Lanes::from_array([1.0,0.0,0.0,0.0])) }
+
+  pub fn from_euler_angles(roll:Float,pitch:Float,yaw:Float)->Rotor {
     // https://en.wikipedia.org/wiki/Conversion_between_quaternions_and_Euler_angles#cite_note-3
     let half_yaw = yaw * 0.5;
     let half_pitch = pitch * 0.5;
@@ -40,7 +50,53 @@ impl Rotor {
       .into()).normalized()
   }
 
-  pub fn load_normalized(data:[f32;4])->Rotor {Rotor(data.into())}
+  pub fn load_normalized(data:[Float;4])->Rotor {Rotor(data.into())}
+
+  /// The rotor of the unit quaternion `[x, y, z, w]`. A quaternion turns
+  /// counterclockwise about its axis and a rotor clockwise, so the axis
+  /// changes sign.
+  pub fn from_quaternion([x,y,z,w]:[Float;4])->Rotor { Rotor(Lanes::from_array([w, -x, -y, -z])) }
+
+  /// The unit quaternion `[x, y, z, w]` of a normalized rotor.
+  pub fn to_quaternion(&self)->[Float;4] { [-self.e23(), -self.e31(), -self.e12(), self.scalar()] }
+
+  /// The rotation of a column-major 3×3 matrix. The columns are made
+  /// orthonormal first, starting from the first column, and the third is the
+  /// cross product of the other two. Scale and shear are dropped that way and
+  /// a reflection becomes a rotation.
+  ///
+  /// The first two columns must span a plane. A zero column or parallel
+  /// columns have no rotation, they fail a `debug_assert` and give NaN
+  /// components in release builds.
+  pub fn from_matrix3(m:[Float;9])->Rotor {
+    let dot = |a:[Float;3], b:[Float;3]| a[0]*b[0] + a[1]*b[1] + a[2]*b[2];
+    let unit = |a:[Float;3]| { let n = dot(a, a).sqrt(); [a[0]/n, a[1]/n, a[2]/n] };
+    let (x, y) = ([m[0], m[1], m[2]], [m[3], m[4], m[5]]);
+    debug_assert!(dot(x, x) > 0.0 && dot(y, y) > 0.0, "from_matrix3 of a zero column {:?}", m);
+    let x = unit(x);
+    let d = dot(x, y);
+    let y = [y[0] - d*x[0], y[1] - d*x[1], y[2] - d*x[2]];
+    debug_assert!(dot(y, y) > tolerance() * tolerance() * dot([m[3], m[4], m[5]], [m[3], m[4], m[5]]), "from_matrix3 of parallel columns {:?}", m);
+    let y = unit(y);
+    let z = [x[1]*y[2] - x[2]*y[1], x[2]*y[0] - x[0]*y[2], x[0]*y[1] - x[1]*y[0]];
+    // Shepperd's method, the largest of w, x, y and z is taken from the
+    // diagonal and the others follow without cancellation.
+    let trace = x[0] + y[1] + z[2];
+    let q = if trace > 0.0 {
+      let s = (trace + 1.0).sqrt() * 2.0;
+      [(y[2] - z[1]) / s, (z[0] - x[2]) / s, (x[1] - y[0]) / s, 0.25 * s]
+    } else if x[0] > y[1] && x[0] > z[2] {
+      let s = (1.0 + x[0] - y[1] - z[2]).sqrt() * 2.0;
+      [0.25 * s, (y[0] + x[1]) / s, (z[0] + x[2]) / s, (y[2] - z[1]) / s]
+    } else if y[1] > z[2] {
+      let s = (1.0 + y[1] - x[0] - z[2]).sqrt() * 2.0;
+      [(y[0] + x[1]) / s, 0.25 * s, (z[1] + y[2]) / s, (z[0] - x[2]) / s]
+    } else {
+      let s = (1.0 + z[2] - x[0] - y[1]).sqrt() * 2.0;
+      [(z[0] + x[2]) / s, (z[1] + y[2]) / s, 0.25 * s, (x[1] - y[0]) / s]
+    };
+    Rotor::from_quaternion(q)
+  }
 
   pub fn normalized(&self)->Rotor {
     let inv_norm = rsqrt_nr1(&dp_bc(&self.0,&self.0));
@@ -48,20 +104,20 @@ impl Rotor {
   }
 
   pub fn inverse(&self)->Rotor {
-    let inv_norm = &rsqrt_nr1(&hi_dp_bc(&self.0, &self.0));
+    let inv_norm = &rsqrt_nr1(&dp_bc(&self.0, &self.0));
     let mut p1 = &self.0 * inv_norm;
-    p1 = p1 * inv_norm;
+    p1 *= inv_norm;
     Rotor(flip_signs(&p1, [false,true,true,true].into()))
   }
 
   pub fn reverse(&self)->Rotor {
-    Rotor(f32x4_xor(&self.0, &f32x4::from([0.0,-0.0,-0.0,-0.0])))
+    Rotor(lanes_xor(&self.0, &Lanes::from([0.0,-0.0,-0.0,-0.0])))
   }
 
   // Constrains the rotor to traverse the shortest arc
   pub fn constrained(&self)->Rotor {
-    let mask = swizzle!(f32x4_and(self.0, f32x4::from([-0.0, 0.0, 0.0, 0.0])), [0,0,0,0]); // TODO: cleanup
-    let p1 = f32x4_xor(&mask,&self.0);
+    let mask = lanes_and(self.0, Lanes::from([-0.0, 0.0, 0.0, 0.0])).xxxx(); // TODO: cleanup
+    let p1 = lanes_xor(&mask,&self.0);
     Rotor(p1)
   }
 
@@ -70,109 +126,141 @@ impl Rotor {
   //
   // Given a rotor $\cos\alpha + \sin\alpha\left[a\ee_{23} + b\ee_{31} +\
   // c\ee_{23}\right]$, the log is computed as simply
-  // $\alpha\left[a\ee_{23} + b\ee_{31} + c\ee_{23}\right]$.
-  // This map is only well-defined if the
-  // rotor is normalized such that $a^2 + b^2 + c^2 = 1$.
+  // $\alpha\left[a\ee_{23} + b\ee_{31} + c\ee_{23}\right]$
+  // with $a^2 + b^2 + c^2 = 1$. The rotor need not be normalized, the angle
+  // comes from `atan2` and is divided by the norm of the bivector itself,
+  // which stays exact towards the identity and half a turn. A full turn, $-1$,
+  // is the same rotation as the identity and has the zero branch as its log.
   pub fn log(&self)->Branch {
-    let cos_ang = self.0[0];
-    let ang = cos_ang.acos();
-    let sin_ang = ang.sin();
-
-    let mut p1  = &self.0 * rcp_nr1(&f32x4::splat(sin_ang));
-    p1 = p1 * f32x4::splat(ang);
-    p1 = zero_first(p1);
-    Branch(p1)
+    let (cos_ang, sin_ang) = (self.0[0], hi_dp(&self.0, &self.0)[0].sqrt());
+    if sin_ang == 0.0 { return Branch(Lanes::splat(0.0)) }
+    let ang = sin_ang.atan2(cos_ang);
+    Branch(zero_first(&self.0 * Lanes::splat(ang / sin_ang)))
   }
 
-  // Compute the square root of the provided rotor $r$.
+  // Compute the square root of the provided rotor $r$. With a negative scalar
+  // $1 + r$ cancels, so the root is taken as half the logarithm instead. The
+  // square root of a full turn, $-1$, has no axis and is taken as the identity.
   pub fn sqrt(&self)->Rotor {
-    let p1 = add_ss(&self.0, &[1.0, 0.0, 0.0, 0.0].into());
-    Rotor(p1).normalized() // TODO avoid extra copy...
+    if self.0[0] < 0.0 { return (self.log() * 0.5).exp() }
+    Rotor(add_ss(&self.0, &[1.0, 0.0, 0.0, 0.0].into())).normalized()
+  }
+
+  /// Spherical interpolation from this rotor at `t = 0` to `b` at `t = 1` at
+  /// constant angular velocity. Both rotors must be normalized, `b` is
+  /// negated when needed so the shortest arc is taken.
+  pub fn slerp(self, b:Rotor, t:Float)->Rotor {
+    let b = self.shortest(b);
+    let d = b * self.reverse();
+    // The axis is undefined for the identity, it is also not needed there
+    let sin_ang = hi_dp(&d.0, &d.0)[0].sqrt();
+    if sin_ang < Float::EPSILON { return self.nlerp(b, t) }
+    Rotor::new(2.0 * sin_ang.atan2(d.scalar()) * t, d.e23(), d.e31(), d.e12()) * self
+  }
+
+  /// Normalized linear interpolation, cheaper than `slerp` but the angular
+  /// velocity is not constant.
+  pub fn nlerp(self, b:Rotor, t:Float)->Rotor {
+    let b = self.shortest(b);
+    Rotor(&self.0 * Lanes::splat(1.0 - t) + b.0 * Lanes::splat(t)).normalized()
   }
 
-  pub fn approx_eq(&self, other:Rotor, epsilon:f32)->bool {(&self.0 - other.0).abs() < f32x4::splat(epsilon)}
+  // `b` and `b * -1.0` perform the same rotation, pick the one closest to this
+  // rotor. Note that `-b` is the reverse and not the antipodal rotor.
+  pub(crate) fn shortest(&self, b:Rotor)->Rotor { if (&self.0 * b.0).to_array().iter().sum::<Float>() < 0.0 { b * -1.0 } else { b } }
 
-  #[inline] pub fn scalar(&self)->f32 { self.0[0] }
-  #[inline] pub fn e12(&self)->f32 { self.0[3] }
-  #[inline] pub fn e21(&self)->f32 { -self.e12() }
-  #[inline] pub fn e31(&self)->f32 { self.0[2] }
-  #[inline] pub fn e13(&self)->f32 { -self.e31() }
-  #[inline] pub fn e23(&self)->f32 { self.0[1] }
-  #[inline] pub fn e32(&self)->f32 { -self.e23() }
+  pub fn approx_eq(&self, other:Rotor, epsilon:Float)->bool {lanes_close(&self.0, &other.0, epsilon)}
+
+  #[inline] pub fn scalar(&self)->Float { self.0[0] }
+  #[inline] pub fn e12(&self)->Float { self.0[3] }
+  #[inline] pub fn e21(&self)->Float { -self.e12() }
+  #[inline] pub fn e31(&self)->Float { self.0[2] }
+  #[inline] pub fn e13(&self)->Float { -self.e31() }
+  #[inline] pub fn e23(&self)->Float { self.0[1] }
+  #[inline] pub fn e32(&self)->Float { -self.e23() }
 }
 
-impl From<Rotor> for [f32;4] {
+impl From<Rotor> for [Float;4] {
   fn from(r:Rotor) -> Self {
     //TODO r.p1.as_array()
     [r.0[0], r.0[1], r.0[2], r.0[3]]
   }
 }
 
-impl Into<Rotor> for [f32;4] {
-  fn into(self) -> Rotor {
-    Rotor(f32x4::from(self))
+impl From<[Float;4]> for Rotor {
+  fn from(val: [Float;4]) -> Self {
+    Rotor(Lanes::from(val))
   }
 }
 
-impl FnMut<(Plane,)> for Rotor { extern "rust-call" fn call_mut(&mut self, args: (Plane,))->Plane {self.call(args)} }
-impl FnOnce<(Plane,)> for Rotor { type Output = Plane; extern "rust-call" fn call_once(self, args: (Plane,))->Plane { self.call(args) }}
-impl Fn<(Plane,)> for Rotor { extern "rust-call" fn call(&self, args: (Plane,))->Plane { Plane(sw01(&args.0.0, &self.0)) } }
-impl FnMut<(&Plane,)> for Rotor { extern "rust-call" fn call_mut(&mut self, args: (&Plane,))->Plane {self.call(args)} }
-impl FnOnce<(&Plane,)> for Rotor { type Output = Plane; extern "rust-call" fn call_once(self, args: (&Plane,))->Plane { self.call(args) }}
-impl Fn<(&Plane,)> for Rotor { extern "rust-call" fn call(&self, args: (&Plane,))->Plane { Plane(sw01(&args.0.0, &self.0)) } }
+impl Transform<Plane> for Rotor { type Output = Plane; fn apply(&self, p:Plane)->Plane { Plane(kernels::sw_1_0(&kernels::sw_1_0_terms(&self.0), &p.0)) } }
 
 // TODO operator()(plane* in, plane* out, size_t count) const noexcept
 
-impl FnMut<(Branch,)> for Rotor { extern "rust-call" fn call_mut(&mut self, args: (Branch,))->Branch {self.call(args)} }
-impl FnOnce<(Branch,)> for Rotor { type Output = Branch; extern "rust-call" fn call_once(self, args: (Branch,))->Branch { self.call(args) }}
-impl Fn<(Branch,)> for Rotor {
-  extern "rust-call" fn call(&self, args: (Branch,))->Branch {
-    Branch(swrb(&args.0.0, &self.0))
+impl Transform<Branch> for Rotor {
+  type Output = Branch;
+  fn apply(&self, b:Branch)->Branch {
+    Branch(kernels::sw_1_12(&kernels::sw_1_12_terms(&self.0), &b.0, &Lanes::splat(0.0)).0)
   }
 }
 
-impl FnMut<(Line,)> for Rotor { extern "rust-call" fn call_mut(&mut self, args: (Line,))->Line {self.call(args)} }
-impl FnOnce<(Line,)> for Rotor { type Output = Line; extern "rust-call" fn call_once(self, args: (Line,))->Line { self.call(args) }}
-impl Fn<(Line,)> for Rotor {
-  extern "rust-call" fn call(&self, args: (Line,))->Line {
-    let (p1, p2) = swrl(&args.0.p1, &args.0.p2, &self.0);
+impl Transform<Line> for Rotor {
+  type Output = Line;
+  fn apply(&self, l:Line)->Line {
+    let (p1, p2) = kernels::sw_1_12(&kernels::sw_1_12_terms(&self.0), &l.p1, &l.p2);
     Line{p1, p2}
   }
 }
 
+impl Transform<Horizon> for Rotor {
+  type Output = Horizon;
+  fn apply(&self, h:Horizon)->Horizon {
+    let (_, p2) = kernels::sw_1_12(&kernels::sw_1_12_terms(&self.0), &Lanes::splat(0.0), &h.p2);
+    Horizon{p2}
+  }
+}
+
 // TODO operator()(line* in, line* out, size_t count) const noexcept
 
-impl FnMut<(Point,)> for Rotor { extern "rust-call" fn call_mut(&mut self, args: (Point,))->Point {self.call(args)} }
-impl FnOnce<(Point,)> for Rotor { type Output = Point; extern "rust-call" fn call_once(self, args: (Point,))->Point { self.call(args) }}
-impl Fn<(Point,)> for Rotor {
-  extern "rust-call" fn call(&self, args: (Point,))->Point {
-    Point(sw01(&args.0.0, &self.0)) // TODO correct?
+impl Transform<Point> for Rotor {
+  type Output = Point;
+  fn apply(&self, p:Point)->Point {
+    Point(kernels::sw_1_3(&kernels::sw_1_3_terms(&self.0), &p.0))
   }
 }
 
+// The origin is the fixed point of every rotor.
+impl Transform<Origin> for Rotor { type Output = Point; fn apply(&self, _o:Origin)->Point { O } }
+
 // TODO operator()(point* in, point* out, size_t count) const noexcept
 
-impl FnMut<(Direction,)> for Rotor { extern "rust-call" fn call_mut(&mut self, args: (Direction,))->Direction {self.call(args)} }
-impl FnOnce<(Direction,)> for Rotor { type Output = Direction; extern "rust-call" fn call_once(self, args: (Direction,))->Direction { self.call(args) }}
-impl Fn<(Direction,)> for Rotor {
-  extern "rust-call" fn call(&self, args: (Direction,))->Direction {
-    Direction(sw01(&args.0.0, &self.0)) // TODO correct?
+impl Transform<Direction> for Rotor {
+  type Output = Direction;
+  fn apply(&self, d:Direction)->Direction {
+    Direction(kernels::sw_1_3(&kernels::sw_1_3_terms(&self.0), &d.0))
   }
 }
 
 // TODO operator()(direction* in, direction* out, size_t count) const noexcept
 
-impl Add<f32> for Rotor {
+// Conjugating a versor moves its axis and keeps its angle and distance.
+impl Transform<Motor> for Rotor { type Output = Motor; fn apply(&self, m:Motor)->Motor { *self * m * self.reverse() } }
+impl Transform<Rotor> for Rotor { type Output = Rotor; fn apply(&self, r:Rotor)->Rotor { *self * r * self.reverse() } }
+impl Transform<Translator> for Rotor { type Output = Translator; fn apply(&self, t:Translator)->Translator { Translator{p2: self.apply(Horizon{p2: t.p2}).p2} } }
+
+call_transform!(Rotor: Plane, Branch, Line, Horizon, Point, Origin, Direction, Motor, Rotor, Translator);
+
+impl Add<Float> for Rotor {
   type Output = Rotor;
-  fn add(self, s:f32) -> Rotor {
-    Rotor(self.0+f32x4::splat(s))
+  fn add(self, s:Float) -> Rotor {
+    Rotor(self.0+Lanes::splat(s))
   }
 }
 
-impl Add<Rotor> for f32 {
+impl Add<Rotor> for Float {
   type Output = Rotor;
   fn add(self, r:Rotor) -> Rotor {
-    Rotor(r.0+f32x4::splat(self))
+    Rotor(r.0+Lanes::splat(self))
   }
 }
 
@@ -194,22 +282,22 @@ impl SubAssign for Rotor {
   fn sub_assign(&mut self, r: Self) { self.0 = &self.0-r.0 }
 }
 
-impl Mul<f32> for Rotor {
+impl Mul<Float> for Rotor {
   type Output = Rotor;
-  fn mul(self, s: f32) -> Rotor { Rotor(self.0*f32x4::splat(s)) }
+  fn mul(self, s: Float) -> Rotor { Rotor(self.0*Lanes::splat(s)) }
 }
 
-impl MulAssign<f32> for Rotor {
-  fn mul_assign(&mut self, s: f32) { self.0 = &self.0*f32x4::splat(s) }
+impl MulAssign<Float> for Rotor {
+  fn mul_assign(&mut self, s: Float) { self.0 = &self.0*Lanes::splat(s) }
 }
 
-impl Div<f32> for Rotor {
+impl Div<Float> for Rotor {
   type Output = Rotor;
-  fn div(self, s: f32) -> Rotor { Rotor(self.0/f32x4::splat(s)) }
+  fn div(self, s: Float) -> Rotor { Rotor(self.0/Lanes::splat(s)) }
 }
 
-impl DivAssign<f32> for Rotor {
-  fn div_assign(&mut self, s: f32) { self.0 = &self.0/f32x4::splat(s) }
+impl DivAssign<Float> for Rotor {
+  fn div_assign(&mut self, s: Float) { self.0 = &self.0/Lanes::splat(s) }
 }
 
 // Reversion
@@ -229,27 +317,6 @@ impl Neg for Rotor {
 
 // Geometric Product
 
-impl Mul<Rotor> for Rotor {
-  type Output = Rotor;
-  fn mul(self,r:Rotor)->Self::Output {
-    Rotor(gp11(&self.0, &r.0))
-  }
-}
-
-impl Mul<Translator> for Rotor {
-  type Output = Motor;
-  fn mul(self,t:Translator)->Self::Output {
-    Motor{p1: self.0, p2: gprt(&self.0, &t.p2)}
-  }
-}
-
-impl Mul<Motor> for Rotor {
-  type Output = Motor;
-  fn mul(self,m:Motor)->Self::Output {
-    Motor{p1: gp11(&self.0,&m.p1), p2: gp12(&self.0, &m.p2)}
-  }
-}
-
 impl Div<Rotor> for Rotor {
   type Output = Rotor;
   fn div(self,other:Rotor)->Self::Output {
@@ -271,160 +338,193 @@ impl Div<Motor> for Rotor {
   }
 }
 
-impl From<Rotor> for [f32;16] { fn from(r:Rotor)->Self { let m = mat_r(&r.0);unsafe { std::mem::transmute::<[f32x4; 4], [f32; 16]>([m.0, m.1, m.2, m.3]) } } }
+impl From<Branch> for Rotor { fn from(b:Branch)->Rotor { Rotor(zero_first(b.0)) } }
+impl From<Rotor> for [Float;16] { fn from(r:Rotor)->Self { unsafe { std::mem::transmute::<[Lanes; 4], [Float; 16]>(mat_r(&r.0)) } } }
+#[cfg(feature = "mint")] impl From<mint::Quaternion<Float>> for Rotor { fn from(q: mint::Quaternion<Float>)->Rotor { Rotor::from_quaternion(q.into()) } }
+#[cfg(feature = "mint")] impl From<Rotor> for mint::Quaternion<Float> { fn from(r: Rotor)->Self { r.to_quaternion().into() } }
+#[cfg(feature = "mint")] impl From<mint::ColumnMatrix3<Float>> for Rotor { fn from(m: mint::ColumnMatrix3<Float>)->Rotor { Rotor::from_matrix3(m.into()) } }
+#[cfg(feature = "mint")] impl From<Rotor> for mint::ColumnMatrix4<Float> { fn from(r: Rotor)->Self { <[Float;16]>::from(r).into() } }
+#[cfg(feature = "glam")] impl From<Quat> for Rotor { fn from(q: Quat)->Rotor { Rotor::from_quaternion(q.to_array()) } }
+#[cfg(feature = "glam")] impl From<Rotor> for Quat { fn from(r: Rotor)->Self { Quat::from_array(r.to_quaternion()) } }
+#[cfg(feature = "glam")] impl From<Mat3> for Rotor { fn from(m: Mat3)->Rotor { Rotor::from_matrix3(m.to_cols_array()) } }
+#[cfg(feature = "glam")] impl From<Rotor> for Mat4 { fn from(r: Rotor)->Self { Mat4::from_cols_array(&r.into()) } }
 
-/// Euler Angles
+/// Euler Angles, the intrinsic `EulerOrder::XYZ` sequence of `Rotor::from_euler`
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EulerAngles {
-  pub roll:f32,
-  pub pitch:f32,
-  pub yaw:f32
+  pub roll:Float,
+  pub pitch:Float,
+  pub yaw:Float
 }
 
-const PI2:f32 = pi/2.0;
 impl From<Rotor> for EulerAngles {
   fn from(r:Rotor)->Self {
-    let buf:[f32;4] = r.into();
-    let test = buf[1] * buf[2] * buf[3] * buf[0];
-    if test > 0.4999 {
-      return EulerAngles{roll: 2.0 * buf[1].atan2(buf[0]), pitch: PI2, yaw: 0.0};
-    } else if test < -0.4999 {
-      return EulerAngles{roll: -2.0 * buf[1].atan2(buf[0]), pitch: -PI2, yaw: 0.0};
-    }
-    let buf1_2 = buf[1] * buf[1];
-    let buf2_2 = buf[2] * buf[2];
-    let buf3_2 = buf[3] * buf[3];
-
-    let roll = (2.0 * (buf[0] * buf[1] + buf[2] * buf[3])).atan2(1.0 - 2.0 * (buf1_2 + buf2_2));
-    let sinp = 2.0 * (buf[0] * buf[2] - buf[1] * buf[3]);
-    let pitch = if sinp.abs() > 1.0 { PI2.copysign(sinp) } else { sinp.asin() };
-    let yaw = (2.0 * (buf[0] * buf[3] + buf[1] * buf[2])).atan2(1.0 - 2.0 * (buf2_2 + buf3_2));
-
+    let [roll, pitch, yaw] = r.to_euler(EulerOrder::XYZ, EulerFrame::Intrinsic).angles;
     EulerAngles{roll, pitch, yaw}
   }
 }
 
 impl From<EulerAngles> for Rotor { fn from(ea:EulerAngles)->Self { Rotor::from_euler_angles(ea.roll,ea.pitch,ea.yaw) } }
 
+/// The axes of three successive rotations, the six Tait-Bryan sequences
+/// about three different axes and the six proper Euler sequences that repeat
+/// the first axis.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EulerOrder { XYZ, XZY, YXZ, YZX, ZXY, ZYX, XYX, XZX, YXY, YZY, ZXZ, ZYZ }
+
+impl EulerOrder {
+  /// Every sequence
+  pub const ALL:[EulerOrder;12] = [EulerOrder::XYZ, EulerOrder::XZY, EulerOrder::YXZ, EulerOrder::YZX, EulerOrder::ZXY, EulerOrder::ZYX,
+    EulerOrder::XYX, EulerOrder::XZX, EulerOrder::YXY, EulerOrder::YZY, EulerOrder::ZXZ, EulerOrder::ZYZ];
+
+  /// The axes as 0 for x, 1 for y and 2 for z
+  pub fn axes(self)->[usize;3] {
+    match self {
+      EulerOrder::XYZ => [0, 1, 2], EulerOrder::XZY => [0, 2, 1], EulerOrder::YXZ => [1, 0, 2],
+      EulerOrder::YZX => [1, 2, 0], EulerOrder::ZXY => [2, 0, 1], EulerOrder::ZYX => [2, 1, 0],
+      EulerOrder::XYX => [0, 1, 0], EulerOrder::XZX => [0, 2, 0], EulerOrder::YXY => [1, 0, 1],
+      EulerOrder::YZY => [1, 2, 1], EulerOrder::ZXZ => [2, 0, 2], EulerOrder::ZYZ => [2, 1, 2],
+    }
+  }
 
-fn swrl(a1:&f32x4, a2:&f32x4, b:&f32x4)->(f32x4,f32x4) {
-  let b_xwyz = b.xwyz();
-  let b_xzwy = b.xzwy();
-  let b_yxxx = b.yxxx();
-  let b_xxxx = b.xxxx();
-  let mut tmp = b * b;
-  tmp = tmp + b_yxxx * b_yxxx;
-  let b_tmp = b.zwyz();
-  let mut tmp2 = b_tmp * b_tmp;
-  let b_tmp = b.wzwy();
-  tmp2 += b_tmp * b_tmp;
-  tmp -= flip_signs(&tmp2, [true, false, false, false].into());
-  let scale = &[0.0, 2.0, 2.0, 2.0].into();
-  let tmp2 = (b_xxxx * b_xwyz + b * b_xzwy) * scale;
-  let tmp3 = (b * b_xwyz - b_xxxx * b_xzwy) * scale;
-  (tmp * a1 + tmp2 * a1.xzwy() + tmp3 * a1.xwyz(),
-   tmp * a2 + tmp2 * a2.xzwy() + tmp3 * a2.xwyz())
-}
-
-// swmm<false, false, false>
-fn swrb(a:&f32x4,b:&f32x4)->f32x4 {
-  let b_xwyz = b.xwyz();
-  let b_xzwy = b.xzwy();
-  let b_yxxx = b.yxxx();
-  let b_yxxx_2 = b_yxxx * b_yxxx;
-
-  let mut tmp = b * b;
-  tmp += b_yxxx_2;
-  let b_tmp = b.zwyz();
-  let mut tmp2 = b_tmp * b_tmp;
-  let b_tmp = b.wzwy();
-  tmp2 += b_tmp * b_tmp;
-  tmp -= f32x4_xor(&tmp2, &[-0.0, 0.0, 0.0, 0.0].into());
-
-  let b_xxxx = b.xxxx();
-  let scale:f32x4 = [0.0, 2.0, 2.0, 2.0].into();
-  let mut tmp2 = b_xxxx * b_xwyz;
-  tmp2 += b * b_xzwy;
-  tmp2 *= scale;
-
-  let mut tmp3 = b * b_xwyz;
-  tmp3 -= b_xxxx * b_xzwy;
-  tmp3 *= &scale;
-
-  let a_xzwy = a.xzwy();
-  let a_xwyz = a.xwyz();
-
-  let mut out = tmp * a;
-  out += tmp2 * a_xzwy;
-  out += tmp3 * a_xwyz;
-
-  out
+  /// Proper Euler angles repeat the first axis.
+  pub fn is_proper(self)->bool { let [i, _, k] = self.axes(); i == k }
 }
 
-// rotor(point), rotor(plane), rotor(direction): false, false
-pub fn sw01(a:&f32x4, b:&f32x4)->f32x4 {
-  let dc_scale = f32x4::from_array([1.0,2.0,2.0,2.0]);
-  let b_xwyz = b.xwyz();
-  let b_xzwy = b.xzwy();
-  let b_xxxx = b.xxxx();
-
-  let mut tmp1 = b.zxxx() * b.zwyz();
-  tmp1 += b.yzwy() * b.yyzw();
-  tmp1 *= dc_scale;
-
-  let mut tmp2 = b * b_xwyz;
-  let true_falses:mask32x4 = [true,false,false,false].into();
-  tmp2 -= flip_signs(&(b.wxxx() * b.wzwy()), true_falses);
-  tmp2 *= dc_scale;
-
-  let mut tmp3 = b * b;
-  tmp3 -= b_xwyz * b_xwyz;
-  tmp3 += b_xxxx * b_xxxx;
-  tmp3 -= b_xzwy * b_xzwy;
+/// Intrinsic rotations turn about the axes of the body, which move along
+/// with each rotation, extrinsic rotations about the fixed axes of the world.
+/// Intrinsic XYZ is extrinsic ZYX with the angles in reverse.
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EulerFrame { Intrinsic, Extrinsic }
 
-  let mut out:f32x4;
-
-  out = tmp1 * a.xzwy();
-  out += tmp2 * a.xwyz();
-  out += tmp3 * a;
-
-  out
-}
+/// Angles decoded by `Rotor::to_euler`, in the order of the axes.
+///
+/// At gimbal lock the middle angle lines up the first and the third axis and
+/// only the sum of their angles is defined. The third angle is then zero,
+/// the first carries the rotation and `gimbal_lock` is set.
+#[derive(Default,Debug,Clone,Copy,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Euler { pub angles:[Float;3], pub gimbal_lock:bool }
 
-pub fn mat_r(b:&f32x4) ->(f32x4, f32x4, f32x4, f32x4) {
-  let buf = *(b * b).as_array();
-  let b0_2 = buf[0];
-  let b1_2 = buf[1];
-  let b2_2 = buf[2];
-  let b3_2 = buf[3];
-
-  let mut c0 = b * b.xzxx();
-  let mut tmp = b.ywyx() * b.yxwx();
-  tmp = f32x4_xor(&[0.0, -0.0, 0.0, 0.0].into(), &tmp); // TODO why reference?
-  let one_twos:f32x4 = [1f32, 2.0, 2.0, 0.0].into();
-  c0 =  one_twos * (c0 + tmp);
-  c0 = c0 - f32x4::splat(b3_2 + b2_2);
-
-  let c1 = b * b.wywx();
-  let mut tmp = b.zwxx() * b.ywyx();
-  tmp = f32x4_xor(&[0.0, 0.0, -0.0, 0.0].into(), &tmp); // TODO why reference?
-  let tmp1 = f32x4::from_array([2.0, -1.0, 2.0, 0.0]);
-  let mut c1:f32x4 = tmp1 * (c1 + tmp);
-  let duno:f32x4 = [0.0, b0_2+b2_2, 0.0, 0.0].into();
-  c1 = c1 + duno;
-
-  let mut c2:f32x4 = f32x4_xor(&[-0.0, 0.0, -0.0, 0.0].into(), &(b * b.zxzx()));
-  c2 = c2 + (b.yzxx() * b.wwxx());
-  c2 *= <[f32;4]as Into<f32x4>>::into([2.0, 2.0, 1.0, 0.0]);
-  c2 += <[f32;4]as Into<f32x4>>::into([0.0, 0.0, b3_2 - b1_2, 0.0]);
-
-  // TODO why is c3 here
-  // c3 = _mm_add_ps(c3, _mm_set_ps(b0_2 + b1_2 + b2_2 + b3_2, 0.f, 0.f, 0.f));
-  let c3 = [0.0, 0.0, 0.0, b0_2 + b1_2 + b2_2 + b3_2].into();
-  (c0,c1,c2,c3)
+impl Rotor {
+  /// Three rotations about the axes of `order` by `angles`, each turning
+  /// like `rotor` does.
+  pub fn from_euler(order:EulerOrder, frame:EulerFrame, angles:[Float;3])->Rotor {
+    let [r1, r2, r3] = [0, 1, 2].map(|n| {
+      let mut axis = [0.0; 3];
+      axis[order.axes()[n]] = 1.0;
+      rotor(angles[n], axis[0], axis[1], axis[2])
+    });
+    // The rotor on the right acts first
+    match frame { EulerFrame::Intrinsic => r1 * r2 * r3, EulerFrame::Extrinsic => r3 * r2 * r1 }
+  }
+
+  /// The angles of `from_euler` that give this rotor, which need not be
+  /// normalized. The first and third are in $[-\pi, \pi]$, the middle one is
+  /// in $[0, \pi]$ for proper Euler and $[-\frac{\pi}{2}, \frac{\pi}{2}]$ for
+  /// Tait-Bryan angles.
+  pub fn to_euler(&self, order:EulerOrder, frame:EulerFrame)->Euler {
+    // The direct method from the quaternion by Bernardes and Viollet, "Quaternion
+    // to Euler angles conversion: A direct, general and computationally
+    // efficient method" (2022). It is written for extrinsic sequences, an
+    // intrinsic sequence is the reversed extrinsic one.
+    let extrinsic = frame == EulerFrame::Extrinsic;
+    let [i, j, k] = order.axes();
+    let (i, k) = if extrinsic { (i, k) } else { (k, i) };
+    let proper = i == k;
+    let k = if proper { 3 - i - j } else { k };
+    // Even or odd permutation of the axes
+    let sign = if (i + 1) % 3 == j { 1.0 } else { -1.0 };
+    let q = self.to_quaternion();
+    let (a, b, c, d) = if proper { (q[3], q[i], q[j], q[k] * sign) }
+      else { (q[3] - q[j], q[i] + q[k] * sign, q[j] + q[3], q[k] * sign - q[i]) };
+
+    let (first, third) = if extrinsic { (0, 2) } else { (2, 0) };
+    let mut angles = [0.0; 3];
+    angles[1] = 2.0 * c.hypot(d).atan2(a.hypot(b));
+    let (half_sum, half_diff) = (b.atan2(a), d.atan2(c));
+    let tolerance = 16.0 * Float::EPSILON;
+    let (at_zero, at_pi) = (angles[1].abs() <= tolerance, (angles[1] - pi).abs() <= tolerance);
+    if at_zero {
+      angles[0] = 2.0 * half_sum;
+    } else if at_pi {
+      angles[0] = 2.0 * half_diff * if extrinsic { -1.0 } else { 1.0 };
+    } else {
+      angles[first] = half_sum - half_diff;
+      angles[third] = half_sum + half_diff;
+    }
+    if !proper {
+      angles[third] *= sign;
+      angles[1] -= pi / 2.0;
+    }
+    // Into [-π, π], and from the counterclockwise angles of the quaternion to
+    // the clockwise ones of rotors
+    let wrap = |a:Float| if a < -pi { a + tau } else if a > pi { a - tau } else { a };
+    let mut angles = angles.map(|a| -wrap(a));
+    // That turns the proper middle angle negative, turning the outer axes
+    // half way around turns it back.
+    if proper {
+      angles = if at_zero || at_pi { [angles[0], angles[1].abs(), 0.0] }
+        else { [wrap(angles[0] + pi), -angles[1], wrap(angles[2] + pi)] };
+    }
+    Euler{angles, gimbal_lock: at_zero || at_pi}
+  }
+}
+
+
+// Columns of the matrix of the point sandwich, the images of x, y, z and w
+// with the rows in the same order. The terms are per input lane in w, x, y, z.
+fn mat_r(b:&Lanes)->[Lanes;4] {
+  let t = kernels::sw_1_3_terms(b);
+  [t[1].yzwx(), t[2].yzwx(), t[3].yzwx(), t[0].yzwx()]
+}
+
+products! {
+  Rotor * Line = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor * Branch = Rotor, gp_1_1(0; 0) -> p1 => Rotor(p1);
+  Rotor * Horizon = Motor, gp_1_2(0; p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Rotor * Direction = Flector, gp_1_3(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Rotor * Dual as Motor = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor * Rotor = Rotor, gp_1_1(0; 0) -> p1 => Rotor(p1);
+  Rotor * Translator as Motor = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor * Motor = Motor, gp_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor ^ Plane = Flector, ext_1_0(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Rotor ^ Line = Motor, ext_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor ^ Branch = Branch, ext_1_1(0; 0) -> p1 => Branch(p1);
+  Rotor ^ Horizon = Motor, ext_1_2(0; p2) -> p2 => Motor{p1: Lanes::splat(0.0), p2};
+  Rotor ^ Point = Point, ext_1_3(0; 0) -> p3 => Point(p3);
+  Rotor ^ Direction = Point, ext_1_3(0; 0) -> p3 => Point(p3);
+  Rotor ^ Dual as Motor = Motor, ext_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor ^ Rotor = Rotor, ext_1_1(0; 0) -> p1 => Rotor(p1);
+  Rotor ^ Translator as Motor = Motor, ext_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor ^ Motor = Motor, ext_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor & Line = Float, reg_1_12(0; p1, p2) -> p1 => p1[0];
+  Rotor & Horizon = Float, reg_1_2(0; p2) -> p1 => p1[0];
+  Rotor & Point = Plane, reg_1_3(0; 0) -> p0 => Plane(p0);
+  Rotor & Direction = Plane, reg_1_3(0; 0) -> p0 => Plane(p0);
+  Rotor & Dual as Motor = Rotor, reg_1_12(0; p1, p2) -> p1 => Rotor(p1);
+  Rotor & Translator as Motor = Float, reg_1_12(0; p1, p2) -> p1 => p1[0];
+  Rotor & Motor = Rotor, reg_1_12(0; p1, p2) -> p1 => Rotor(p1);
+  Rotor | Plane = Plane, dot_1_0(0; 0) -> p0 => Plane(p0);
+  Rotor | Line = Motor, dot_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor | Branch = Rotor, dot_1_1(0; 0) -> p1 => Rotor(p1);
+  Rotor | Horizon = Horizon, dot_1_2(0; p2) -> p2 => Horizon{p2};
+  Rotor | Point = Flector, dot_1_3(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Rotor | Direction = Flector, dot_1_3(0; 0) -> (p0, p3) => Flector{p0, p3};
+  Rotor | Dual as Motor = Motor, dot_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor | Rotor = Rotor, dot_1_1(0; 0) -> p1 => Rotor(p1);
+  Rotor | Translator as Motor = Motor, dot_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor | Motor = Motor, dot_1_12(0; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Rotor / Plane = Flector;
+  Rotor / Line = Motor;
+  Rotor / Branch = Rotor;
+  Rotor / Point = Flector;
+  Rotor / Dual = Motor;
 }
 
-
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -445,14 +545,31 @@ mod tests {
       0.0, pi.cos(), -pi.sin(), 0.0,
       0.0, pi.sin(), pi.cos(), 0.0,
       0.0, 0.0, 0.0, 1.0];
-    assert_eq!(<[f32;16]>::from(r), m);
+    assert_eq!(<[Float;16]>::from(r), m);
   }
 
-  const EPSILON: f32 = 0.000_001;
-  fn approx_eq1(a: f32, b: f32) {
+  const EPSILON: Float = 0.000_001;
+  fn approx_eq1(a: Float, b: Float) {
     assert!((a - b).abs() < EPSILON, "{:?} ≉ {:?}", a, b);
   }
 
+  #[test] fn rotor_slerp() {
+    let (a, b) = (rotor(0.0, 0.0, 0.0, 1.0), rotor(pi/2.0, 0.0, 0.0, 1.0));
+    let c = rotor(pi/4.0, 0.0, 0.0, 1.0);
+    for (x, y) in [(a.slerp(b, 0.5), c), (a.slerp(b * -1.0, 0.5), c), (a.slerp(b, 0.0), a), (a.slerp(b, 1.0), b), (b.slerp(b, 0.5), b)] { close(x, y) }
+    let n = a.nlerp(b * -1.0, 0.5);
+    close(n, c);
+  }
+
+  // Keys closer than the rounding error of the angle used to give NaN
+  #[test] fn rotor_slerp_nearly_equal_keys() {
+    let a = rotor(1.0, 1.0, 2.0, 3.0);
+    for angle in [1e-3, 1e-5, 1e-7, 0.0] {
+      let (b, c) = (rotor(angle, 0.0, 1.0, 0.0) * a, rotor(angle / 2.0, 0.0, 1.0, 0.0) * a);
+      close(a.slerp(b, 0.5), c);
+    }
+  }
+
   #[test] fn euler_angles() {
     let r1 = rotor(1.0, 1.0, 0.0, 0.0) * rotor(1.0, 0.0, 1.0, 0.0) * rotor(1.0, 0.0, 0.0, 1.0);
     let ea = EulerAngles::from(r1);
@@ -475,10 +592,22 @@ mod tests {
     approx_eq1(ea1.yaw, ea2.yaw);
   }
 
+  #[test] fn euler_angles_round_trip() {
+    for angles in [[0.4, pi / 2.0, 0.1], [0.4, -pi / 2.0, 0.1], [-2.0, 1.0, 3.0], [0.0, 0.0, 0.0]] {
+      let r = Rotor::from_euler(EulerOrder::XYZ, EulerFrame::Intrinsic, angles);
+      let ea = EulerAngles::from(r);
+      close(Rotor::from(ea), r);
+      close(Rotor::from(EulerAngles::from(Rotor::from(ea))), r);
+    }
+    let ea = EulerAngles::from(Rotor::from_euler(EulerOrder::XYZ, EulerFrame::Intrinsic, [0.4, pi / 2.0, 0.1]));
+    close(ea.pitch, pi / 2.0);
+    close(ea.roll - ea.yaw, 0.3);
+  }
+
   #[test] fn rotor_line() {
     let r = Rotor::load_normalized([1.0, 4.0, -3.0, 2.0]);
     let l = line(-1.0, 2.0, -3.0, -6.0, 5.0, 4.0);
-    let k = r(l);
+    let k = r.apply(l);
     approx_eq([k.e01(), k.e02(), k.e03(), 0.0], [-110.0, 20.0, 10.0, 0.0]);
     approx_eq([k.e12(), k.e31(), k.e23(), 0.0], [-240.0, 102.0, -36.0, 0.0]);
   }
@@ -486,8 +615,8 @@ mod tests {
   #[test] fn rotor_point() {
     let r = rotor(pi*0.5, 0.0, 0.0, 1.0);
     let a = point(1.0, 0.0, 0.0);
-    let b:Point = r(a);
-    approx_eq([b.x(), b.y(), b.z(), 0.0], [0f32, -1.0, 0.0, 0.0]);
+    let b:Point = r.apply(a);
+    approx_eq([b.x(), b.y(), b.z(), 0.0], [0.0, -1.0, 0.0, 0.0]);
   }
 
   #[test] fn rotor_sqrt() {
@@ -497,6 +626,19 @@ mod tests {
     assert!(s.approx_eq(r, EPSILON));
   }
 
+  #[test] fn log_of_degenerate_rotors() {
+    for r in [rotor(0.0, 1.0, 0.0, 0.0), rotor(1e-6, 0.0, 1.0, 0.0), rotor(pi, 0.0, 0.0, 1.0), rotor(0.3, 1.0, 1.0, 0.0) * 4.0] {
+      let l = r.log();
+      close(l.exp(), r.normalized());
+    }
+    assert_eq!(Rotor::from([-1.0, 0.0, 0.0, 0.0]).log(), branch(0.0, 0.0, 0.0));
+    assert_eq!(Rotor::from([-1.0, 0.0, 0.0, 0.0]).sqrt(), Rotor::from([1.0, 0.0, 0.0, 0.0]));
+    for angle in [2.0 * pi - 1e-6, 2.0 * pi - 1e-9, 2.0 * pi + 1e-6] {
+      let r = rotor(angle, 0.0, 1.0, 0.0);
+      close(r.sqrt() * r.sqrt(), r);
+    }
+  }
+
   #[test] fn normalize_rotor() {
     let r:Rotor = [4.0, -3.0, 3.0, 28.0].into();
     r.normalized();
@@ -1,20 +1,22 @@
-use std::{fmt::{Display, Formatter, Result},simd::{f32x4,mask32x4,SimdFloat,simd_swizzle as swizzle,Which::{First,Second}},ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg, Fn}};
-use crate::{Plane, Line, Point, Motor, Rotor, Horizon,maths::*};
+use std::{ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Not, Neg}};
+use super::{Plane, Line, Branch, Horizon, Point, Origin, Direction, Motor, Rotor, Transform, Dual, Flector, maths::*};
 
 /// ae₀₁ + be₀₂ + ce₀₃
-pub fn translator(delta:f32,x:f32,y:f32,z:f32)->Translator {
+pub fn translator(delta:Float,x:Float,y:Float,z:Float)->Translator {
   Translator::new(delta,x,y,z)
 }
 
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-pub struct Translator { pub p2:f32x4 }
+pub struct Translator { pub(crate) p2:Lanes }
+
+serde_blades!(Translator { e01, e02, e03 } => |[a, b, c]| Translator{p2: Lanes::from_array([0.0, a, b, c])});
 
 impl Translator {
-  pub fn new(delta:f32,x:f32,y:f32,z:f32)->Translator {
-    let norm:f32 = (x * x + y * y + z * z).sqrt();
-    let inv_norm:f32 = 1.0 / norm;
+  pub fn new(delta:Float,x:Float,y:Float,z:Float)->Translator {
+    let norm:Float = (x * x + y * y + z * z).sqrt();
+    let inv_norm:Float = 1.0 / norm;
     let half_d = -0.5 * delta;
-    let p2 = f32x4::splat(half_d) * f32x4::from_array([0.0,x,y,z]) * f32x4::from_array([0.0,inv_norm,inv_norm,inv_norm]);
+    let p2 = Lanes::splat(half_d) * Lanes::from_array([0.0,x,y,z]) * Lanes::from_array([0.0,inv_norm,inv_norm,inv_norm]);
     Translator{p2}
   }
 
@@ -26,7 +28,7 @@ impl Translator {
   /// The translator data loaded this way *must* be normalized. That is,
   /// the quantity $`-\sqrt{a^2 + b^2 + c^2}`$ must be half the desired
   /// displacement.
-  pub fn load_normalized(data:[f32;4])->Translator {
+  pub fn load_normalized(data:[Float;4])->Translator {
     Translator{ p2: data.into()}
   }
 
@@ -37,14 +39,21 @@ impl Translator {
   }
 
   pub fn inverse(&self)->Translator {
-    Translator{p2: flip_signs(&self.p2, mask32x4::from_array([false,true,true,true]))}
+    Translator{p2: flip_signs(&self.p2, Mask::from_array([false,true,true,true]))}
   }
 
+  /// Same as the inverse, the scalar of a translator is always 1.
+  pub fn reverse(&self)->Translator { self.inverse() }
+
   // Compute the logarithm of the translator, producing a horizon axis.
   // In practice, the logarithm of a translator is simply the horizon partition
   // (without the scalar $1$).f
   // pub fn log(&self)->IdealLine { IdealLine{p2: self.p2} } TODO
 
+  // The translator by the vector (x, y, z) and back.
+  pub(crate) fn from_offset([x,y,z]:[Float;3])->Translator { Translator{p2: Lanes::from_array([0.0, -0.5*x, -0.5*y, -0.5*z])} }
+  #[cfg(any(feature = "glam", feature = "mint"))] pub(crate) fn offset(&self)->[Float;3] { [-2.0*self.p2[1], -2.0*self.p2[2], -2.0*self.p2[3]] }
+
   /// Compute the square root of the provided translator $t$.
   #[inline] pub fn sqrt(&self)->Translator { *self * 0.5 }
 
@@ -53,61 +62,92 @@ impl Translator {
   /// (without the scalar $1$).
   pub fn log(&self)-> Horizon { Horizon {p2: self.p2} }
 
-  pub fn approx_eq(&self, other:Translator, epsilon:f32)->bool {(&self.p2 - other.p2).abs() < f32x4::splat(epsilon)}
+  /// Interpolate from this translator at `t = 0` to `b` at `t = 1`. The
+  /// logarithm of a translator is linear so this is exact.
+  pub fn slerp(self, b:Translator, t:Float)->Translator {
+    Translator{p2: &self.p2 * Lanes::splat(1.0 - t) + b.p2 * Lanes::splat(t)}
+  }
+
+  /// Same as `slerp`, a translator has no double cover to account for.
+  pub fn nlerp(self, b:Translator, t:Float)->Translator { self.slerp(b, t) }
+
+  pub fn approx_eq(&self, other:Translator, epsilon:Float)->bool {lanes_close(&self.p2, &other.p2, epsilon)}
 
-  #[inline] pub fn scalar(&self)->f32 { 1.0 }
-  #[inline] pub fn e01(&self)->f32 { self.p2[1] }
-  #[inline] pub fn e10(&self)->f32 { -self.e01() }
-  #[inline] pub fn e02(&self)->f32 { self.p2[2] }
-  #[inline] pub fn e20(&self)->f32 { -self.e02() }
-  #[inline] pub fn e03(&self)->f32 { self.p2[3] }
-  #[inline] pub fn e30(&self)->f32 { -self.e03() }
+  #[inline] pub fn scalar(&self)->Float { 1.0 }
+  #[inline] pub fn e01(&self)->Float { self.p2[1] }
+  #[inline] pub fn e10(&self)->Float { -self.e01() }
+  #[inline] pub fn e02(&self)->Float { self.p2[2] }
+  #[inline] pub fn e20(&self)->Float { -self.e02() }
+  #[inline] pub fn e03(&self)->Float { self.p2[3] }
+  #[inline] pub fn e30(&self)->Float { -self.e03() }
 }
 
-impl Display for Translator {
-  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-    write!(f, "{}e01 + {}e02 + {}e03 + 1e0123", self.e01(), self.e02(), self.e03())
-  }
+/// The translation by a vector
+#[cfg(feature = "mint")] impl From<mint::Vector3<Float>> for Translator { fn from(v: mint::Vector3<Float>)->Translator { Translator::from_offset(v.into()) } }
+#[cfg(feature = "mint")] impl From<Translator> for mint::Vector3<Float> { fn from(t: Translator)->Self { t.offset().into() } }
+/// The translation by a vector
+#[cfg(feature = "glam")] impl From<Vec3> for Translator { fn from(v: Vec3)->Translator { Translator::from_offset(v.to_array()) } }
+#[cfg(feature = "glam")] impl From<Translator> for Vec3 { fn from(t: Translator)->Self { Vec3::from_array(t.offset()) } }
+
+
+// Conjugates a plane $p$ with this translator and returns the result $tp\widetilde{t}$.
+impl Transform<Plane> for Translator {
+  type Output = Plane;
+  fn apply(&self, p:Plane)->Plane { Plane(kernels::sw_2_0(&kernels::sw_2_0_terms(&self.p2), &p.0)) }
 }
 
-impl FnMut<(Plane,)> for Translator { extern "rust-call" fn call_mut(&mut self, args: (Plane,))->Plane { self.call(args) }}
-impl FnOnce<(Plane,)> for Translator { type Output = Plane; extern "rust-call" fn call_once(self, args: (Plane,))->Plane {self.call(args)} }
-// Conjugates a plane $p$ with this translator and returns the result t*p*!t TODO check manual if this is right
-impl Fn<(Plane,)> for Translator {
-  extern "rust-call" fn call(&self, args: (Plane,))->Plane {
-    let tmp:f32x4 = &self.p2 + f32x4::from_array([1.0,1.0,1.0,1.0]);
-    Plane(sw02(&args.0.0, &tmp))
+impl Transform<Line> for Translator {
+  type Output = Line;
+  fn apply(&self, l:Line)->Line {
+    let (p1,p2) = kernels::sw_2_12(&kernels::sw_2_12_terms(&self.p2), &l.p1, &l.p2);
+    Line{p1,p2}
   }
 }
 
-impl FnMut<(Line,)> for Translator { extern "rust-call" fn call_mut(&mut self, args: (Line,))->Line { self.call(args) }}
-impl FnOnce<(Line,)> for Translator { type Output = Line; extern "rust-call" fn call_once(self, args: (Line,))->Line {self.call(args)} }
-impl Fn<(Line,)> for Translator {
-  extern "rust-call" fn call(&self, args: (Line,))->Line {
-    let (p1,p2) = swl2(&args.0.p1, &args.0.p2, &self.p2); // TODO p1 is just a, isn't this unnecessary
-    Line{p1,p2 }
+// A translated branch no longer passes through the origin.
+impl Transform<Branch> for Translator {
+  type Output = Line;
+  fn apply(&self, b:Branch)->Line {
+    let (p1,p2) = kernels::sw_2_12(&kernels::sw_2_12_terms(&self.p2), &b.0, &Lanes::splat(0.0));
+    Line{p1,p2}
   }
 }
 
-impl FnMut<(Point,)> for Translator { extern "rust-call" fn call_mut(&mut self, args: (Point,))->Point { self.call(args) }}
-impl FnOnce<(Point,)> for Translator { type Output = Point; extern "rust-call" fn call_once(self, args: (Point,))->Point {self.call(args)} }
-impl Fn<(Point,)> for Translator {
-  extern "rust-call" fn call(&self, args: (Point,))->Point {
-    Point(sw32(&args.0.0, &self.p2))
+// Lines at infinity are invariant under translation.
+impl Transform<Horizon> for Translator { type Output = Horizon; fn apply(&self, h:Horizon)->Horizon { h } }
+
+impl Transform<Point> for Translator {
+  type Output = Point;
+  fn apply(&self, p:Point)->Point {
+    Point(kernels::sw_2_3(&kernels::sw_2_3_terms(&self.p2), &p.0))
   }
 }
 
-impl Add<f32> for Translator {
+// The origin is the w lane of a point, its image is the first term of the point sandwich.
+impl Transform<Origin> for Translator { type Output = Point; fn apply(&self, _o:Origin)->Point { Point(kernels::sw_2_3_terms(&self.p2)[0]) } }
+
+// Directions are invariant under translation.
+impl Transform<Direction> for Translator { type Output = Direction; fn apply(&self, d:Direction)->Direction { d } }
+
+// Conjugating a versor moves its axis and keeps its angle and distance.
+impl Transform<Motor> for Translator { type Output = Motor; fn apply(&self, m:Motor)->Motor { *self * m * self.reverse() } }
+impl Transform<Rotor> for Translator { type Output = Motor; fn apply(&self, r:Rotor)->Motor { *self * r * self.reverse() } }
+// Translations commute.
+impl Transform<Translator> for Translator { type Output = Translator; fn apply(&self, t:Translator)->Translator { t } }
+
+call_transform!(Translator: Plane, Line, Branch, Horizon, Point, Origin, Direction, Motor, Rotor, Translator);
+
+impl Add<Float> for Translator {
   type Output = Translator;
-  fn add(self, s:f32) -> Translator {
-    Translator{ p2: self.p2+f32x4::splat(s) }
+  fn add(self, s:Float) -> Translator {
+    Translator{ p2: self.p2+Lanes::splat(s) }
   }
 }
 
-impl Add<Translator> for f32 {
+impl Add<Translator> for Float {
   type Output = Translator;
   fn add(self, t:Translator) -> Translator {
-    Translator{ p2: t.p2+f32x4::splat(self) }
+    Translator{ p2: t.p2+Lanes::splat(self) }
   }
 }
 
@@ -124,6 +164,32 @@ impl AddAssign for Translator {
   }
 }
 
+// Only the ideal part of the translator changes, the scalar stays one.
+impl Add<Horizon> for Translator {
+  type Output = Translator;
+  fn add(self, h: Horizon) -> Translator {
+    Translator{ p2: self.p2+h.p2 }
+  }
+}
+
+impl Sub<Horizon> for Translator {
+  type Output = Translator;
+  fn sub(self, h: Horizon) -> Translator {
+    Translator{ p2: self.p2-h.p2 }
+  }
+}
+
+impl Add<Translator> for Horizon {
+  type Output = Translator;
+  fn add(self, t: Translator) -> Translator { t + self }
+}
+
+// The scalar becomes minus one, which is no translator.
+impl Sub<Translator> for Horizon {
+  type Output = Motor;
+  fn sub(self, t: Translator) -> Motor { Motor::from(self) - Motor::from(t) }
+}
+
 impl Sub<Translator> for Translator {
   type Output = Translator;
   fn sub(self, other: Translator) -> Translator {
@@ -137,36 +203,36 @@ impl SubAssign for Translator {
   }
 }
 
-impl Mul<Translator> for f32 {
+impl Mul<Translator> for Float {
   type Output = Translator;
   fn mul(self, t: Translator) -> Translator {
     t*self
   }
 }
 
-impl Mul<f32> for Translator {
+impl Mul<Float> for Translator {
   type Output = Translator;
-  fn mul(self, s: f32) -> Translator {
-    Translator{ p2: self.p2*f32x4::splat(s) }
+  fn mul(self, s: Float) -> Translator {
+    Translator{ p2: self.p2*Lanes::splat(s) }
   }
 }
 
-impl MulAssign<f32> for Translator {
-  fn mul_assign(&mut self, s: f32) {
-    self.p2 *= f32x4::splat(s)
+impl MulAssign<Float> for Translator {
+  fn mul_assign(&mut self, s: Float) {
+    self.p2 *= Lanes::splat(s)
   }
 }
 
-impl Div<f32> for Translator {
+impl Div<Float> for Translator {
   type Output = Translator;
-  fn div(self, s: f32) -> Translator {
-    Translator{ p2:self.p2/f32x4::splat(s) }
+  fn div(self, s: Float) -> Translator {
+    Translator{ p2:self.p2/Lanes::splat(s) }
   }
 }
 
-impl DivAssign<f32> for Translator {
-  fn div_assign(&mut self, s: f32) {
-    self.p2 /= f32x4::splat(s)
+impl DivAssign<Float> for Translator {
+  fn div_assign(&mut self, s: Float) {
+    self.p2 /= Lanes::splat(s)
   }
 }
 
@@ -182,31 +248,17 @@ impl Neg for Translator {
 impl Not for Translator {
   type Output = Self;
   fn not(self)->Self::Output {
-    Translator { p2: flip_signs(&self.p2, mask32x4::from_array([false,true,true,true])) }
+    Translator { p2: flip_signs(&self.p2, Mask::from_array([false,true,true,true])) }
   }
 }
 
 // Geometric Product
-impl Mul<Rotor> for Translator {
-  type Output = Motor;
-  fn mul(self, r: Rotor) -> Self::Output {
-    let p2 = gptr(&r.0, &self.p2);
-    Motor{p1: r.0, p2}
-  }
-}
 impl Mul<Translator> for Translator {
   type Output = Translator;
   fn mul(self, other: Translator) -> Translator {
     self + other
   }
 }
-impl Mul<Motor> for Translator {
-  type Output = Motor;
-  fn mul(self, m: Motor) -> Self::Output {
-    let p2 = gptr(&m.p1, &self.p2);
-    Motor{p1: m.p1.into(), p2: p2 + m.p2}
-  }
-}
 impl Div<Translator> for Translator {
   type Output = Translator;
   fn div(self, other: Translator) -> Self::Output {
@@ -214,93 +266,67 @@ impl Div<Translator> for Translator {
   }
 }
 
-// Apply a translator to a line
-// a := p1 input
-// d := p2 input
-// c := p2 translator
-// out points to the start address of a line (p1, p2)
-fn swl2(a:&f32x4, d:&f32x4, c:&f32x4)->(f32x4, f32x4) {
-  // a0 + a1 e23 + a2 e31 + a3 e12 +
-  //
-  // (2a0 c0 + d0) e0123 +
-  // (2(a2 c3 - a3 c2 - a1 c0) + d1) e01 +
-  // (2(a3 c1 - a1 c3 - a2 c0) + d2) e02 +
-  // (2(a1 c2 - a2 c1 - a3 c0) + d3) e03
-  let mut p2_out = a.xzwy() * c.xwyz();
-  // Add and subtract the same quantity in the low component to produce a cancellation
-  p2_out -= a.xwyz() * c.xzwy();
-  p2_out -= flip_signs(&(a * c.xxxx()), mask32x4::from_array([true, false, false, false]));
-  (a.clone(), p2_out + p2_out + d)
-}
-
-// Apply a translator to a point.
-// Assumes e0123 component of p2 is exactly 0
-// p2: (e0123, e01, e02, e03)
-// p3: (e123, e032, e013, e021)
-// b * a * ~b
-pub fn sw32(a:&f32x4, b:&f32x4)->f32x4 {
-  // a0 e123 +
-  // (a1 - 2 a0 b1) e032 +
-  // (a2 - 2 a0 b2) e013 +
-  // (a3 - 2 a0 b3) e021
-  a + a.xxxx() * b * f32x4::from_array([0.0, -2.0, -2.0, -2.0])
-}
-
-
-// Apply a translator to a plane.
-// Assumes e0123 component of p2 is exactly 0
-// p0: (e0, e1, e2, e3)
-// p2: (e0123, e01, e02, e03)
-// b * a * ~b
-// The low component of p2 is expected to be the scalar component instead
- fn sw02(a:&f32x4, b:&f32x4)->f32x4 {
-  // (a0 b0^2 + 2a1 b0 b1 + 2a2 b0 b2 + 2a3 b0 b3) e0 +
-  // (a1 b0^2) e1 +
-  // (a2 b0^2) e2 +
-  // (a3 b0^2) e3
-  //
-  // Because the plane is projectively equivalent on multiplication by a
-  // scalar, we can divide the result through by b0^2
-  //
-  // (a0 + 2a1 b1 / b0 + 2a2 b2 / b0 + 2a3 b3 / b0) e0 +
-  // a1 e1 +
-  // a2 e2 +
-  // a3 e3
-  //
-  // The additive term clearly contains a dot product between the plane's
-  // normal and the translation axis, demonstrating that the plane
-  // "doesn't care" about translations along its span. More precisely, the
-  // plane translates by the projection of the translator on the plane's
-  // normal.
-
-  // a1*b1 + a2*b2 + a3*b3 stored in the low component of tmp
-  let tmp = hi_dp(a, b);
-  let mut inv_b = rcp_nr1(b);
-  // 2 / b0
-  inv_b = add_ss(&inv_b, &inv_b);
-  inv_b = swizzle!(inv_b.clone(), f32x4::splat(0.0), [First(0),Second(1),Second(2),Second(3)]); // TODO faster?
-  a + mul_ss(&tmp, &inv_b)
-}
-
 // Two translations are additive
 // (1-t2e/2) (1-t1e/2)       =
 // 1 - (t2+t1)e/2 + t2et1e/4 = , because e^2 = 0
 // 1 - (t2+t1)e/2
 
+products! {
+  Translator as Motor * Line = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor * Branch = Motor, gp_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor * Horizon = Horizon, gp_12_2(p1, p2; p2) -> p2 => Horizon{p2};
+  Translator as Motor * Direction = Flector, gp_12_3(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Translator as Motor * Dual as Motor = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor * Rotor = Motor, gp_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor * Motor = Motor, gp_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor ^ Plane = Flector, ext_12_0(p1, p2; 0) -> (p0, p3) => Flector{p0, p3};
+  Translator as Motor ^ Line = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor ^ Branch = Motor, ext_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor ^ Horizon = Horizon, ext_12_2(p1, p2; p2) -> p2 => Horizon{p2};
+  Translator as Motor ^ Point = Point, ext_12_3(p1, p2; 0) -> p3 => Point(p3);
+  Translator as Motor ^ Direction = Point, ext_12_3(p1, p2; 0) -> p3 => Point(p3);
+  Translator as Motor ^ Dual as Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor ^ Rotor = Motor, ext_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor ^ Translator as Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor ^ Motor = Motor, ext_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor & Line = Float, reg_12_12(p1, p2; p1, p2) -> (p1, _) => p1[0];
+  Translator as Motor & Branch = Float, reg_12_1(p1, p2; 0) -> p1 => p1[0];
+  Translator as Motor & Point = Plane, reg_12_3(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Translator as Motor & Direction = Plane, reg_12_3(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Translator as Motor & Dual as Motor = Motor, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor & Rotor = Float, reg_12_1(p1, p2; 0) -> p1 => p1[0];
+  Translator as Motor & Motor = Motor, reg_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor | Plane = Plane, dot_12_0(p1, p2; 0) -> (p0, _) => Plane(p0);
+  Translator as Motor | Line = Line, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Line{p1, p2};
+  Translator as Motor | Branch = Branch, dot_12_1(p1, p2; 0) -> (p1, _) => Branch(p1);
+  Translator as Motor | Horizon = Horizon, dot_12_2(p1, p2; p2) -> p2 => Horizon{p2};
+  Translator as Motor | Point = Point, dot_12_3(p1, p2; 0) -> (_, p3) => Point(p3);
+  Translator as Motor | Direction = Point, dot_12_3(p1, p2; 0) -> (_, p3) => Point(p3);
+  Translator as Motor | Dual as Motor = Motor, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor | Rotor = Motor, dot_12_1(p1, p2; 0) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor | Translator as Motor = Motor, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator as Motor | Motor = Motor, dot_12_12(p1, p2; p1, p2) -> (p1, p2) => Motor{p1, p2};
+  Translator / Plane = Flector;
+  Translator / Line = Motor;
+  Translator / Branch = Motor;
+  Translator / Point = Flector;
+  Translator / Dual = Motor;
+  Translator / Rotor = Motor;
+  Translator / Motor = Motor;
+}
+
 #[cfg(test)]
 mod tests {
-  use std::simd::f32x4;
   use super::*;
-  use crate::*;
+  use super::super::*;
   const ORIGIN:Point = point(0.0,0.0,0.0);
-  const EPSILON: f32 = 0.02;
-
-  #[test]
-  fn simd_sandwich() {
-    let a = f32x4::from_array([1.0, 2.0, 3.0, 4.0]);
-    let b = f32x4::from_array([-4.0, -3.0, -2.0, -1.0]);
-    let c = sw02(&a, &b);
-    assert_eq!([c[0], c[1], c[2], c[3]], [9.0, 2.0, 3.0, 4.0]);
+  const EPSILON: Float = 0.02;
+
+  #[test] fn translator_plane() {
+    let t = translator(1.0, 0.0, 0.0, 1.0);
+    let p = t.apply(plane(0.0, 0.0, 1.0, 0.0));
+    assert_eq!([p.a(), p.b(), p.c(), p.d()], [0.0, 0.0, 1.0, -1.0]);
+    assert!(p.approx_eq(plane(0.0, 0.0, 1.0, -1.0), EPSILON));
   }
 
   #[test] fn translator_from_points() {
@@ -311,22 +337,29 @@ mod tests {
     // assert_eq!(a_to_b);
 
     // translate a to b
-    let c:Point = a_to_b(a);
+    let c:Point = a_to_b.apply(a);
     assert!(c.approx_eq(b, EPSILON));
     //translate halfway between a and b (origin)
-    let d:Point = (a_to_b*0.5)(a);
+    let d:Point = (a_to_b*0.5).apply(a);
     assert!(d.approx_eq(ORIGIN, EPSILON));
 
     let t = Translator::new(4.0,1.0,0.0,1.0);
-    let e:Point = t(ORIGIN);
-    let f = point(8f32.sqrt(), 0.0, 8f32.sqrt());
+    let e:Point = t.apply(ORIGIN);
+    let f = point(Float::sqrt(8.0), 0.0, Float::sqrt(8.0));
     assert!(e.approx_eq(f, EPSILON));
   }
 
+  #[test] fn translator_slerp() {
+    let (a, b) = (translator(0.0, 1.0, 0.0, 0.0), translator(4.0, 0.0, 1.0, 0.0));
+    let c = a.slerp(b, 0.25).apply(ORIGIN);
+    assert!(c.approx_eq(point(0.0, 1.0, 0.0), EPSILON));
+    assert_eq!(a.nlerp(b, 0.25), a.slerp(b, 0.25));
+  }
+
   #[test] fn translator_point() {
     let t = translator(1.0, 0.0, 0.0, 1.0);
     let a = point(1.0, 0.0, 0.0);
-    let b = t(a);
+    let b = t.apply(a);
     assert_eq!([b.x(), b.y(), b.z()], [1.0, 0.0, 1.0]);
   }
 
@@ -334,7 +367,7 @@ mod tests {
     let data = [0.0, -5.0, -2.0, 2.0];
     let t = Translator::load_normalized(data);
     let l = line(-1.0, 2.0, -3.0, -6.0, 5.0, 4.0);
-    let k = t(l);
+    let k = t.apply(l);
     assert_eq!([k.e01(),k.e02(),k.e03(),k.e12(),k.e31(),k.e23()],
                [35.0, -14.0, 71.0, 4.0, 5.0, -6.0])
   }
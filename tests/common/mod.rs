@@ -0,0 +1,107 @@
+// Random elements for the law tests. The cases come from a seeded xorshift
+// generator, a failure reports its seed and can be replayed with `G3_SEED=<seed>`.
+#![allow(dead_code)]
+
+use g3::*;
+
+/// Cases tried for every law.
+pub const CASES:usize = 256;
+/// Relative to the largest component, products of three elements still pass.
+pub const TOLERANCE:f32 = 0.000_2;
+
+pub struct Rng(u64);
+
+impl Rng {
+  pub fn new(seed:u64)->Rng { Rng(seed.max(1)) }
+
+  // xorshift64*
+  pub fn next(&mut self)->u64 {
+    self.0 ^= self.0 >> 12;
+    self.0 ^= self.0 << 25;
+    self.0 ^= self.0 >> 27;
+    self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+  }
+
+  /// Uniform in `[lo, hi)`.
+  pub fn range(&mut self, lo:f32, hi:f32)->f32 { lo + (hi - lo) * (self.next() >> 40) as f32 / (1u64 << 24) as f32 }
+  /// Uniform in `[-r, r)`.
+  pub fn float(&mut self, r:f32)->f32 { self.range(-r, r) }
+  /// Magnitude in `[lo, hi)` with a random sign.
+  pub fn magnitude(&mut self, lo:f32, hi:f32)->f32 { let m = self.range(lo, hi); if self.next() & 1 == 0 { m } else { -m } }
+  /// Rotation angle, away from the half turn where the logarithm switches sides.
+  pub fn angle(&mut self)->f32 { self.float(0.9 * pi) }
+  /// Unit vector.
+  pub fn axis(&mut self)->[f32;3] {
+    loop {
+      let [x, y, z] = [self.float(1.0), self.float(1.0), self.float(1.0)];
+      let n = (x*x + y*y + z*z).sqrt();
+      if n > 0.1 && n <= 1.0 { return [x/n, y/n, z/n] }
+    }
+  }
+}
+
+pub fn seed()->u64 { std::env::var("G3_SEED").ok().and_then(|s| s.parse().ok()).unwrap_or(0x9e37_79b9_7f4a_7c15) }
+
+/// Check a law on `CASES` random cases, `law` returns why a case fails.
+pub fn forall(name:&str, mut law:impl FnMut(&mut Rng)->Option<String>) {
+  let seed = seed();
+  let mut rng = Rng::new(seed);
+  for case in 0..CASES {
+    if let Some(why) = law(&mut rng) { panic!("{} fails in case {} of G3_SEED={}: {}", name, case, seed, why) }
+  }
+}
+
+/// Components agree up to `TOLERANCE` times the largest of them, or one.
+pub fn close<A:Into<Multivector>, B:Into<Multivector>>(a:A, b:B)->Option<String> {
+  let (a, b) = (a.into(), b.into());
+  let (x, y) = (a.to_array(), b.to_array());
+  let scale = x.iter().chain(y.iter()).fold(1.0f32, |m, c| m.max(c.abs()));
+  if x.iter().zip(y.iter()).all(|(x, y)| (x - y).abs() <= TOLERANCE * scale) { None } else { Some(format!("{:?} ≉ {:?}", a, b)) }
+}
+
+/// The screw motion the fixed cases move their elements with.
+pub fn screw()->Motor { Motor::from_screw_axis(pi/3.0, 2.0, line(3.0, 1.0, 3.0, 4.0, -2.0, 1.0).normalized()) }
+
+/// Same as `close` for numbers.
+pub fn close1(a:f32, b:f32)->Option<String> { close(a, b) }
+
+pub trait Random { fn random(rng:&mut Rng)->Self; }
+
+impl Random for Plane {
+  fn random(rng:&mut Rng)->Plane { let [a, b, c] = rng.axis(); plane(a, b, c, rng.float(4.0)) * rng.magnitude(0.5, 2.0) }
+}
+impl Random for Point {
+  fn random(rng:&mut Rng)->Point { point(rng.float(4.0), rng.float(4.0), rng.float(4.0)) * rng.magnitude(0.5, 2.0) }
+}
+impl Random for Direction {
+  fn random(rng:&mut Rng)->Direction { let [x, y, z] = rng.axis(); Direction::new(x, y, z) }
+}
+/// Through two points, so always a simple line.
+impl Random for Line {
+  fn random(rng:&mut Rng)->Line {
+    let a = Point::random(rng).normalized();
+    let ([x, y, z], d) = (rng.axis(), rng.range(0.5, 2.0));
+    a & point(a.x() + d*x, a.y() + d*y, a.z() + d*z)
+  }
+}
+impl Random for Branch {
+  fn random(rng:&mut Rng)->Branch { let ([x, y, z], d) = (rng.axis(), rng.magnitude(0.5, 2.0)); branch(d*x, d*y, d*z) }
+}
+impl Random for Horizon {
+  fn random(rng:&mut Rng)->Horizon { horizon(rng.float(2.0), rng.float(2.0), rng.float(2.0)) }
+}
+impl Random for Dual {
+  fn random(rng:&mut Rng)->Dual { dual(rng.magnitude(0.5, 2.0), rng.float(2.0)) }
+}
+impl Random for Rotor {
+  fn random(rng:&mut Rng)->Rotor { let [x, y, z] = rng.axis(); rotor(rng.angle(), x, y, z) }
+}
+impl Random for Translator {
+  fn random(rng:&mut Rng)->Translator { let [x, y, z] = rng.axis(); translator(rng.float(4.0), x, y, z) }
+}
+impl Random for Motor {
+  fn random(rng:&mut Rng)->Motor { Rotor::random(rng) * Translator::random(rng) }
+}
+impl Random for Flector {
+  fn random(rng:&mut Rng)->Flector { Motor::random(rng) * Plane::random(rng).normalized() }
+}
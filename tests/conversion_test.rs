@@ -0,0 +1,132 @@
+// Conversions to matrices, quaternions and the glam and mint types agree with
+// applying the element itself, run with `--features glam,mint` for the interop.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::*;
+
+  fn moved(m:[f32;16], [x, y, z]:[f32;3])->[f32;3] {
+    let w = m[3]*x + m[7]*y + m[11]*z + m[15];
+    [0, 1, 2].map(|i| (m[i]*x + m[4+i]*y + m[8+i]*z + m[12+i]) / w)
+  }
+
+  fn same(a:[f32;3], b:Point)->Option<String> { close(point(a[0], a[1], a[2]), b.normalized()) }
+
+  #[test] fn matrices_move_points_like_the_element() {
+    forall("rotor matrix", |rng| {
+      let (r, a) = (Rotor::random(rng), Point::random(rng).normalized());
+      same(moved(r.into(), a.into()), r.apply(a))
+    });
+    forall("motor matrix", |rng| {
+      let (m, a) = (Motor::random(rng), Point::random(rng).normalized());
+      same(moved(m.into(), a.into()), m.apply(a))
+    });
+    forall("flector matrix", |rng| {
+      let (f, a) = (Flector::random(rng).normalized(), Point::random(rng).normalized());
+      same(moved(f.into(), a.into()), f.apply(a))
+    });
+  }
+
+  // Up to sign, a rotor and its negation are the same rotation.
+  fn either<T:Into<Multivector> + std::ops::Mul<f32, Output = T> + Copy>(a:T, b:T)->Option<String> { close(a, b).and(close(a, b * -1.0)) }
+
+  #[test] fn matrices_convert_back() {
+    forall("from_matrix3", |rng| {
+      let r = Rotor::random(rng);
+      let m = <[f32;16]>::from(r);
+      either(Rotor::from_matrix3([m[0], m[1], m[2], m[4], m[5], m[6], m[8], m[9], m[10]]), r)
+    });
+    forall("from_matrix", |rng| { let m = Motor::random(rng).normalized(); either(Motor::from_matrix(m.into()), m) });
+    // Scale is dropped and the homogeneous coordinate divided out
+    forall("from_matrix of a scaled matrix", |rng| {
+      let m = Motor::random(rng).normalized();
+      let s = rng.range(0.5, 2.0);
+      let a = <[f32;16]>::from(m).map(|x| x * s);
+      either(Motor::from_matrix(a), m)
+    });
+  }
+
+  // Degenerate matrices have no rotation, debug builds reject them.
+  const IDENTITY:[f32;16] = [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0];
+
+  #[cfg(debug_assertions)]
+  #[test] #[should_panic(expected = "zero last entry")] fn from_matrix_without_weight() {
+    let mut m = IDENTITY;
+    m[15] = 0.0;
+    Motor::from_matrix(m);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test] #[should_panic(expected = "zero column")] fn from_matrix_with_zero_column() {
+    let mut m = IDENTITY;
+    m[0] = 0.0;
+    Motor::from_matrix(m);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test] #[should_panic(expected = "zero column")] fn from_matrix3_with_zero_column() {
+    Rotor::from_matrix3([1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test] #[should_panic(expected = "parallel columns")] fn from_matrix3_with_parallel_columns() {
+    Rotor::from_matrix3([1.0, 2.0, 3.0, -2.0, -4.0, -6.0, 0.0, 0.0, 1.0]);
+  }
+
+  #[test] fn quaternions_round_trip() {
+    forall("quaternion", |rng| { let r = Rotor::random(rng); close(Rotor::from_quaternion(r.to_quaternion()), r) });
+    forall("dual quaternion", |rng| {
+      let m = Motor::random(rng).normalized();
+      let (real, dual) = m.to_dual_quaternion();
+      close(Motor::from_dual_quaternion(real, dual), m)
+    });
+  }
+
+  #[cfg(feature = "glam")]
+  #[test] fn glam() {
+    use glam::{Affine3A, Mat3, Mat4, Quat, Vec3};
+    forall("Quat", |rng| {
+      let (r, a) = (Rotor::random(rng), Point::random(rng).normalized());
+      same((Quat::from(r) * Vec3::from(a)).to_array(), r.apply(a)).or(close(Rotor::from(Quat::from(r)), r))
+    });
+    forall("Mat3", |rng| {
+      let r = Rotor::random(rng);
+      either(Rotor::from(Mat3::from_quat(r.into())), r)
+    });
+    forall("Mat4 and Affine3A", |rng| {
+      let (m, a) = (Motor::random(rng).normalized(), Point::random(rng).normalized());
+      same(Mat4::from(m).transform_point3(a.into()).to_array(), m.apply(a))
+        .or(same(Affine3A::from(m).transform_point3(a.into()).to_array(), m.apply(a)))
+        .or(either(Motor::from(Affine3A::from(m)), m))
+    });
+    // The dual part is half the translation times the real part
+    forall("dual quaternion", |rng| {
+      let m = Motor::random(rng).normalized();
+      let (real, dual) = m.to_dual_quaternion();
+      let t = (Quat::from_array(dual) * Quat::from_array(real).conjugate()) * 2.0;
+      same([t.x, t.y, t.z], m.apply(point(0.0, 0.0, 0.0))).or(close1(t.w, 0.0))
+    });
+    forall("Vec3", |rng| {
+      let (t, a) = (Translator::random(rng), Point::random(rng).normalized());
+      same((Vec3::from(t) + Vec3::from(a)).to_array(), t.apply(a)).or(close(Translator::from(Vec3::from(t)), t))
+    });
+    let p = plane(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(Plane::from(glam::Vec4::from(p)), p);
+    assert_eq!(Point::from(glam::Vec4::new(2.0, 4.0, 6.0, 2.0)).normalized(), point(1.0, 2.0, 3.0));
+    assert_eq!(Vec3::from(Direction::new(0.0, 0.0, 2.0)), Vec3::Z);
+    assert_eq!(Branch::from(Vec3::new(1.0, 2.0, 3.0)), branch(1.0, 2.0, 3.0));
+  }
+
+  #[cfg(feature = "mint")]
+  #[test] fn mint() {
+    forall("Quaternion", |rng| { let r = Rotor::random(rng); close(Rotor::from(mint::Quaternion::from(r)), r) });
+    forall("ColumnMatrix4", |rng| { let m = Motor::random(rng).normalized(); either(Motor::from(mint::ColumnMatrix4::from(m)), m) });
+    let t = translator(2.0, 0.0, 1.0, 0.0);
+    assert_eq!(mint::Vector3::from(t), mint::Vector3{x: 0.0, y: 2.0, z: 0.0});
+    assert_eq!(Translator::from(mint::Vector3{x: 0.0, y: 2.0, z: 0.0}), t);
+    let p = plane(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(Plane::from(mint::Vector4::from(p)), p);
+  }
+}
@@ -0,0 +1,117 @@
+// The generic elements agree with the crate root and the gradients of their
+// operations with finite differences.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::{*, diff::{Jet, Scalar}};
+  use super::common::*;
+
+  fn jet_line(l:Line)->diff::Line<Jet<6>> { diff::Line::from(l) }
+
+  // Central differences of f at x in double precision against the gradient of f at the jets of x.
+  fn gradient(x:[f64;6], f:impl Fn([f64;6])->f64, df:impl Fn([Jet<6>;6])->Jet<6>)->Option<String> {
+    let (h, jet) = (1e-6, df(Jet::variables(x)));
+    let grad:[f64;6] = std::array::from_fn(|i| {
+      let (mut a, mut b) = (x, x);
+      a[i] += h;
+      b[i] -= h;
+      (f(a) - f(b)) / (2.0 * h)
+    });
+    let value = f(x);
+    let off = (0..6).any(|i| (grad[i] - jet.grad[i]).abs() > 1e-5 * (1.0 + grad[i].abs())) || (value - jet.value).abs() > 1e-9 * (1.0 + value.abs());
+    if off { Some(format!("{:?} {:?} ≉ {:?} {:?}", jet.value, jet.grad, value, grad)) } else { None }
+  }
+
+  fn random_motor(rng:&mut Rng)->Motor { Motor::random(rng).normalized() }
+
+  #[test] fn same_as_the_elements() {
+    forall("exp", |rng| { let l = Line::random(rng); close(diff::Line::<f32>::from(l).exp().to_f32(), l.exp()) });
+    forall("log", |rng| { let m = random_motor(rng); close(diff::Motor::<f32>::from(m).log().to_f32(), m.log()) });
+    forall("normalized", |rng| { let m = Motor::random(rng); close(diff::Motor::<f32>::from(m).normalized().to_f32(), m.normalized()) });
+    forall("product", |rng| {
+      let (a, b) = (Motor::random(rng), Motor::random(rng));
+      close((diff::Motor::<f32>::from(a) * diff::Motor::from(b)).to_f32(), a * b)
+    });
+    forall("apply", |rng| {
+      let (m, p, l) = (random_motor(rng), Point::random(rng), Line::random(rng));
+      let d = diff::Motor::<f32>::from(m);
+      close(d.apply(diff::Point::from(p)).to_f32(), m.apply(p)).or(close(d.apply(diff::Line::from(l)).to_f32(), m.apply(l)))
+    });
+    forall("apply to planes and by rotors", |rng| {
+      let (m, r, p, q) = (random_motor(rng), Rotor::random(rng).normalized(), Plane::random(rng), Point::random(rng));
+      let (d, e) = (diff::Motor::<f32>::from(m), diff::Rotor::<f32>::from(r));
+      close(d.apply(diff::Plane::from(p)).to_f32(), m.apply(p)).or(close(e.apply(diff::Point::from(q)).to_f32(), r.apply(q)))
+    });
+    forall("join, meet and inner product", |rng| {
+      let (p, q, a, b, l) = (Point::random(rng), Point::random(rng), Plane::random(rng), Plane::random(rng), Line::random(rng));
+      let (dp, dq, da, db, dl) = (diff::Point::<f32>::from(p), diff::Point::from(q), diff::Plane::from(a), diff::Plane::from(b), diff::Line::from(l));
+      close((dp & dq).to_f32(), p & q).or(close((dp & dl).to_f32(), p & l)).or(close((da ^ db).to_f32(), a ^ b))
+        .or(close((da ^ dl).to_f32(), a ^ l)).or(close((da | dq).to_f32(), a | q)).or(close((dl | da).to_f32(), l | a))
+        .or(close1(da | db, a | b)).or(close1(dl | dl, l | l)).or(close1(dl & diff::Line::from(p & q), l & (p & q)))
+    });
+    forall("products", |rng| {
+      let (m, r, a, p) = (Motor::random(rng), Rotor::random(rng), Plane::random(rng), Point::random(rng));
+      let (dm, dr, da, dp) = (diff::Motor::<f32>::from(m), diff::Rotor::from(r), diff::Plane::from(a), diff::Point::from(p));
+      close((dr * dm).to_f32(), r * m).or(close((dm * dr).to_f32(), m * r)).or(close((dr * dr).to_f32(), r * r))
+        .or(close((da * dp).to_f32(), a * p)).or(close((dm * da).to_f32(), m * a)).or(close((dm * dp).to_f32(), m * p))
+    });
+    forall("distance", |rng| {
+      let (a, b) = (Point::random(rng), Point::random(rng));
+      close1(diff::Point::<f32>::from(a).distance(diff::Point::from(b)), a.distance(b))
+    });
+  }
+
+  #[test] fn logarithm_of_small_and_pure_motors() {
+    for m in [Motor::from(translator(3.0, 1.0, 2.0, 0.0)), rotor(1e-3, 1.0, 0.0, 0.0) * translator(1.0, 0.0, 1.0, 0.0), Motor::from(rotor(0.0, 0.0, 0.0, 1.0))] {
+      assert_eq!(close(diff::Motor::<f32>::from(m).log().to_f32(), m.log()), None);
+      assert_eq!(close(diff::Motor::<f32>::from(m).log().exp().to_f32(), m), None);
+    }
+  }
+
+  #[test] fn gradients_match_finite_differences() {
+    forall("distance after motion", |rng| {
+      let (m, p, q) = (random_motor(rng), Point::random(rng), Point::random(rng));
+      let x = [0, 1, 2, 3, 4, 5].map(|_| rng.range(-1.0, 1.0) as f64);
+      fn error<S:Scalar>(x:[S;6], m:Motor, p:Point, q:Point)->S {
+        let d = diff::line(x[0], x[1], x[2], x[3], x[4], x[5]).exp() * diff::Motor::from(m);
+        d.apply(diff::Point::from(p)).distance(diff::Point::from(q))
+      }
+      gradient(x, |x| error(x, m, p, q), |x| error(x, m, p, q))
+    });
+    forall("log", |rng| {
+      let (l, i) = (Line::random(rng), rng.range(0.0, 6.0) as usize);
+      let x = [0, 1, 2, 3, 4, 5].map(|_| rng.range(-1.0, 1.0) as f64);
+      fn log<S:Scalar>(x:[S;6], l:Line, i:usize)->S {
+        let m = diff::line(x[0], x[1], x[2], x[3], x[4], x[5]).exp() * diff::Line::from(l).exp();
+        let l = m.log();
+        [l.e01(), l.e02(), l.e03(), l.e23(), l.e31(), l.e12()][i]
+      }
+      gradient(x, |x| log(x, l, i), |x| log(x, l, i))
+    });
+  }
+
+  #[test] fn distance_to_itself() {
+    // The minimum of the distance, where the square root has no derivative
+    let [a, b, c, _, _, _] = Jet::<6>::variables([1.0, 2.0, 3.0, 0.0, 0.0, 0.0]);
+    let p = diff::point(a, b, c);
+    let d = p.distance(p);
+    assert_eq!((d.value, d.grad), (0.0, [0.0; 6]));
+    let q = diff::point(a, b, c + Jet::constant(2.0));
+    assert_eq!(p.squared_distance(q).value, 4.0);
+    assert_eq!(p.squared_distance(q).grad, [0.0; 6]);
+  }
+
+  #[test] fn gradients_at_the_identity() {
+    // The derivative of exp at zero is the identity on the bivectors
+    let m = jet_line(Line::default()).exp();
+    let components = [m.e01(), m.e02(), m.e03(), m.e23(), m.e31(), m.e12()];
+    let [a, b, c, d, e, f] = Jet::variables([0.0; 6]);
+    let n = diff::line(a, b, c, d, e, f).exp();
+    assert!(components.iter().all(|x| x.grad == [0.0; 6]));
+    assert_eq!([n.e01(), n.e02(), n.e03(), n.e23(), n.e31(), n.e12()].map(|x| x.grad), std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 })));
+    // And so is the derivative of log at the identity
+    let l = n.log();
+    assert_eq!([l.e01(), l.e02(), l.e03(), l.e23(), l.e31(), l.e12()].map(|x| x.grad), std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 })));
+  }
+}
@@ -0,0 +1,76 @@
+// Every Euler angle convention decodes the rotor it encoded, including at
+// gimbal lock where only the sum of the outer angles is defined.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::*;
+
+  const FRAMES:[EulerFrame;2] = [EulerFrame::Intrinsic, EulerFrame::Extrinsic];
+
+  fn either(a:Rotor, b:Rotor)->Option<String> { close(a, b).and(close(a, b * -1.0)) }
+
+  // Angles inside the range that to_euler decodes into
+  fn angles(rng:&mut Rng, order:EulerOrder)->[f32;3] {
+    let middle = if order.is_proper() { rng.range(0.05, 0.95) * pi } else { rng.float(0.45 * pi) };
+    [rng.angle(), middle, rng.angle()]
+  }
+
+  #[test] fn round_trip_every_convention() {
+    for order in EulerOrder::ALL { for frame in FRAMES {
+      forall(&format!("{:?} {:?} angles", order, frame), |rng| {
+        let a = angles(rng, order);
+        let e = Rotor::from_euler(order, frame, a).to_euler(order, frame);
+        close(e.angles[0], a[0]).or(close1(e.angles[1], a[1])).or(close1(e.angles[2], a[2]))
+          .or(if e.gimbal_lock { Some("gimbal lock".into()) } else { None })
+      });
+      forall(&format!("{:?} {:?} rotor", order, frame), |rng| {
+        let r = Rotor::random(rng);
+        either(Rotor::from_euler(order, frame, r.to_euler(order, frame).angles), r.normalized())
+      });
+    }}
+  }
+
+  #[test] fn gimbal_lock_is_reported() {
+    for order in EulerOrder::ALL { for frame in FRAMES {
+      let locks = if order.is_proper() { [0.0, pi] } else { [pi / 2.0, -pi / 2.0] };
+      for middle in locks {
+        forall(&format!("{:?} {:?} at {}", order, frame, middle), |rng| {
+          let r = Rotor::from_euler(order, frame, [rng.angle(), middle, rng.angle()]);
+          let e = r.to_euler(order, frame);
+          either(Rotor::from_euler(order, frame, e.angles), r).or(close1(e.angles[2], 0.0))
+            .or(if e.gimbal_lock { None } else { Some("no gimbal lock".into()) })
+        });
+      }
+    }}
+  }
+
+  #[test] fn conventions() {
+    let [a, b, c] = [0.3, -0.7, 1.1];
+    let [x, y, z] = [rotor(a, 1.0, 0.0, 0.0), rotor(b, 0.0, 1.0, 0.0), rotor(c, 0.0, 0.0, 1.0)];
+    assert_eq!(close(Rotor::from_euler(EulerOrder::XYZ, EulerFrame::Intrinsic, [a, b, c]), x * y * z), None);
+    assert_eq!(close(Rotor::from_euler(EulerOrder::XYZ, EulerFrame::Extrinsic, [a, b, c]), z * y * x), None);
+    assert_eq!(close(Rotor::from_euler(EulerOrder::ZYX, EulerFrame::Intrinsic, [c, b, a]),
+      Rotor::from_euler(EulerOrder::XYZ, EulerFrame::Extrinsic, [a, b, c])), None);
+    // EulerAngles is intrinsic XYZ
+    let ea = EulerAngles{roll: a, pitch: b, yaw: c};
+    assert_eq!(close(Rotor::from(ea), Rotor::from_euler(EulerOrder::XYZ, EulerFrame::Intrinsic, [a, b, c])), None);
+    let e = Rotor::from(ea).to_euler(EulerOrder::XYZ, EulerFrame::Intrinsic);
+    assert_eq!(close(e.angles[0], a).or(close1(e.angles[1], b)).or(close1(e.angles[2], c)), None);
+  }
+
+  // glam turns counterclockwise, rotors clockwise, so the angles flip sign.
+  #[cfg(feature = "glam")]
+  #[test] fn glam_euler() {
+    use glam::{EulerRot, Quat};
+    let orders = [(EulerOrder::XYZ, EulerRot::XYZ), (EulerOrder::ZYX, EulerRot::ZYX), (EulerOrder::YXZ, EulerRot::YXZ),
+      (EulerOrder::XZY, EulerRot::XZY), (EulerOrder::YZX, EulerRot::YZX), (EulerOrder::ZXY, EulerRot::ZXY)];
+    for (order, rot) in orders {
+      forall(&format!("glam {:?}", order), |rng| {
+        let [a, b, c] = angles(rng, order);
+        either(Rotor::from(Quat::from_euler(rot, a, b, c)), Rotor::from_euler(order, EulerFrame::Intrinsic, [-a, -b, -c]))
+      });
+    }
+  }
+}
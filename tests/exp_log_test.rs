@@ -1,4 +1,3 @@
-#![feature(portable_simd)]
 #[cfg(test)]
 mod tests {
   use g3::*;
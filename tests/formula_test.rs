@@ -0,0 +1,42 @@
+// Formulas written with `pga!` expand to the same operators as written out.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::*;
+
+  #[test] fn blades() {
+    assert_eq!(pga!(2 e1 + 3 e0), plane(2.0, 0.0, 0.0, 3.0));
+    let l:Line = pga!(e23 + 2 e31 + 3 e12 + 4 e01 + 5 e02 + 6 e03);
+    assert_eq!([l.e23(), l.e31(), l.e12(), l.e01(), l.e02(), l.e03()], [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(pga!(e21 + e10), line(-1.0, 0.0, 0.0, 0.0, 0.0, -1.0));
+    assert_eq!(pga!(e123 + 2 e032 + 3 e013 + 4 e021), point(2.0, 3.0, 4.0));
+    assert_eq!(pga!(1.5 e0123), dual(0.0, 1.5));
+    // e1e2 is e12 and e1e2e3 is e123
+    let m = pga!(e1 * e2);
+    assert_eq!([m.scalar(), m.e12()], [0.0, 1.0]);
+    assert_eq!(Multivector::from(pga!(e1 ^ e2 ^ e3)), Multivector::from(pga!(e123)));
+  }
+
+  #[test] fn formulas_match_the_operators() {
+    forall("join and meet", |rng| {
+      let (a, b, p) = (Point::random(rng), Point::random(rng), Plane::random(rng));
+      close(pga!(!(!a ^ !b)), a & b).or(close(pga!((a & b) ^ p), (a & b) ^ p))
+    });
+    forall("sandwich", |rng| {
+      let (m, l, a) = (Motor::random(rng).normalized(), Line::random(rng), Point::random(rng));
+      close(pga!(m * l * ~m), m * l * m.reverse()).or(close(pga!(~(m * a)), (m * a).reverse()))
+    });
+    forall("precedence of Rust", |rng| {
+      let (a, b, p, q, r) = (Point::random(rng), Point::random(rng), Plane::random(rng), Plane::random(rng), Plane::random(rng));
+      close(pga!(p ^ a & b), p ^ (a & b)).or(close(pga!(p | q ^ r), p | (q ^ r))).or(close(pga!(a & b ^ p), (a & b) ^ p))
+    });
+    forall("calls and methods", |rng| {
+      let l = Line::random(rng);
+      let twice = |l:Line| l * 2.0;
+      close(pga!(twice(l + e12).normalized()), twice(l + line(0.0, 0.0, 0.0, 0.0, 0.0, 1.0)).normalized())
+        .or(close(pga!(2 l - 0.5 e21), l * 2.0 + line(0.0, 0.0, 0.0, 0.0, 0.0, 0.5)))
+    });
+  }
+}
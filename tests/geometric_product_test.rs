@@ -1,7 +1,5 @@
-#![feature(portable_simd)]
 #[cfg(test)]
 mod tests {
-  use std::simd::f32x4;
   use g3::*;
 
   const EPSILON: f32 = 0.000_001;
@@ -28,7 +26,7 @@ mod tests {
     let m = p1 * p2;
     approx_eq([m.scalar(), m.e12(), m.e31(), m.e23()], [5.0, -1.0, 7.0, -11.0]);
     approx_eq([m.e01(), m.e02(), m.e03(), m.e0123()], [10.0, 16.0, 2.0, 0.0]);
-    let p3:Plane = (p1/p2).sqrt()(p2);
+    let p3:Plane = (p1/p2).sqrt().apply(p2);
     approx_eq([p3.e0(),p3.e1(),p3.e2(),p3.e3()], [p1.e0(),p1.e1(),p1.e2(),p1.e3()]);
     assert!(p3.approx_eq(p1, 0.001));
     let p1 = p1.normalized();
@@ -67,7 +65,7 @@ mod tests {
 
     let b1 = b1.normalized();
     let b2 = b2.normalized();
-    let b3:Branch = (b2 * b1).sqrt()(b1).reverse();
+    let b3:Branch = (b2 * b1).sqrt().apply(b1).reverse();
     approx_eq([b3.x(), b3.y(), b3.z(), 0.0], [b2.x(), b2.y(), b2.z(), 0.0])
   }
 
@@ -85,7 +83,7 @@ mod tests {
     approx_eq([m.e01(), m.e02(), m.e03(), m.e0123()], [1.0, -2.0, -4.0, 6.0]);
     let l1 = l1.normalized();
     let l2 = l2.normalized();
-    let l3:Line = (l1 * l2).sqrt()(l2);
+    let l3:Line = (l1 * l2).sqrt().apply(l2);
     assert!(l3.approx_eq(-l1, 0.001));
   }
 
@@ -109,7 +107,7 @@ mod tests {
     let b = point(-2.0, 1.0, 4.0);
     let t = a * b;
     approx_eq([t.e01(), t.e02(), t.e03(), 0.0], [-3.0, -1.0, 1.0, 0.0]);
-    let c = t.sqrt()(b);
+    let c = t.sqrt().apply(b);
     approx_eq([c.x(), c.y(), c.z(), 0.0], [1.0, 2.0, 3.0, 0.0]);
   }
 
@@ -127,7 +125,7 @@ mod tests {
 
   #[test] fn mul_rotor_translator() {
     let r:Rotor = [1.0, 0.0, 0.0, 1.0].into();
-    let t = Translator{p2: f32x4::from([0.0, 0.0, 0.0, 1.0])};
+    let t = Translator::load_normalized([0.0, 0.0, 0.0, 1.0]);
     let m = r * t;
     approx_eq([m.scalar(), m.e01(), m.e02(), m.e03()], [1.0, 0.0, 0.0, 1.0]);
     approx_eq([m.e23(), m.e31(), m.e12(), m.e0123()], [0.0, 0.0, 1.0, 1.0]);
@@ -135,7 +133,7 @@ mod tests {
 
   #[test] fn mul_translator_rotor() {
     let r:Rotor = [1.0, 0.0, 0.0, 1.0].into();
-    let t = Translator{p2: f32x4::from([0.0, 0.0, 0.0, 1.0])};
+    let t = Translator::load_normalized([0.0, 0.0, 0.0, 1.0]);
     let m = t * r;
     approx_eq([m.scalar(), m.e01(), m.e02(), m.e03()], [1.0, 0.0, 0.0, 1.0]);
     approx_eq([m.e23(), m.e31(), m.e12(), m.e0123()], [0.0, 0.0, 1.0, 1.0]);
@@ -143,7 +141,7 @@ mod tests {
 
   #[test] fn mul_motor_rotor() {
     let r1:Rotor = [1.0, 2.0, 3.0, 4.0].into();
-    let t = Translator{p2: f32x4::from([3.0, -2.0, 1.0, -3.0])};
+    let t = Translator::load_normalized([3.0, -2.0, 1.0, -3.0]);
     let r2:Rotor = [-4.0, 2.0, -3.0, 1.0].into();
     let m1 = (t * r1) * r2;
     let m2 = t * (r1 * r2);
@@ -152,7 +150,7 @@ mod tests {
 
   #[test] fn mul_rotor_motor() {
     let r1:Rotor = [1.0, 2.0, 3.0, 4.0].into();
-    let t = Translator{p2: f32x4::from([3.0, -2.0, 1.0, -3.0])};
+    let t = Translator::load_normalized([3.0, -2.0, 1.0, -3.0]);
     let r2:Rotor = [-4.0, 2.0, -3.0, 1.0].into();
     let m1 = r2 * (r1 * t);
     let m2 = (r2 * r1) * t;
@@ -161,8 +159,8 @@ mod tests {
 
   #[test] fn mul_motor_translator() {
     let r:Rotor = [1.0, 2.0, 3.0, 4.0].into();
-    let t1 = Translator{p2: f32x4::from([3.0, -2.0, 1.0, -3.0])};
-    let t2 = Translator{p2: f32x4::from([-4.0, 2.0, -3.0, 1.0])};
+    let t1 = Translator::load_normalized([3.0, -2.0, 1.0, -3.0]);
+    let t2 = Translator::load_normalized([-4.0, 2.0, -3.0, 1.0]);
     let m1 = (r * t1) * t2;
     let m2 = r * (t1 * t2);
     assert_eq!(m1, m2);
@@ -170,8 +168,8 @@ mod tests {
 
   #[test] fn mul_translator_motor() {
     let r:Rotor = [1.0, 2.0, 3.0, 4.0].into();
-    let t1 = Translator{p2: f32x4::from([3.0, -2.0, 1.0, -3.0])};
-    let t2 = Translator{p2: f32x4::from([-4.0, 2.0, -3.0, 1.0])};
+    let t1 = Translator::load_normalized([3.0, -2.0, 1.0, -3.0]);
+    let t2 = Translator::load_normalized([-4.0, 2.0, -3.0, 1.0]);
     let m1 = t2 * (r * t1);
     let m2 = (t2 * r) * t1;
     assert_eq!(m1, m2);
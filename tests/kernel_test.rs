@@ -0,0 +1,80 @@
+// Every operator between elements agrees with the general multivector, whose
+// products are generated from the Cayley table.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::screw;
+
+  fn mv<T:Into<Multivector>>(x:T)->Multivector { x.into() }
+  fn same(name:&str, a:Multivector, b:Multivector) { assert!(a.approx_eq(b, 0.000_1), "{}: {:?} ≉ {:?}", name, a, b) }
+
+  #[test] fn operators_match_cayley_table() {
+    let (p, q) = (plane(1.0, -2.0, 0.5, 3.0), plane(-0.3, 1.0, 2.0, -1.0));
+    let (a, b) = (point(1.0, 2.0, -3.0), point(-0.5, 0.2, 4.0));
+    let l = line(0.3, -1.0, 2.0, 1.0, 0.5, -0.2);
+    let k = line(-1.0, 0.4, 0.7, -0.3, 1.2, 0.9);
+    let (br, br2) = (branch(0.3, -0.7, 1.1), branch(-1.0, 0.2, 0.5));
+    let h = horizon(0.5, 1.0, -2.0);
+    let (r, r2) = (rotor(0.7, 1.0, -2.0, 0.5), rotor(-1.3, 0.2, 1.0, 1.0));
+    let (t, t2) = (translator(1.5, 1.0, 2.0, -1.0), translator(-0.5, 0.0, 1.0, 3.0));
+    let m = screw();
+    let n = Motor::from_screw_axis(-0.4, 1.0, line(-1.0, 2.0, 0.0, 0.0, 1.0, 1.0).normalized());
+    same("br*br", mv(br*br2), mv(br)*br2);
+    same("l*l", mv(l*k), mv(l)*k);
+    same("m*r", mv(m*r), mv(m)*r);
+    let mut x = m; x *= r; same("m*=r", mv(x), mv(m)*r);
+    same("m*t", mv(m*t), mv(m)*t);
+    let mut x = m; x *= t; same("m*=t", mv(x), mv(m)*t);
+    same("m*m", mv(m*n), mv(m)*n);
+    same("p*a", mv(p*a), mv(p)*a);
+    same("p*q", mv(p*q), mv(p)*q);
+    same("a*p", mv(a*p), mv(a)*p);
+    // The translator between two points is normalized.
+    let ab = mv(a) * b;
+    same("a*b", mv(a*b), ab / ab.scalar());
+    same("r*r", mv(r*r2), mv(r)*r2);
+    same("r*t", mv(r*t), mv(r)*t);
+    same("r*m", mv(r*m), mv(r)*m);
+    same("t*r", mv(t*r), mv(t)*r);
+    same("t*t", mv(t*t2), mv(t)*t2);
+    same("t*m", mv(t*m), mv(t)*m);
+    same("br^p", mv(br^p), mv(br)^p);
+    same("br^l", mv(br^l), mv(br)^l);
+    same("br^h", mv(br^h), mv(br)^h);
+    same("br&a", mv(br&a), mv(br)&a);
+    same("h^p", mv(h^p), mv(h)^p);
+    same("h^l", mv(h^l), mv(h)^l);
+    same("h^br", mv(h^br), mv(h)^br);
+    same("h&a", mv(h&a), mv(h)&a);
+    same("h|p", mv(h|p), mv(h)|p);
+    same("l^p", mv(l^p), mv(l)^p);
+    same("l^l", mv(l^k), mv(l)^k);
+    same("l^h", mv(l^h), mv(l)^h);
+    same("l^br", mv(l^br), mv(l)^br);
+    same("l&a", mv(l&a), mv(l)&a);
+    same("l|a", mv(l|a), mv(l)|a);
+    same("l|l", mv(l|k), mv(l)|k);
+    same("l|p", mv(l|p), mv(l)|p);
+    same("p|q", mv(p|q), mv(p)|q);
+    same("p|l", mv(p|l), mv(p)|l);
+    same("p|h", mv(p|h), mv(p)|h);
+    same("p|a", mv(p|a), mv(p)|a);
+    same("p^q", mv(p^q), mv(p)^q);
+    same("p^l", mv(p^l), mv(p)^l);
+    same("p^h", mv(p^h), mv(p)^h);
+    same("p^br", mv(p^br), mv(p)^br);
+    same("p^a", mv(p^a), mv(p)^a);
+    same("p&a", mv(p&a), mv(p)&a);
+    same("a|p", mv(a|p), mv(a)|p);
+    same("a|l", mv(a|l), mv(a)|l);
+    same("a|b", mv(a|b), mv(a)|b);
+    same("a^p", mv(a^p), mv(a)^p);
+    same("a&b", mv(a&b), mv(a)&b);
+    same("a&l", mv(a&l), mv(a)&l);
+    same("a&h", mv(a&h), mv(a)&h);
+    same("a&br", mv(a&br), mv(a)&br);
+    same("a&p", mv(a&p), mv(a)&p);
+  }
+}
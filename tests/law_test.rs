@@ -0,0 +1,133 @@
+// The laws of the algebra on random elements of every kind, so that a wrong
+// sign or lane in one of the kernels shows up as a broken law.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::*;
+
+  fn mv<T:Into<Multivector>>(x:T)->Multivector { x.into() }
+
+  // (a*b)*c = a*(b*c) for every triple, the products in between are typed.
+  macro_rules! associative {
+    ($($a:ident * $b:ident * $c:ident),*) => {$(
+      forall(concat!("(", stringify!($a), "*", stringify!($b), ")*", stringify!($c)), |rng| {
+        let (a, b, c) = ($a::random(rng), $b::random(rng), $c::random(rng));
+        close((a * b) * c, a * (b * c))
+      });
+    )*}
+  }
+
+  #[test] fn geometric_product_is_associative() {
+    associative!(
+      Motor * Motor * Motor, Rotor * Rotor * Rotor, Translator * Translator * Translator,
+      Rotor * Translator * Motor, Translator * Motor * Rotor, Plane * Plane * Plane,
+      Plane * Motor * Point, Plane * Point * Line, Line * Line * Line, Branch * Rotor * Line,
+      Horizon * Motor * Branch, Flector * Motor * Flector, Dual * Line * Motor, Point * Rotor * Plane
+    );
+  }
+
+  // a^b = (-1)^(rs) b^a for grades r and s.
+  macro_rules! graded_commutative {
+    ($($a:ident ^ $b:ident = $sign:expr),*) => {$(
+      forall(concat!(stringify!($a), "^", stringify!($b)), |rng| {
+        let (a, b) = ($a::random(rng), $b::random(rng));
+        close(a ^ b, mv(b ^ a) * $sign)
+      });
+    )*}
+  }
+
+  #[test] fn outer_product_is_anticommutative() {
+    graded_commutative!(
+      Plane ^ Plane = -1.0, Plane ^ Point = -1.0, Point ^ Plane = -1.0, Plane ^ Line = 1.0,
+      Plane ^ Branch = 1.0, Plane ^ Horizon = 1.0, Line ^ Line = 1.0, Line ^ Branch = 1.0, Branch ^ Horizon = 1.0
+    );
+    forall("a^a = 0", |rng| { let p = Plane::random(rng); close(p ^ p, 0.0) });
+  }
+
+  macro_rules! join_is_dual_meet {
+    ($($a:ident & $b:ident),*) => {$(
+      forall(concat!(stringify!($a), "&", stringify!($b)), |rng| {
+        let (a, b) = ($a::random(rng), $b::random(rng));
+        close(a & b, !(!a ^ !b))
+      });
+    )*}
+  }
+
+  #[test] fn regressive_product_is_dual_of_outer() {
+    join_is_dual_meet!(
+      Point & Point, Point & Line, Line & Point, Point & Plane, Plane & Point, Point & Branch,
+      Point & Horizon, Line & Line, Line & Branch, Horizon & Line
+    );
+    forall("!(!a^!b) of multivectors", |rng| {
+      let (a, b) = (mv(Motor::random(rng)) + mv(Plane::random(rng)), mv(Flector::random(rng)) + mv(Line::random(rng)));
+      close(a & b, !(!a ^ !b))
+    });
+  }
+
+  macro_rules! inverse {
+    ($($t:ident),*) => {$(
+      forall(concat!(stringify!($t), " * inverse"), |rng| {
+        let a = $t::random(rng);
+        close(a * a.inverse(), 1.0).or(close(a.inverse() * a, 1.0))
+      });
+    )*}
+  }
+
+  #[test] fn inverse_is_reciprocal() {
+    inverse!(Motor, Rotor, Translator, Plane, Point, Line, Branch, Dual, Flector);
+    forall("a / a", |rng| { let m = Motor::random(rng); close(m / m, 1.0) });
+  }
+
+  #[test] fn exp_inverts_log() {
+    forall("exp(log(m))", |rng| { let m = Motor::random(rng); close(m.log().exp(), m) });
+    forall("exp(log(r))", |rng| { let r = Rotor::random(rng); close(r.log().exp(), r) });
+    forall("exp(log(t))", |rng| { let t = Translator::random(rng); close(t.log().exp(), t) });
+    forall("sqrt(m)^2", |rng| { let m = Motor::random(rng); let s = m.sqrt(); close(s * s, m) });
+  }
+
+  // The sum of two lines is a screw, it splits into an axis and an ideal line
+  // that commute and together exponentiate to the same motor.
+  #[test] fn screws_decompose_into_commuting_lines() {
+    forall("decompose(l)", |rng| {
+      let l = Line::random(rng) + Line::random(rng);
+      let (axis, ideal) = l.decompose();
+      (!axis.is_simple() || !ideal.is_simple()).then(|| format!("{:?} + {:?} is not simple", axis, ideal))
+        .or(close(axis + ideal, l))
+        .or(close(axis * ideal, ideal * axis))
+        .or(close(axis.exp() * ideal.exp(), l.exp()))
+    });
+  }
+
+  #[test] fn motors_factor_into_screws_rotors_and_translators() {
+    forall("from(to_screw(m))", |rng| { let m = Motor::random(rng); close(Motor::from(m.to_screw()), m) });
+    forall("t * r", |rng| { let m = Motor::random(rng); let (t, r) = m.decompose(); close(t * r, m) });
+    forall("r * t", |rng| { let m = Motor::random(rng); let (r, t) = m.decompose_rotor_first(); close(r * t, m) });
+  }
+
+  // Rigid motions and reflections keep distances between points and the norms
+  // of planes and lines.
+  macro_rules! isometry {
+    ($($versor:expr),*) => {$(
+      forall(concat!("isometry of ", stringify!($versor)), |rng| {
+        let v = $versor(rng);
+        let (a, b, p, l) = (Point::random(rng), Point::random(rng), Plane::random(rng), Line::random(rng));
+        close1(distance(v.apply(a), v.apply(b)), distance(a, b))
+          .or(close1(v.apply(p).norm(), p.norm()))
+          .or(close1(v.apply(l).norm(), l.norm()))
+      });
+    )*}
+  }
+
+  #[test] fn sandwiches_preserve_norms() {
+    isometry!(
+      |rng:&mut Rng| Motor::random(rng), |rng:&mut Rng| Rotor::random(rng), |rng:&mut Rng| Translator::random(rng),
+      |rng:&mut Rng| Plane::random(rng).normalized(), |rng:&mut Rng| Flector::random(rng).normalized()
+    );
+    forall("motor keeps angles", |rng| {
+      let (m, d, e) = (Motor::random(rng), Direction::random(rng), Direction::random(rng));
+      close1(angle(m.apply(d), m.apply(e)), angle(d, e))
+    });
+  }
+}
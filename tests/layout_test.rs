@@ -0,0 +1,35 @@
+// The documented memory layout of the elements that go into GPU buffers, run
+// with `--features bytemuck` for the casts.
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use std::mem::size_of;
+
+  #[test] fn sizes_have_no_padding() {
+    assert_eq!(size_of::<Point>(), 4 * size_of::<f32>());
+    assert_eq!(size_of::<Plane>(), 4 * size_of::<f32>());
+    assert_eq!(size_of::<Rotor>(), 4 * size_of::<f32>());
+    assert_eq!(size_of::<Line>(), 8 * size_of::<f32>());
+    assert_eq!(size_of::<Motor>(), 8 * size_of::<f32>());
+    assert_eq!(size_of::<double::Point>(), 4 * size_of::<f64>());
+    assert_eq!(size_of::<double::Motor>(), 8 * size_of::<f64>());
+  }
+
+  #[cfg(feature = "bytemuck")]
+  #[test] fn casts_in_component_order() {
+    let points = [point(1.0, 2.0, 3.0), point(4.0, 5.0, 6.0) * 2.0];
+    assert_eq!(bytemuck::cast_slice::<Point, f32>(&points), &[1.0, 1.0, 2.0, 3.0, 2.0, 8.0, 10.0, 12.0]);
+    let p = plane(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(bytemuck::cast::<Plane, [f32;4]>(p), [p.d(), p.a(), p.b(), p.c()]);
+    let r = rotor(1.0, 1.0, 2.0, 3.0);
+    assert_eq!(bytemuck::cast::<Rotor, [f32;4]>(r), [r.scalar(), r.e23(), r.e31(), r.e12()]);
+    let l = line(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+    assert_eq!(bytemuck::cast::<Line, [f32;8]>(l), [0.0, l.e23(), l.e31(), l.e12(), 0.0, l.e01(), l.e02(), l.e03()]);
+    let m = rotor(1.0, 1.0, 2.0, 3.0) * translator(2.0, 3.0, 2.0, 1.0);
+    assert_eq!(bytemuck::cast::<Motor, [f32;8]>(m), [m.scalar(), m.e23(), m.e31(), m.e12(), m.e0123(), m.e01(), m.e02(), m.e03()]);
+    assert_eq!(<Motor as bytemuck::Zeroable>::zeroed(), Motor::default());
+    // And back from a buffer
+    assert_eq!(bytemuck::cast_slice::<f32, Point>(&[1.0, 1.0, 2.0, 3.0]), &[point(1.0, 2.0, 3.0)]);
+  }
+}
@@ -0,0 +1,43 @@
+// Every element prints in blade notation and parses back to itself.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::*;
+
+  fn mv<T:Into<Multivector>>(x:T)->Multivector { x.into() }
+
+  macro_rules! round_trip {
+    ($($t:ident),*) => {$(
+      forall(concat!(stringify!($t), " notation"), |rng| {
+        let a = $t::random(rng);
+        let (plain, unicode) = (a.to_string(), format!("{:#}", a));
+        match (plain.parse::<$t>(), unicode.parse::<$t>()) {
+          (Ok(b), Ok(c)) if b == a && c == a => None,
+          other => Some(format!("{} and {} became {:?}", plain, unicode, other)),
+        }
+      });
+    )*}
+  }
+
+  #[test] fn elements_print_and_parse_back() {
+    round_trip!(Plane, Point, Direction, Line, Branch, Horizon, Motor, Rotor, Translator, Flector, Dual);
+    forall("multivector notation", |rng| {
+      let m = mv(Motor::random(rng)) + Flector::random(rng);
+      if m.to_string().parse::<Multivector>() == Ok(m) { None } else { Some(m.to_string()) }
+    });
+  }
+
+  #[test] fn precision() {
+    let m = Motor::from(rotor(pi / 2.0, 0.0, 0.0, 1.0)) * translator(2.0, 1.0, 0.0, 0.0);
+    assert_eq!(format!("{:.3}", m), "0.707 + 0.707e12 - 0.707e01 + 0.707e02");
+    assert_eq!(format!("{:#.1}", point(1.0, 2.0, 3.0)), "1.0e₀₃₂ + 2.0e₀₁₃ + 3.0e₀₂₁ + 1.0e₁₂₃");
+  }
+
+  #[test] fn blades_outside_the_element() {
+    assert_eq!("0.7 + 0.7e12 + 1e01".parse::<Rotor>(), Err(ParseError::NotInElement{blade: "e01".into(), element: "Rotor"}));
+    assert_eq!("1e₀₃₂".parse::<Plane>(), Err(ParseError::NotInElement{blade: "e₀₃₂".into(), element: "Plane"}));
+    assert_eq!("1e1 + 1e123".parse::<Plane>().unwrap_err().to_string(), "Plane has no e123 component");
+  }
+}
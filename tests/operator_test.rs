@@ -0,0 +1,152 @@
+// Every operator between two elements agrees with the same product of general
+// multivectors, and dividing by an element is undone by multiplying with it.
+mod common;
+
+#[cfg(test)]
+mod tests {
+  use g3::*;
+  use super::common::screw;
+
+  fn mv<T:Into<Multivector>>(x:T)->Multivector { x.into() }
+  fn same(name:&str, a:Multivector, b:Multivector) { assert!(a.approx_eq(b, 0.000_1), "{}: {:?} ≉ {:?}", name, a, b) }
+
+  // Two elements of every kind, the second one for products of an element with its own kind.
+  trait Sample { fn first()->Self; fn second()->Self; }
+  impl Sample for Plane { fn first()->Self { plane(1.0, -2.0, 0.5, 3.0) } fn second()->Self { plane(-0.3, 1.0, 2.0, -1.0) } }
+  impl Sample for Line {
+    fn first()->Self { point(1.0, 2.0, -3.0) & point(-0.5, 0.2, 4.0) }
+    fn second()->Self { point(0.5, -1.0, 2.0) & point(2.0, 1.0, 1.0) }
+  }
+  impl Sample for Branch { fn first()->Self { branch(0.3, -0.7, 1.1) } fn second()->Self { branch(-1.0, 0.2, 0.5) } }
+  impl Sample for Horizon { fn first()->Self { horizon(0.5, 1.0, -2.0) } fn second()->Self { horizon(-1.0, 0.3, 0.8) } }
+  impl Sample for Point { fn first()->Self { point(1.0, 2.0, -3.0) * 1.5 } fn second()->Self { point(-0.5, 0.2, 4.0) } }
+  impl Sample for Direction { fn first()->Self { Direction::new(1.0, -2.0, 0.5) } fn second()->Self { Direction::new(0.3, 1.0, 1.0) } }
+  impl Sample for Dual { fn first()->Self { dual(2.0, -0.5) } fn second()->Self { dual(-1.5, 0.75) } }
+  impl Sample for Rotor { fn first()->Self { rotor(0.7, 1.0, -2.0, 0.5) } fn second()->Self { rotor(-1.3, 0.2, 1.0, 1.0) } }
+  impl Sample for Translator { fn first()->Self { translator(1.5, 1.0, 2.0, -1.0) } fn second()->Self { translator(-0.5, 0.0, 1.0, 3.0) } }
+  impl Sample for Motor {
+    fn first()->Self { screw() }
+    fn second()->Self { Motor::from_screw_axis(-0.4, 1.0, line(-1.0, 2.0, 0.0, 0.0, 1.0, 1.0).normalized()) }
+  }
+
+  macro_rules! check {
+    ($name:ident, $a:ident, $b:ident, /) => { same(stringify!($name /), mv(<$a as Sample>::first() / <$b as Sample>::second()) * mv(<$b as Sample>::second()), mv(<$a as Sample>::first())) };
+    ($name:ident, $a:ident, $b:ident, $op:tt) => { same(stringify!($name $op), mv(<$a as Sample>::first() $op <$b as Sample>::second()), mv(<$a as Sample>::first()) $op mv(<$b as Sample>::second())) };
+  }
+
+  macro_rules! pairs {
+    ($($name:ident: $a:ident, $b:ident => [$($op:tt)*];)*) => {$(
+      #[test] fn $name() { $(check!($name, $a, $b, $op);)* }
+    )*}
+  }
+
+  // The translator between two points is normalized, so is their quotient.
+  #[test] fn point_point_translator() {
+    let (a, b) = (Point::first(), Point::second());
+    let ab = mv(a) * mv(b);
+    same("point_point *", mv(a * b), ab / ab.scalar());
+    same("point_point /", mv(a / b) * mv(b), mv(a.normalized()) * b.w());
+  }
+
+  pairs! {
+    plane_plane: Plane, Plane => [* ^ | /];
+    plane_line: Plane, Line => [* ^ | /];
+    plane_branch: Plane, Branch => [* ^ | /];
+    plane_horizon: Plane, Horizon => [* ^ |];
+    plane_point: Plane, Point => [* ^ & | /];
+    plane_direction: Plane, Direction => [* ^ & |];
+    plane_dual: Plane, Dual => [* ^ & | /];
+    plane_rotor: Plane, Rotor => [* ^ | /];
+    plane_translator: Plane, Translator => [* ^ | /];
+    plane_motor: Plane, Motor => [* ^ & | /];
+    line_plane: Line, Plane => [* ^ | /];
+    line_line: Line, Line => [* ^ & | /];
+    line_branch: Line, Branch => [* ^ & | /];
+    line_horizon: Line, Horizon => [* ^ &];
+    line_point: Line, Point => [* & | /];
+    line_direction: Line, Direction => [* & |];
+    line_dual: Line, Dual => [* ^ & | /];
+    line_rotor: Line, Rotor => [* ^ & | /];
+    line_translator: Line, Translator => [* ^ & | /];
+    line_motor: Line, Motor => [* ^ & | /];
+    branch_plane: Branch, Plane => [* ^ | /];
+    branch_line: Branch, Line => [* ^ & | /];
+    branch_branch: Branch, Branch => [* | /];
+    branch_horizon: Branch, Horizon => [* ^ &];
+    branch_point: Branch, Point => [* & | /];
+    branch_direction: Branch, Direction => [* & |];
+    branch_dual: Branch, Dual => [* ^ & | /];
+    branch_rotor: Branch, Rotor => [* ^ | /];
+    branch_translator: Branch, Translator => [* ^ & | /];
+    branch_motor: Branch, Motor => [* ^ & | /];
+    horizon_plane: Horizon, Plane => [* ^ | /];
+    horizon_line: Horizon, Line => [* ^ & /];
+    horizon_branch: Horizon, Branch => [* ^ & /];
+    horizon_point: Horizon, Point => [* & /];
+    horizon_direction: Horizon, Direction => [&];
+    horizon_dual: Horizon, Dual => [* ^ & | /];
+    horizon_rotor: Horizon, Rotor => [* ^ & | /];
+    horizon_translator: Horizon, Translator => [* ^ | /];
+    horizon_motor: Horizon, Motor => [* ^ & | /];
+    point_plane: Point, Plane => [* ^ & | /];
+    point_line: Point, Line => [* & | /];
+    point_branch: Point, Branch => [* & | /];
+    point_horizon: Point, Horizon => [* &];
+    point_point: Point, Point => [& |];
+    point_direction: Point, Direction => [* &];
+    point_dual: Point, Dual => [* ^ & | /];
+    point_rotor: Point, Rotor => [* ^ & | /];
+    point_translator: Point, Translator => [* ^ & | /];
+    point_motor: Point, Motor => [* ^ & | /];
+    direction_plane: Direction, Plane => [* ^ & | /];
+    direction_line: Direction, Line => [* & | /];
+    direction_branch: Direction, Branch => [* & | /];
+    direction_horizon: Direction, Horizon => [&];
+    direction_point: Direction, Point => [* & /];
+    direction_direction: Direction, Direction => [&];
+    direction_dual: Direction, Dual => [* ^ & | /];
+    direction_rotor: Direction, Rotor => [* ^ & | /];
+    direction_translator: Direction, Translator => [* ^ & | /];
+    direction_motor: Direction, Motor => [* ^ & | /];
+    dual_plane: Dual, Plane => [* ^ & | /];
+    dual_line: Dual, Line => [* ^ & | /];
+    dual_branch: Dual, Branch => [* ^ & | /];
+    dual_horizon: Dual, Horizon => [* ^ & |];
+    dual_point: Dual, Point => [* ^ & | /];
+    dual_direction: Dual, Direction => [* ^ & |];
+    dual_dual: Dual, Dual => [* ^ & | /];
+    dual_rotor: Dual, Rotor => [* ^ & | /];
+    dual_translator: Dual, Translator => [* ^ & | /];
+    dual_motor: Dual, Motor => [* ^ & | /];
+    rotor_plane: Rotor, Plane => [* ^ | /];
+    rotor_line: Rotor, Line => [* ^ & | /];
+    rotor_branch: Rotor, Branch => [* ^ | /];
+    rotor_horizon: Rotor, Horizon => [* ^ & |];
+    rotor_point: Rotor, Point => [* ^ & | /];
+    rotor_direction: Rotor, Direction => [* ^ & |];
+    rotor_dual: Rotor, Dual => [* ^ & | /];
+    rotor_rotor: Rotor, Rotor => [* ^ | /];
+    rotor_translator: Rotor, Translator => [* ^ & | /];
+    rotor_motor: Rotor, Motor => [* ^ & | /];
+    translator_plane: Translator, Plane => [* ^ | /];
+    translator_line: Translator, Line => [* ^ & | /];
+    translator_branch: Translator, Branch => [* ^ & | /];
+    translator_horizon: Translator, Horizon => [* ^ |];
+    translator_point: Translator, Point => [* ^ & | /];
+    translator_direction: Translator, Direction => [* ^ & |];
+    translator_dual: Translator, Dual => [* ^ & | /];
+    translator_rotor: Translator, Rotor => [* ^ & | /];
+    translator_translator: Translator, Translator => [* ^ | /];
+    translator_motor: Translator, Motor => [* ^ & | /];
+    motor_plane: Motor, Plane => [* ^ & | /];
+    motor_line: Motor, Line => [* ^ & | /];
+    motor_branch: Motor, Branch => [* ^ & | /];
+    motor_horizon: Motor, Horizon => [* ^ & |];
+    motor_point: Motor, Point => [* ^ & | /];
+    motor_direction: Motor, Direction => [* ^ & |];
+    motor_dual: Motor, Dual => [* ^ & | /];
+    motor_rotor: Motor, Rotor => [* ^ & | /];
+    motor_translator: Motor, Translator => [* ^ & | /];
+    motor_motor: Motor, Motor => [* ^ & | /];
+  }
+}
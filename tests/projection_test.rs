@@ -1,41 +1,56 @@
+// Projections on random elements end up incident with what they are projected
+// onto, at the right distance or parallel to the original.
+mod common;
+
 #[cfg(test)]
 mod tests {
-  use g3::{plane,point,line};
+  use g3::*;
+  use super::common::*;
+
+  fn normal(p:Plane)->Plane { plane(p.a(), p.b(), p.c(), 0.0).normalized() }
+  fn direction(l:Line)->Branch { Branch::from(l.normalized()) }
+  // Equal up to orientation.
+  fn parallel<T:Into<Multivector> + std::ops::Mul<f32, Output = T> + Copy>(a:T, b:T)->Option<String> { close(a, b).and(close(a, b * -1.0)) }
 
-  #[test] #[ignore] fn project_point_onto_line() {
-    let l = line(1.0, 2.0, 3.0, 1.0, 2.0, 3.0);
-    let a = point(1.0, 2.0, 3.0);
-    let _ = l.project_point(a);
-    todo!()
+  #[test] fn project_point_onto_line() {
+    forall("point onto line", |rng| {
+      let (a, l) = (Point::random(rng), Line::random(rng));
+      let q = a.project_line(l);
+      close1(distance(q, l), 0.0).or(close1(distance(a, q), distance(a, l)))
+    });
   }
-  #[test] #[ignore] fn project_plane_onto_line() {
-    let l = line(1.0, 2.0, 3.0, 1.0, 2.0, 3.0);
-    let p = plane(1.0, 2.0, 3.0, 4.0);
-    let _ = l.project_plane(p);
-    todo!()
+  #[test] fn project_plane_onto_line() {
+    forall("plane onto line", |rng| {
+      let (p, l) = (Plane::random(rng), Line::random(rng));
+      close(l ^ p.project_line(l), Point::default())
+    });
   }
-  #[test] #[ignore] fn project_point_onto_plane() {
-    let p = plane(1.0, 2.0, 3.0, 4.0);
-    let a = point(1.0, 2.0, 3.0);
-    let _ = p.project_point(a);
-    todo!()
+  #[test] fn project_point_onto_plane() {
+    forall("point onto plane", |rng| {
+      let (a, p) = (Point::random(rng), Plane::random(rng));
+      let q = a.project_plane(p);
+      close1(distance(q, p), 0.0).or(close1(distance(a, q), distance(a, p).abs()))
+    });
   }
-  #[test] #[ignore] fn project_line_onto_plane() {
-    let p = plane(1.0, 2.0, 3.0, 4.0);
-    let l = line(1.0, 2.0, 3.0, 1.0, 2.0, 3.0);
-    let _ = p.project_line(l);
-    todo!()
+  #[test] fn project_line_onto_plane() {
+    forall("line onto plane", |rng| {
+      let (a, b, p) = (Point::random(rng), Point::random(rng), Plane::random(rng));
+      let q = (a & b).project_plane(p);
+      close(q ^ p, Point::default()).or(close1(distance(a.project_plane(p), q), 0.0))
+    });
   }
-  #[test] #[ignore] fn project_plane_onto_point() {
-    let p = plane(1.0, 2.0, 3.0, 4.0);
-    let a = point(1.0, 2.0, 3.0);
-    let _ = a.project_plane(p);
-    todo!()
+  #[test] fn project_plane_onto_point() {
+    forall("plane onto point", |rng| {
+      let (p, a) = (Plane::random(rng), Point::random(rng));
+      let q = p.project_point(a);
+      close1(distance(a, q), 0.0).or(parallel(normal(q), normal(p)))
+    });
   }
-  #[test] #[ignore] fn project_line_onto_point() {
-    let p = plane(1.0, 2.0, 3.0, 4.0);
-    let l = line(1.0, 2.0, 3.0, 1.0, 2.0, 3.0);
-    let _ = p.project_line(l);
-    todo!()
+  #[test] fn project_line_onto_point() {
+    forall("line onto point", |rng| {
+      let (l, a) = (Line::random(rng), Point::random(rng));
+      let q = l.project_point(a);
+      close1(distance(a, q), 0.0).or(parallel(direction(q), direction(l)))
+    });
   }
 }
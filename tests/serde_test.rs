@@ -0,0 +1,70 @@
+// Elements survive JSON by named components and bincode as arrays, run with
+// `--features serde`.
+mod common;
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod tests {
+  use g3::*;
+  use super::common::*;
+  use serde::{Serialize, de::DeserializeOwned};
+
+  fn round_trip<T:Random + Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(name:&str) {
+    forall(name, |rng| {
+      let a = T::random(rng);
+      let json:T = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+      let binary:T = bincode::deserialize(&bincode::serialize(&a).unwrap()).unwrap();
+      if json != a || binary != a { Some(format!("{:?} became {:?} and {:?}", a, json, binary)) } else { None }
+    });
+  }
+
+  #[test] fn elements_round_trip() {
+    round_trip::<Plane>("plane");
+    round_trip::<Point>("point");
+    round_trip::<Direction>("direction");
+    round_trip::<Line>("line");
+    round_trip::<Branch>("branch");
+    round_trip::<Horizon>("horizon");
+    round_trip::<Dual>("dual");
+    round_trip::<Rotor>("rotor");
+    round_trip::<Translator>("translator");
+    round_trip::<Motor>("motor");
+    round_trip::<Flector>("flector");
+  }
+
+  #[test] fn named_components() {
+    assert_eq!(serde_json::to_string(&point(1.0, 2.0, 3.0)).unwrap(), r#"{"x":1.0,"y":2.0,"z":3.0,"w":1.0}"#);
+    assert_eq!(serde_json::to_string(&plane(1.0, 2.0, 3.0, 4.0)).unwrap(), r#"{"a":1.0,"b":2.0,"c":3.0,"d":4.0}"#);
+    assert_eq!(serde_json::to_value(translator(2.0, 0.0, 1.0, 0.0)).unwrap(), serde_json::json!({"e01":0.0,"e02":-1.0,"e03":0.0}));
+    let m = rotor(pi / 2.0, 0.0, 0.0, 1.0) * translator(2.0, 1.0, 0.0, 0.0);
+    let json = serde_json::to_value(m).unwrap();
+    for (name, x) in [("scalar", m.scalar()), ("e23", m.e23()), ("e31", m.e31()), ("e12", m.e12()),
+      ("e01", m.e01()), ("e02", m.e02()), ("e03", m.e03()), ("e0123", m.e0123())] {
+      assert_eq!(json[name].as_f64(), Some(x as f64), "{}", name);
+    }
+    let ea = EulerAngles{roll: 0.5, pitch: 0.0, yaw: -1.0};
+    assert_eq!(serde_json::to_string(&ea).unwrap(), r#"{"roll":0.5,"pitch":0.0,"yaw":-1.0}"#);
+    assert_eq!(serde_json::from_str::<EulerAngles>(&serde_json::to_string(&ea).unwrap()).unwrap(), ea);
+  }
+
+  #[test] fn compact_arrays() {
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Pose { #[serde(with = "g3::compact")] at:Point, #[serde(with = "g3::compact")] turn:Rotor }
+    let pose = Pose{at: point(1.0, 2.0, 3.0), turn: rotor(0.0, 1.0, 0.0, 0.0)};
+    let json = serde_json::to_string(&pose).unwrap();
+    assert_eq!(json, r#"{"at":[1.0,2.0,3.0,1.0],"turn":[1.0,0.0,0.0,0.0]}"#);
+    assert_eq!(serde_json::from_str::<Pose>(&json).unwrap(), pose);
+    // Binary formats write the bare components
+    assert_eq!(bincode::serialize(&point(1.0, 2.0, 3.0)).unwrap().len(), 16);
+    // The array is read where names are expected too
+    assert_eq!(serde_json::from_str::<Point>("[1.0,2.0,3.0,1.0]").unwrap(), point(1.0, 2.0, 3.0));
+  }
+
+  #[test] fn malformed_input() {
+    assert!(serde_json::from_str::<Point>(r#"{"x":1.0,"y":2.0,"z":3.0}"#).unwrap_err().to_string().contains("missing field `w`"));
+    assert!(serde_json::from_str::<Point>(r#"{"x":1.0,"y":2.0,"z":3.0,"w":1.0,"v":0.0}"#).unwrap_err().to_string().contains("unknown field `v`"));
+    assert!(serde_json::from_str::<Point>(r#"{"x":1.0,"x":2.0,"z":3.0,"w":1.0}"#).unwrap_err().to_string().contains("duplicate field `x`"));
+    assert!(serde_json::from_str::<Point>("[1.0,2.0,3.0]").is_err());
+    assert!(serde_json::from_str::<Point>("[1.0,2.0,3.0,1.0,0.0]").is_err());
+  }
+}